@@ -0,0 +1,346 @@
+use crate::gamestate::toggle;
+use crate::prelude::*;
+use std::sync::LazyLock;
+
+/// Console commands. Type `help` in the console for a list.
+pub(crate) static COMMANDS: LazyLock<Commands<GameState>> = LazyLock::new(commands);
+
+type Cmd = Command<GameState>;
+
+fn commands() -> Commands<GameState> {
+    use ArgType::*;
+    Commands::new()
+        .register(
+            Cmd::new("pause", |g, _| {
+                toggle(&mut g.paused);
+                Ok(())
+            })
+            .help("pause/unpause simulation"),
+        )
+        .register(
+            Cmd::new("reset", |g, _| {
+                g.reset();
+                Ok(())
+            })
+            .help("restart from the initial world"),
+        )
+        .register(
+            Cmd::new("select", |g, args| {
+                g.selected_critter = Some(args.get(0)?);
+                Ok(())
+            })
+            .alias("sel")
+            .arg("critter", Int)
+            .help("select critter by index"),
+        )
+        .register(
+            Cmd::new("get", |g, args| {
+                g.console.print(g.get_path(&g.expand_path(args.str(0)?)?)?);
+                Ok(())
+            })
+            .arg("path", Str)
+            .help("print value at path (`sel` = selected critter)"),
+        )
+        .register(
+            Cmd::new("set", |g, args| g.set_path(&g.expand_path(args.str(0)?)?, args.str(1)?))
+                .arg("path", Str)
+                .arg("value", Rest)
+                .help("set value at path, `*` matches all (e.g. sel.body.springs.*.k)"),
+        )
+        .register(Cmd::new("ls", |g, args| g.list_fields(&g.expand_path(args.str(0).unwrap_or_default())?)).opt_arg("path", Str).help("list fields at path"))
+        .register(
+            Cmd::new("new", |g, args| {
+                *g.selected_critter_mut()? = Critter::new(args.get(0)?, g.selected_critter()?.brain.size().x());
+                Ok(())
+            })
+            .alias("n")
+            .arg("bones", Int)
+            .help("replace selected critter by a new one"),
+        )
+        .register(
+            Cmd::new("angle", |g, args| {
+                let a = f32::sin(args.get(0)?);
+                g.selected_critter_mut()?.body.springs.iter_mut().for_each(|s| s.angle_setpoint = a);
+                Ok(())
+            })
+            .arg("radians", Float)
+            .help("set all spring angle setpoints of the selected critter"),
+        )
+        .register(
+            Cmd::new("mouse", |g, args| {
+                match args.get_opt(0)? {
+                    Some(v) => g.follow_mouse = v,
+                    None => toggle(&mut g.follow_mouse),
+                };
+                Ok(())
+            })
+            .opt_arg("on", Bool)
+            .help("selected critter follows mouse"),
+        )
+        .register(
+            Cmd::new("tool", |g, args| {
+                match args.get_opt::<String>(0)? {
+                    Some(tool) => g.tool = tool.parse()?,
+                    None => g.console.print(format!("{} (have: {})", g.tool.name(), Tool::ALL.map(Tool::name).join(", "))),
+                }
+                Ok(())
+            })
+            .opt_arg("name", Str)
+            .help("set (or print) the mouse tool, or press 1-7"),
+        )
+        .register(
+            Cmd::new("mousefood", |g, _| {
+                g.tool = if g.tool == Tool::Feed { Tool::Select } else { Tool::Feed };
+                Ok(())
+            })
+            .alias("mf")
+            .help("toggle between the feed and select tools"),
+        )
+        .register(
+            Cmd::new("zoom", |g, args| {
+                match args.get_opt(0)? {
+                    Some(zoom) => g.camera.set_zoom(zoom),
+                    None => g.console.print(format!("{}", g.camera.camera.zoom)),
+                }
+                Ok(())
+            })
+            .opt_arg("factor", Float)
+            .help("set (or print) camera zoom, also: mouse wheel"),
+        )
+        .register(
+            Cmd::new("rotate", |g, args| {
+                g.camera.camera.rot = args.get::<f32>(0)?.to_radians();
+                Ok(())
+            })
+            .arg("degrees", Float)
+            .help("set camera rotation"),
+        )
+        .register(
+            Cmd::new("follow", |g, args| {
+                match args.get_opt(0)? {
+                    Some(v) => g.camera.follow = v,
+                    None => toggle(&mut g.camera.follow),
+                };
+                Ok(())
+            })
+            .opt_arg("on", Bool)
+            .help("camera follows the selected critter (until panned with the middle mouse button)"),
+        )
+        .register(
+            Cmd::new("bloom", |g, args| {
+                match args.get_opt(0)? {
+                    Some(v) => g.post_fx.bloom = v,
+                    None => toggle(&mut g.post_fx.bloom),
+                };
+                Ok(())
+            })
+            .opt_arg("on", Bool)
+            .help("glow around bright things"),
+        )
+        .register(
+            Cmd::new("vignette", |g, args| {
+                match args.get_opt(0)? {
+                    Some(v) => g.post_fx.vignette = v,
+                    None => toggle(&mut g.post_fx.vignette),
+                };
+                Ok(())
+            })
+            .opt_arg("on", Bool)
+            .help("darken the screen edges"),
+        )
+        .register(
+            Cmd::new("home", |g, _| {
+                g.home_camera();
+                Ok(())
+            })
+            .help("reset camera"),
+        )
+        .register(
+            Cmd::new("plot", |g, args| {
+                match args.get_opt::<String>(0)? {
+                    Some(metric) => _ = g.metrics.toggle_plot(&metric)?,
+                    None => g.console.print(format!("have: {}", g.metrics.names().collect_vec().join(", "))),
+                }
+                Ok(())
+            })
+            .opt_arg("metric", Str)
+            .help("show/hide a real-time plot (or list metrics)"),
+        )
+        .register(
+            Cmd::new("dt", |g, args| {
+                g.dt = args.get(0)?;
+                Ok(())
+            })
+            .arg("dt", Float)
+            .help("physics time step"),
+        )
+        .register(
+            Cmd::new("speed", |g, args| {
+                g.speed = args.get(0)?;
+                Ok(())
+            })
+            .arg("n", Int)
+            .help("simulation steps per frame"),
+        )
+        .register(
+            Cmd::new("scrollback", |g, args| {
+                g.console.set_scrollback(args.get(0)?);
+                Ok(())
+            })
+            .arg("lines", Int)
+            .help("number of console output lines to keep"),
+        )
+        .register(
+            Cmd::new("exec", |g, args| {
+                g.exec_file(args.str(0)?);
+                Ok(())
+            })
+            .arg("file", Str)
+            .help("run commands from a file (one per line, # comments)"),
+        )
+        .register(
+            Cmd::new("echo", |g, args| {
+                g.console.print(args.str(0).unwrap_or_default());
+                Ok(())
+            })
+            .opt_arg("text", Rest)
+            .help("print text (e.g. a $variable)"),
+        )
+        .register(
+            Cmd::new("var", |g, args| {
+                match (args.str(0), args.str(1)) {
+                    (Ok(name), Ok(value)) => drop(g.script.vars.insert(name.into(), unquote(value).into())),
+                    (Ok(name), Err(_)) => g.console.print(g.script.vars.get(name).ok_or_else(|| anyhow!("undefined variable: {name}"))?),
+                    _ => g.script.vars.iter().for_each(|(k, v)| g.console.print(format!("{k} = {v}"))),
+                }
+                Ok(())
+            })
+            .opt_arg("name", Str)
+            .opt_arg("value", Rest)
+            .help("list, show or define variables, used as $name"),
+        )
+        .register(
+            Cmd::new("alias", |g, args| {
+                match (args.str(0), args.str(1)) {
+                    (Ok(name), Ok(body)) => drop(g.script.aliases.insert(name.into(), unquote(body).into())),
+                    (Ok(name), Err(_)) => g.console.print(g.script.aliases.get(name).ok_or_else(|| anyhow!("undefined alias: {name}"))?),
+                    _ => g.script.aliases.iter().for_each(|(k, v)| g.console.print(format!("{k} = {v}"))),
+                }
+                Ok(())
+            })
+            .opt_arg("name", Str)
+            .opt_arg("commands", Rest)
+            .help("list, show or define aliases, e.g. alias setup \"speed 4; dt 0.02\""),
+        )
+        .register(
+            Cmd::new("unalias", |g, args| g.script.aliases.remove(args.str(0)?).map(drop).ok_or_else(|| anyhow!("undefined alias")))
+                .arg("name", Str)
+                .help("remove an alias"),
+        )
+        .register(
+            Cmd::new("bind", |g, args| {
+                let chord = args.get::<Chord>(0)?;
+                match args.get_opt::<Button>(1)? {
+                    Some(action) => {
+                        if let Some(prev) = g.keymap.bind(chord, action) {
+                            g.console.print(format!("{chord} was bound to {}", prev.as_str()));
+                        }
+                        g.store_keymap()?;
+                    }
+                    None => g.console.print(g.keymap.0.get(&chord).map(|a| a.as_str().to_string()).unwrap_or_else(|| "unbound".into())),
+                }
+                Ok(())
+            })
+            .arg("key", Str)
+            .opt_arg("action", Str)
+            .help("bind a key to an action (or print its binding), e.g. bind ctrl+shift+s quicksave"),
+        )
+        .register(
+            Cmd::new("unbind", |g, args| {
+                let chord = args.get::<Chord>(0)?;
+                g.keymap.unbind(chord).ok_or_else(|| anyhow!("{chord} is not bound"))?;
+                g.store_keymap()
+            })
+            .arg("key", Str)
+            .help("remove a key binding"),
+        )
+        .register(
+            Cmd::new("bindlist", |g, args| {
+                let filter = args.get_opt::<Button>(0)?;
+                for (chord, action) in g.keymap.iter().filter(|(_, a)| filter.is_none_or(|f| f == *a)).collect_vec() {
+                    g.console.print(format!("{:<12} {chord}", action.as_str()));
+                }
+                Ok(())
+            })
+            .opt_arg("action", Str)
+            .help("list key bindings (of an action)"),
+        )
+        .register(
+            Cmd::new("bindreset", |g, _| {
+                g.keymap = default_keymap();
+                g.store_keymap()
+            })
+            .help("restore the default key bindings"),
+        )
+        .register(
+            Cmd::new("save", |g, args| {
+                slot_key(args.str(0)?)?; // 👈 validate name early
+                g.requests.push(ShellRequest::Save { slot: args.str(0)?.into(), tick: g.tick });
+                Ok(())
+            })
+            .arg("name", Str)
+            .help("save to a named slot"),
+        )
+        .register(
+            Cmd::new("load", |g, args| {
+                g.requests.push(ShellRequest::Load { slot: args.str(0)?.into() });
+                Ok(())
+            })
+            .arg("name", Str)
+            .help("load from a named slot (e.g. autosave.0)"),
+        )
+        .register(
+            Cmd::new("list", |g, _| {
+                g.requests.push(ShellRequest::ListSaves);
+                Ok(())
+            })
+            .help("list save slots"),
+        )
+        .register(
+            Cmd::new("autosave", |g, args| {
+                match args.get_opt::<f64>(0)? {
+                    Some(minutes) => g.autosave.interval_secs = minutes * 60.0,
+                    None => g.console.print(format!("autosave every {} min to {} slots", g.autosave.interval_secs / 60.0, g.autosave.slots)),
+                }
+                Ok(())
+            })
+            .opt_arg("minutes", Float)
+            .help("autosave interval, 0 = off"),
+        )
+        .register(
+            Cmd::new("export", |g, args| {
+                let what = args.get::<ExportTarget>(0)?;
+                let path = args.str(1).map(str::to_owned).unwrap_or_else(|_| what.default_file());
+                let data = g.export_ron(what)?.into_bytes();
+                g.requests.push(ShellRequest::WriteFile { path, data });
+                Ok(())
+            })
+            .arg("game|world|critter", Str)
+            .opt_arg("file", Str)
+            .help("export as RON (web: download)"),
+        )
+        .register(
+            Cmd::new("import", |g, args| {
+                let what = args.get::<ExportTarget>(0)?;
+                let request = match args.str(1) {
+                    Ok(path) => ShellRequest::ReadFile { path: path.into() },
+                    Err(_) => ShellRequest::PickFile { path: what.default_file() },
+                };
+                g.load_file(request, PendingLoad::Import(what));
+                Ok(())
+            })
+            .arg("game|world|critter", Str)
+            .opt_arg("file", Str)
+            .help("import RON (web: upload if no file given). Critters are added"),
+        )
+}
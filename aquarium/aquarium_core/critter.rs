@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Reflect, Clone)]
 pub struct Critter {
     pub body: Contraption,
     pub brain: Brain,
@@ -143,21 +143,39 @@ impl Critter {
     }
 
     pub fn draw(&self, out: &mut Out) {
-        self.body.draw(out);
+        self.draw_body(out);
         self.draw_vision(out);
     }
 
+    /// 🐛 Smooth curve through the bones, round head.
+    fn draw_body(&self, out: &mut Out) {
+        let bones = &self.body.bones;
+        if bones.is_empty() {
+            return;
+        }
+        // 👇 outer end of the first and last bone: the one farthest from its neighbor.
+        let outer_end = |i: usize, neighbor: usize| {
+            let (a, b) = self.body.bone_ends(i);
+            let n = bones[neighbor].position;
+            if a.distance_to(n) > b.distance_to(n) { a } else { b }
+        };
+        let last = bones.len() - 1;
+        let spine = std::iter::once(outer_end(0, 1.min(last))).chain(bones.iter().map(|b| b.position)).chain([outer_end(last, last.saturating_sub(1))]).collect_vec();
+        out.draw_path(L_SPRITES, Path::smooth(&spine).with_color(RGBA::YELLOW).with_width(4));
+        out.draw_circle(L_SPRITES, Circle::filled(bones[0].position, 4.0, RGBA::YELLOW));
+    }
+
     fn draw_vision(&self, out: &mut Out) {
         let head = &self.body.bones[0];
         let matrix = head.rotation_matrix();
         let ey = vec2::from(matrix[0]);
         let ex = vec2::from(matrix[1]);
 
-        let start = head.position.as_f32();
+        let start = head.position;
         let len = 100.0;
         let forward = start + len * ey;
         let sideways = start + len * ex;
-        out.draw_line_screen(L_SPRITES + 2, Line::new(start.as_(), forward.as_()).with_color(RGBA::WHITE));
-        out.draw_line_screen(L_SPRITES + 2, Line::new(start.as_(), sideways.as_()).with_color(RGBA::YELLOW));
+        out.draw_line(L_SPRITES + 2, Line::new(start, forward).with_color(RGBA::WHITE));
+        out.draw_line(L_SPRITES + 2, Line::new(start, sideways).with_color(RGBA::YELLOW));
     }
 }
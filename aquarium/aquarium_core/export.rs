@@ -0,0 +1,120 @@
+//! 📝 Human-readable export/import (RON) of the game, the world or a single critter.
+use crate::prelude::*;
+use std::str::FromStr;
+
+/// What to export/import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExportTarget {
+    /// The full `GameState`.
+    Game,
+    World,
+    /// Export: the selected critter. Import: added to the world and selected.
+    Critter,
+}
+
+/// What to do with a file once the shell has loaded it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PendingLoad {
+    Exec,
+    Import(ExportTarget),
+}
+
+impl GameState {
+    pub(crate) fn export_ron(&self, what: ExportTarget) -> Result<String> {
+        let config = ron::ser::PrettyConfig::default();
+        Ok(match what {
+            ExportTarget::Game => ron::ser::to_string_pretty(self, config)?,
+            ExportTarget::World => ron::ser::to_string_pretty(&self.world, config)?,
+            ExportTarget::Critter => ron::ser::to_string_pretty(self.selected_critter()?, config)?,
+        })
+    }
+
+    pub(crate) fn import_ron(&mut self, what: ExportTarget, text: &str) -> Result<()> {
+        match what {
+            ExportTarget::Game => self.replace(ron::from_str(text)?), // 👈 keeps the console (and its output)
+            ExportTarget::World => self.world = ron::from_str(text)?,
+            ExportTarget::Critter => {
+                self.world.critters.push(ron::from_str(text)?);
+                self.selected_critter = Some(self.world.critters.len() - 1);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ExportTarget {
+    /// Default file name, e.g. `world.ron`.
+    pub(crate) fn default_file(self) -> String {
+        format!("{self}.ron")
+    }
+}
+
+impl FromStr for ExportTarget {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "game" => Ok(Self::Game),
+            "world" => Ok(Self::World),
+            "critter" | "sel" => Ok(Self::Critter),
+            _ => bail!("expected game, world or critter, got {s:?}"),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Game => "game",
+            Self::World => "world",
+            Self::Critter => "critter",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+    use std::mem::take;
+
+    #[gtest]
+    fn export_import_roundtrip() {
+        let mut g = GameState::new();
+        g.world.food.push(vec2(1.0, 2.0));
+        g.world.walls.push(vec![vec2(0.0, 0.0), vec2(10.0, 0.0)]);
+        g.speed = 3;
+        g.console.print("hello");
+
+        let ron = g.export_ron(ExportTarget::Game).unwrap();
+        let mut h = GameState::new();
+        h.import_ron(ExportTarget::Game, &ron).unwrap();
+        expect_that!(h.export_ron(ExportTarget::World).unwrap(), eq(&g.export_ron(ExportTarget::World).unwrap()));
+        expect_that!(h.speed, eq(3));
+        expect_that!(h.console.output.len(), eq(0)); // 👈 keeps its own console
+        h.console = take(&mut g.console);
+        expect_that!(h.export_ron(ExportTarget::Game).unwrap(), eq(&ron));
+
+        let world = g.export_ron(ExportTarget::World).unwrap();
+        let mut h = GameState::new();
+        h.import_ron(ExportTarget::World, &world).unwrap();
+        expect_that!(h.export_ron(ExportTarget::World).unwrap(), eq(&world));
+
+        let critter = g.export_ron(ExportTarget::Critter).unwrap();
+        h.import_ron(ExportTarget::Critter, &critter).unwrap();
+        expect_that!(h.world.critters.len(), eq(2));
+        expect_that!(h.selected_critter, some(eq(1)));
+        expect_that!(h.export_ron(ExportTarget::Critter).unwrap(), eq(&critter));
+
+        expect_that!(h.import_ron(ExportTarget::World, "(critters: 3)"), err(anything()));
+    }
+
+    #[gtest]
+    fn cancelled_pick_does_not_leak() {
+        let mut g = GameState::new();
+        for _ in 0..3 {
+            g.load_file(ShellRequest::PickFile { path: "world.ron".into() }, PendingLoad::Import(ExportTarget::World));
+        }
+        expect_that!(g.pending_loads, eq(&[("world.ron".to_owned(), PendingLoad::Import(ExportTarget::World))]));
+    }
+}
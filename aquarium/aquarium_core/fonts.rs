@@ -0,0 +1,19 @@
+//! 🔤 Fonts embedded in the game (their glyph atlases are in the texture atlas, see `atlas_builder`).
+use crate::prelude::*;
+use std::sync::LazyLock;
+
+/// DejaVu Sans, 16px, baked with `atlas_builder --font`. For labels and the UI.
+pub static DEJAVU_SANS: LazyLock<Font> = LazyLock::new(|| Font::from_bmfont(include_str!("../assets/dejavu_sans.fnt")).expect("dejavu_sans.fnt"));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn dejavu_sans() {
+        expect_that!(DEJAVU_SANS.sprite, eq(sprite!("dejavu_sans")));
+        expect_that!(DEJAVU_SANS.size, eq(16.0));
+        expect_that!(DEJAVU_SANS.glyphs.contains_key(&'é'), eq(true)); // 👈 beyond ASCII
+    }
+}
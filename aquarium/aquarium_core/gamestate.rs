@@ -1,6 +1,7 @@
 use crate::prelude::*;
+use std::mem::take;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Reflect)]
 pub struct GameState {
     pub now_secs: f64,
     pub dt: f32,
@@ -8,33 +9,89 @@ pub struct GameState {
     pub tick: u64,
     pub paused: bool,
 
+    /// Persisted separately, see `store_keymap`.
+    #[serde(skip, default = "default_keymap")]
+    #[reflect(skip)]
     pub keymap: Keymap,
     #[serde(skip)]
+    #[reflect(skip)]
     pub inputs: Inputs,
+    #[reflect(skip)]
     pub console: Console,
+    #[reflect(skip)]
+    pub inspector: Inspector,
+    #[reflect(skip)]
+    pub script: Script,
+    #[reflect(skip)]
+    pub autosave: Autosave,
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub camera: CameraControl,
+    /// Post-processing effects, see `Out::bloom`.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub post_fx: PostFx,
+    /// Simulation controls panel.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub ui: UiState,
+    /// Recent population, energy, speed: see `record_metrics` and the `plot` command.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub metrics: Metrics,
+    /// Canvas size, as of the current tick.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) viewport: vec2u,
+    /// Requests to the shell (file I/O, saving), forwarded to `Out` at the end of the tick.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) requests: Vec<ShellRequest>,
+    /// Files requested from the shell, and what to do with them when they arrive.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) pending_loads: Vec<(String, PendingLoad)>,
+    /// Has the autoexec script been requested?
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) started: bool,
 
     pub world: World,
 
     // commands and keypresses control this contraption.
     pub selected_critter: Option<usize>,
     pub follow_mouse: bool,
-    pub food_follows_mouse: bool,
+    #[reflect(skip)]
+    pub tool: Tool,
+    /// What's under the mouse.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) hover: Option<Pick>,
+    /// What's being dragged with the mouse.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) dragging: Option<Pick>,
+    /// Index of the wall being drawn with `Tool::Wall`.
+    #[serde(skip)]
+    #[reflect(skip)]
+    pub(crate) drawing_wall: Option<usize>,
+    /// Randomness for tools (e.g. `Tool::Spray`). Advances even while paused, unlike `tick`.
+    #[serde(skip, default = "tool_rng")]
+    #[reflect(skip)]
+    pub(crate) rng: ChaCha8Rng,
     // filter for smooth manual control
     mouse_filter: [vec2f; 3],
 }
 
+/// Width (pixels) of the simulation controls panel.
+const CONTROLS_WIDTH: f32 = 200.0;
+
 impl GameState {
     pub fn new() -> Self {
-        let keymap = Keymap::from([
-            (button!("tab"), K_CLI), // macroquad
-            (K_ARROW_DOWN, K_DOWN),
-            (K_ARROW_LEFT, K_LEFT),
-            (K_ARROW_RIGHT, K_RIGHT),
-            (K_ARROW_UP, K_UP),
-            (K_SPACE, K_TICK),
-        ]);
+        let keymap = default_keymap();
 
         let console = Console::with_hotkey(K_CLI);
+        let inspector = Inspector::with_hotkey(K_INSPECT);
 
         let world = World::test1();
         log::info!("new...");
@@ -47,11 +104,26 @@ impl GameState {
             keymap,
             inputs: default(),
             console,
+            inspector,
+            script: default(),
+            autosave: default(),
+            camera: default(),
+            post_fx: default(),
+            ui: UiState::default().with(|ui| ui.style.text = TextStyle::new(&DEJAVU_SANS)),
+            metrics: default(),
+            viewport: default(),
+            requests: default(),
+            pending_loads: default(),
+            started: false,
             world,
             mouse_filter: default(),
             selected_critter: Some(0),
             follow_mouse: false,
-            food_follows_mouse: true, // <<
+            tool: Tool::Select,
+            hover: None,
+            dragging: None,
+            drawing_wall: None,
+            rng: tool_rng(),
             dt: 0.05,
             speed: 1,
         }
@@ -60,21 +132,85 @@ impl GameState {
     fn tick_and_draw(&mut self, now_micros: u64, events: impl Iterator<Item = shell_api::InputEvent>, out: &mut shell_api::Out) {
         self.update_inputs(now_micros, events);
 
-        self.console.tick_and_draw(&self.inputs, out).map(|cmd| self.exec_command(&cmd));
+        self.viewport = out.viewport_size;
+        if !self.started {
+            self.started = true;
+            self.home_camera();
+            self.fetch_keymap();
+            self.exec_file(AUTOEXEC);
+        }
+        self.handle_loaded_files();
+        self.handle_fetched();
+        for msg in self.inputs.shell_messages() {
+            self.console.print(msg);
+        }
+        for cmd in self.inputs.drain_commands().collect_vec() {
+            self.exec_command(&cmd, true); // 👈 e.g. from web page buttons
+        }
+        if let Some(save) = self.autosave.tick(self.now_secs, self.tick) {
+            self.requests.push(save);
+        }
+        self.tick_hotkeys();
+
+        self.console.tick_and_draw(&self.inputs, &*COMMANDS, out).map(|cmd| self.exec_command(&cmd, true));
+        let follow = self.selected_critter().ok().and_then(|c| c.body.bones.first()).map(|b| b.position);
+        self.camera.tick(&self.inputs, follow, out);
         if !self.console.active {
-            self.tick_manual_control();
+            self.tick_controls(out);
+            if let Some(critter) = self.selected_critter.and_then(|i| self.world.critters.get_mut(i)) {
+                self.inspector.tick_and_draw(&mut self.inputs, &mut self.console, "sel", critter, out);
+            }
+            self.tick_manual_control(out.screen_to_world(self.inputs.mouse_position()));
         }
 
         if !self.paused || self.inputs.is_down(K_TICK) {
             for _ in 0..self.speed {
                 self.world.tick(self.now_secs, self.dt);
             }
+            self.record_metrics();
         }
+        out.debug = self.metrics.summary();
 
         self.draw(out);
+        out.requests.append(&mut self.requests);
     }
 
-    fn tick_manual_control(&mut self) {
+    /// 🎛️ Simulation controls, top-center of the screen (clicks on it don't reach the world).
+    fn tick_controls(&mut self, out: &mut Out) {
+        let pos = ((out.viewport_size.x() as f32 - CONTROLS_WIDTH) / 2.0, 0.0);
+        let critter = self.selected_critter.and_then(|i| self.world.critters.get_mut(i));
+        let controls = self.ui.panel(&mut self.inputs, out, pos, CONTROLS_WIDTH, |ui| {
+            ui.int_slider("speed", &mut self.speed, 1..=20);
+            ui.slider("dt", &mut self.dt, 0.001..=0.1);
+            if let Some(critter) = critter {
+                ui.slider("stiffness", &mut critter.body.stiffness, 0.0..=200.0);
+            }
+            ui.checkbox("paused", &mut self.paused);
+        });
+        if !self.metrics.plotted().is_empty() {
+            let pos = (controls.min.x(), controls.max.y() + self.ui.style.padding);
+            self.ui.panel(&mut self.inputs, out, pos, CONTROLS_WIDTH, |ui| self.metrics.draw(ui));
+        }
+    }
+
+    /// 📈 Sample the world for the `plot` command.
+    /// `mean_energy`: kinetic energy per critter. `physics_energy`: kinetic + spring energy of all critters
+    /// (without gravitational energy, which is relative to an arbitrary y = 0, see `Contraption::energy`).
+    fn record_metrics(&mut self) {
+        let critters = &self.world.critters;
+        let population = critters.len() as f32;
+        let kinetic = critters.iter().map(|c| c.body.kinetic_energy()).sum::<f32>();
+        let springs = critters.iter().map(|c| c.body.spring_energy()).sum::<f32>();
+        let mean = |total: f32| if critters.is_empty() { 0.0 } else { total / population };
+        let speed = critters.iter().map(|c| c.body.velocity().len()).sum();
+        self.metrics.record("population", population);
+        self.metrics.record("mean_energy", mean(kinetic));
+        self.metrics.record("mean_speed", mean(speed));
+        self.metrics.record("physics_energy", kinetic + springs);
+    }
+
+    /// `mouse_world`: mouse position in world coordinates.
+    fn tick_manual_control(&mut self, mouse_world: vec2f) {
         let mut delta = vec2f::ZERO;
         if self.inputs.is_down(K_LEFT) {
             delta += (-1.0, 0.0);
@@ -88,8 +224,9 @@ impl GameState {
         if self.inputs.is_down(K_UP) {
             delta += (0.0, -1.0);
         }
+        delta += self.inputs.left_stick(); // 🎮
 
-        self.mouse_filter[0] = self.inputs.mouse_position().as_();
+        self.mouse_filter[0] = mouse_world;
         for i in 1..self.mouse_filter.len() {
             self.mouse_filter[i] = 0.7 * self.mouse_filter[i] + 0.3 * self.mouse_filter[i - 1];
         }
@@ -106,12 +243,6 @@ impl GameState {
             }
         }
 
-        if self.food_follows_mouse {
-            if let Some(food) = self.world.food.get_mut(0) {
-                *food = self.inputs.mouse_position().as_();
-            }
-        }
-
         let mouse1 = self.inputs.just_pressed(K_MOUSE1);
         let mouse2 = self.inputs.just_pressed(K_MOUSE2);
         let mouse = self.inputs.mouse_position();
@@ -123,16 +254,22 @@ impl GameState {
                 if mouse2 {
                     *c.brain.inputs.at_mut(idx) -= 0.5;
                 }
+                return; // 👈 mouse is on the brain panel, not in the world
             }
         }
+
+        self.tick_tool(mouse_world);
     }
 
     fn draw(&self, out: &mut Out) {
         self.world.draw(out);
+        self.world.draw_picks(out, self.hover, self.selected_critter);
+        self.draw_palette(out);
         if let Ok(sel) = self.selected_critter() {
             sel.brain.draw(out)
         }
-        out.bloom = true;
+        out.bloom = self.post_fx.bloom;
+        out.vignette = self.post_fx.vignette;
     }
 
     fn update_inputs(&mut self, now_micros: u64, events: impl Iterator<Item = InputEvent>) {
@@ -147,57 +284,142 @@ impl GameState {
         }
     }
 
-    fn exec_command(&mut self, cmd: &str) {
-        match self.exec_command_impl(cmd) {
-            Ok(_) => self.console.print("ok"),
-            Err(e) => self.console.print(format!("{e}")),
-        }
-    }
-
-    fn exec_command_impl(&mut self, cmd: &str) -> Result<()> {
-        match cmd.trim().split_ascii_whitespace().collect_vec().as_slice() {
-            ["pause"] => Ok(toggle(&mut self.paused)),
-            ["reset"] => Ok(self.reset()),
-            ["sel" | "select", i] => Ok(self.selected_critter = Some(i.parse()?)),
-            ["s", s] => Ok(self.selected_critter_mut()?.body.stiffness = s.parse()?),
-            ["n", n] => Ok(*self.selected_critter_mut()? = Critter::new(n.parse()?, self.selected_critter()?.brain.size().x())),
-            ["g", g] => Ok(self.selected_critter_mut()?.body.g = g.parse()?),
-            ["k", k] => Ok({
-                let k = k.parse()?;
-                self.selected_critter_mut()?.body.springs.iter_mut().for_each(|s| s.k = k)
-            }),
-            ["angle", a] => Ok({
-                let a = f32::sin(a.parse()?);
-                self.selected_critter_mut()?.body.springs.iter_mut().for_each(|s| s.angle_setpoint = a)
-            }),
-            ["ca", v] => Ok(self.selected_critter_mut()?.crawl_amplitude = v.parse::<f32>()?),
-            ["cf", v] => Ok(self.selected_critter_mut()?.crawl_frequency = v.parse::<f32>()?),
-            ["cw", v] => Ok(self.selected_critter_mut()?.crawl_wavenumber = v.parse::<f32>()?),
-            ["cg", v] => Ok(self.selected_critter_mut()?.crawl_gamma = v.parse::<f32>()?),
-            ["mouse"] => Ok(toggle(&mut self.follow_mouse)),
-            ["mouse", v] => Ok(self.follow_mouse = v.parse()?),
-            ["mousefood" | "mf"] => Ok(toggle(&mut self.food_follows_mouse)),
-            ["dt", v] => Ok(self.dt = v.parse()?),
-            ["speed", v] => Ok(self.speed = v.parse()?),
-            _ => Err(anyhow!("unknown command: {cmd:?}")),
-        }
-    }
-
-    fn selected_critter_mut(&mut self) -> Result<&mut Critter> {
+    /// Reset the camera to show the world at the original (unzoomed) canvas position.
+    pub(crate) fn home_camera(&mut self) {
+        self.camera.camera = Camera::at(self.viewport.as_f32() / 2.0);
+        self.camera.follow = false;
+    }
+
+    /// Execute a command line (typed or from a script).
+    /// `verbose`: print "ok" after each successful command.
+    fn exec_command(&mut self, line: &str, verbose: bool) {
+        let cmds = match self.script.expand(line) {
+            Ok(cmds) => cmds,
+            Err(e) => return self.console.print(format!("{e}")),
+        };
+        for cmd in cmds {
+            match COMMANDS.exec(self, &cmd) {
+                Ok(Some(output)) => self.console.print(output),
+                Ok(None) if verbose => self.console.print("ok"),
+                Ok(None) => (),
+                Err(e) => return self.console.print(format!("{cmd}: {e}")),
+            }
+        }
+    }
+
+    /// Replace the game by `g` (loaded or imported), keeping the console, camera, keymap, etc. of the running game.
+    /// Marked as started, so autoexec does not run again.
+    pub(crate) fn replace(&mut self, mut g: GameState) {
+        g.console = take(&mut self.console);
+        g.inputs = take(&mut self.inputs);
+        g.pending_loads = take(&mut self.pending_loads);
+        g.camera = take(&mut self.camera);
+        g.keymap = take(&mut self.keymap);
+        g.viewport = self.viewport;
+        g.started = true;
+        *self = g;
+    }
+
+    /// Ask the shell to load a script, executed by `handle_loaded_files` when it arrives.
+    pub(crate) fn exec_file(&mut self, path: &str) {
+        self.load_file(ShellRequest::ReadFile { path: path.into() }, PendingLoad::Exec);
+    }
+
+    /// Send a `ReadFile` or `PickFile` request, remember what to do with the result.
+    pub(crate) fn load_file(&mut self, request: ShellRequest, then: PendingLoad) {
+        if let ShellRequest::PickFile { path } = &request {
+            self.pending_loads.retain(|(p, _)| p != path); // 👈 an earlier dialog may never be answered
+        }
+        if let ShellRequest::ReadFile { path } | ShellRequest::PickFile { path } = &request {
+            self.pending_loads.push((path.clone(), then));
+        }
+        self.requests.push(request);
+    }
+
+    /// Handle files loaded by the shell: execute scripts or import RON.
+    fn handle_loaded_files(&mut self) {
+        for (path, result) in self.inputs.drain_loaded_files().collect_vec() {
+            let Some(i) = self.pending_loads.iter().position(|(p, _)| p == &path) else {
+                log::warn!("unexpected file: {path}");
+                continue;
+            };
+            let (_, then) = self.pending_loads.remove(i);
+            match (then, result) {
+                (PendingLoad::Exec, Ok(bytes)) => self.exec_script(&path, &bytes),
+                (PendingLoad::Import(what), Ok(bytes)) => match self.import_ron(what, &String::from_utf8_lossy(&bytes)) {
+                    Ok(()) => self.console.print(format!("imported {what} from {path}")),
+                    Err(e) => self.console.print(format!("import {path}: {e}")),
+                },
+                (PendingLoad::Exec, Err(e)) if path == AUTOEXEC => log::info!("no autoexec: {e}"), // 👈 optional
+                (_, Err(e)) => self.console.print(e),
+            }
+        }
+    }
+
+    /// Execute a script line by line. Lines starting with `#` are comments.
+    fn exec_script(&mut self, path: &str, bytes: &[u8]) {
+        log::info!("exec {path}");
+        for line in String::from_utf8_lossy(bytes).lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            self.exec_command(line, false);
+        }
+    }
+
+    /// Print the fields at `path` (reflection), with their values.
+    pub(crate) fn list_fields(&self, path: &str) -> Result<()> {
+        for field in self.list_path(path)? {
+            let value = self.at_path(path)?.field(&field).map(|v| v.get_string()).unwrap_or_default();
+            self.console.print(format!("{field}: {value}"));
+        }
+        Ok(())
+    }
+
+    /// Reflection path shorthand: `sel` expands to the selected critter.
+    /// E.g. `sel.body.stiffness` => `world.critters.0.body.stiffness`.
+    pub(crate) fn expand_path(&self, path: &str) -> Result<String> {
+        match path.split_once('.').map(|(head, tail)| (head, Some(tail))).unwrap_or((path, None)) {
+            ("sel", tail) => {
+                let sel = self.selected_critter.ok_or_else(|| anyhow!("no critter selected"))?;
+                Ok(format!("world.critters.{sel}.{}", tail.unwrap_or_default()))
+            }
+            _ => Ok(path.to_owned()),
+        }
+    }
+
+    pub(crate) fn selected_critter_mut(&mut self) -> Result<&mut Critter> {
         self.selected_critter.and_then(|i| self.world.critters.get_mut(i)).ok_or_else(|| anyhow!("there is no critter #{:?}", self.selected_critter))
     }
 
-    fn selected_critter(&self) -> Result<&Critter> {
+    pub(crate) fn selected_critter(&self) -> Result<&Critter> {
         self.selected_critter.and_then(|i| self.world.critters.get(i)).ok_or_else(|| anyhow!("there is no critter #{:?}", self.selected_critter))
     }
 }
 
+/// Script executed on startup, if it exists.
+const AUTOEXEC: &str = "autoexec.cfg";
+
 impl Default for GameState {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// 💾 Bump `VERSION` when changing anything serialized (e.g. adding a field to `Critter`),
+/// and upgrade older saves in `migrate` (see `shell_api::Migrate`).
+impl Migrate for GameState {
+    const TYPE_ID: &'static str = "aquarium";
+    const VERSION: u32 = 5;
+
+    fn migrate(version: u32, payload: &[u8]) -> Result<Self> {
+        match version {
+            1 => Ok(decode_payload::<GameStateV1>(payload)?.into()),
+            2 => Ok(decode_payload::<GameStateV2>(payload)?.into()),
+            3 => Ok(decode_payload::<GameStateV3>(payload)?.into()),
+            4 => Ok(decode_payload::<GameStateV4>(payload)?.into()),
+            _ => bail!("cannot migrate save from version {version}"),
+        }
+    }
+}
+
 impl shell_api::GameCore for GameState {
     fn tick(&mut self, now_micros: u64, events: impl Iterator<Item = shell_api::InputEvent>, out: &mut shell_api::Out) {
         self.tick_and_draw(now_micros, events, out)
@@ -206,12 +428,63 @@ impl shell_api::GameCore for GameState {
     fn reset(&mut self) {
         *self = Self::new()
     }
+
+    fn load(&mut self, loaded: Self) {
+        self.replace(loaded)
+    }
 }
 
-fn toggle(v: &mut bool) {
+pub(crate) fn toggle(v: &mut bool) {
     *v = !*v
 }
 
 fn not_found() -> Error {
     anyhow!("does not exist")
 }
+
+/// 🎨 Post-processing effects, toggled with the `bloom` and `vignette` commands.
+#[derive(Debug, Clone, Copy)]
+pub struct PostFx {
+    pub bloom: bool,
+    pub vignette: bool,
+}
+
+impl Default for PostFx {
+    fn default() -> Self {
+        Self { bloom: true, vignette: true }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+    use shell_api::GameCore;
+
+    fn reads_autoexec(out: &Out) -> bool {
+        out.requests.iter().any(|r| matches!(r, ShellRequest::ReadFile { path } if path == AUTOEXEC))
+    }
+
+    #[gtest]
+    fn load_keeps_session() {
+        let mut g = GameState::new();
+        let mut out = Out::default();
+        g.tick(0, [].into_iter(), &mut out);
+        expect_that!(reads_autoexec(&out), eq(true));
+        g.console.print("hello");
+
+        let mut saved = GameState::new();
+        saved.speed = 3;
+        saved.world.food = vec![vec2(1.0, 2.0)];
+        let loaded = decode_save::<GameState>(&encode_save(&saved).unwrap()).unwrap();
+        g.load(loaded);
+        expect_that!(g.speed, eq(3));
+        expect_that!(g.world.food, eq(&[vec2(1.0, 2.0)]));
+        expect_that!(g.console.output.len(), eq(1));
+
+        // 👇 autoexec does not run again
+        let mut out = Out::default();
+        g.tick(50_000, [].into_iter(), &mut out);
+        expect_that!(reads_autoexec(&out), eq(false));
+    }
+}
@@ -0,0 +1,223 @@
+//! ⌨️ Key bindings. User bindings (`bind`, `unbind`) are persisted apart from the save game,
+//! so that loading an old save does not undo them.
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+/// Storage key of the user's keymap (see `ShellRequest::Store`).
+pub(crate) const KEYMAP_KEY: &str = "keymap";
+
+/// Slot written by `K_QUICKSAVE` (ctrl+s).
+const QUICKSAVE_SLOT: &str = "quicksave";
+
+pub fn default_keymap() -> Keymap {
+    let mut keymap = Keymap::from(
+        [
+            (button!("backquote"), K_CLI),
+            (button!("f2"), K_INSPECT),
+            (K_ARROW_DOWN, K_DOWN),
+            (K_ARROW_LEFT, K_LEFT),
+            (K_ARROW_RIGHT, K_RIGHT),
+            (K_ARROW_UP, K_UP),
+            (K_SPACE, K_TICK),
+            (K_PAD_DOWN, K_DOWN),
+            (K_PAD_LEFT, K_LEFT),
+            (K_PAD_RIGHT, K_RIGHT),
+            (K_PAD_UP, K_UP),
+            (K_PAD_A, K_TICK),
+        ]
+        .into_iter()
+        .chain(Tool::keymap()),
+    );
+    keymap.bind(Chord::new(Mods::CTRL, button!("s")), K_QUICKSAVE);
+    keymap.bind(Chord::new(Mods::CTRL, button!("a")), K_SELECT_ALL);
+    keymap.bind(Chord::new(Mods::CTRL, button!("c")), K_COPY);
+    keymap.bind(Chord::new(Mods::CTRL, button!("x")), K_CUT);
+    keymap
+}
+
+/// User changes to `default_keymap`, as stored under `KEYMAP_KEY`.
+/// Only the differences are stored, so that new default bindings still reach existing users.
+/// Chords and actions are kept as text, so that one bad entry does not lose all the others.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+struct KeymapOverrides {
+    /// Chords bound by the user (to something other than their default), e.g. "ctrl+shift+s": "quicksave".
+    #[serde(default)]
+    bind: BTreeMap<String, String>,
+    /// Default chords unbound by the user.
+    #[serde(default)]
+    unbind: Vec<String>,
+}
+
+/// Key names from before they were the same on every shell (see `KEY_NAMES`), as found in stored keymaps.
+const RENAMED_KEYS: [(&str, &str); 12] = [
+    ("`", "backquote"),
+    ("graveaccent", "backquote"),
+    ("key0", "0"),
+    ("key1", "1"),
+    ("key2", "2"),
+    ("key3", "3"),
+    ("key4", "4"),
+    ("key5", "5"),
+    ("key6", "6"),
+    ("key7", "7"),
+    ("key8", "8"),
+    ("key9", "9"),
+];
+
+impl KeymapOverrides {
+    fn new(keymap: &Keymap, defaults: &Keymap) -> Self {
+        Self {
+            bind: keymap.0.iter().filter(|(c, a)| defaults.0.get(c) != Some(a)).map(|(c, a)| (c.to_string(), a.as_str().to_string())).collect(),
+            unbind: defaults.0.keys().filter(|c| !keymap.0.contains_key(c)).map(Chord::to_string).collect(),
+        }
+    }
+
+    /// Parse stored overrides. Keymaps stored in full (before overrides) are read as if everything was bound by the user.
+    fn parse(text: &str) -> Result<Self> {
+        match ron::from_str(text) {
+            Ok(overrides) => Ok(overrides),
+            Err(e) => ron::from_str(text).map(|bind| Self { bind, unbind: vec![] }).map_err(|_| e.into()),
+        }
+    }
+
+    /// Apply to `keymap`, skipping (and describing) entries that don't parse.
+    fn apply(&self, keymap: &mut Keymap) -> Vec<String> {
+        let mut errors = vec![];
+        for chord in &self.unbind {
+            match parse_chord(chord) {
+                Ok(chord) => drop(keymap.unbind(chord)),
+                Err(e) => errors.push(format!("unbind {chord}: {e}")),
+            }
+        }
+        for (chord, action) in &self.bind {
+            match (parse_chord(chord), action.parse::<Button>()) {
+                (Ok(chord), Ok(action)) => drop(keymap.bind(chord, action)),
+                (Err(e), _) | (_, Err(e)) => errors.push(format!("bind {chord} {action}: {e}")),
+            }
+        }
+        errors
+    }
+}
+
+/// Parse a stored chord, upgrading old key names (`RENAMED_KEYS`).
+fn parse_chord(s: &str) -> Result<Chord> {
+    let mut chord = s.parse::<Chord>()?;
+    if let Some((_, new)) = RENAMED_KEYS.iter().find(|(old, _)| chord.key.as_str() == *old) {
+        chord.key = new.parse()?;
+    }
+    Ok(chord)
+}
+
+impl GameState {
+    /// Ask the shell for the user's keymap, applied by `handle_fetched` when it arrives.
+    pub(crate) fn fetch_keymap(&mut self) {
+        self.requests.push(ShellRequest::Fetch { key: KEYMAP_KEY.into() });
+    }
+
+    /// Persist the user's changes to the default keymap (after `bind`, `unbind`).
+    pub(crate) fn store_keymap(&mut self) -> Result<()> {
+        let overrides = KeymapOverrides::new(&self.keymap, &default_keymap());
+        let data = ron::ser::to_string_pretty(&overrides, default())?.into_bytes();
+        self.requests.push(ShellRequest::Store { key: KEYMAP_KEY.into(), data });
+        Ok(())
+    }
+
+    /// Handle data fetched by the shell: the user's keymap, applied over the defaults.
+    pub(crate) fn handle_fetched(&mut self) {
+        for (key, result) in self.inputs.drain_fetched().collect_vec() {
+            match (key.as_str(), result) {
+                (KEYMAP_KEY, Ok(bytes)) => match KeymapOverrides::parse(&String::from_utf8_lossy(&bytes)) {
+                    Ok(overrides) => {
+                        self.keymap = default_keymap();
+                        for e in overrides.apply(&mut self.keymap) {
+                            self.console.print(format!("keymap: {e}, skipped"));
+                        }
+                    }
+                    Err(e) => self.console.print(format!("keymap: {e}, using defaults")),
+                },
+                (KEYMAP_KEY, Err(e)) => log::info!("no user keymap: {e}"), // 👈 nothing bound yet
+                (key, _) => log::warn!("unexpected fetch: {key}"),
+            }
+        }
+    }
+
+    /// Actions that don't belong to any particular component.
+    pub(crate) fn tick_hotkeys(&mut self) {
+        if self.inputs.just_pressed(K_QUICKSAVE) {
+            self.requests.push(ShellRequest::Save { slot: QUICKSAVE_SLOT.into(), tick: self.tick });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// Game that just received `stored` as the user's keymap.
+    fn fetched(stored: &str) -> GameState {
+        let mut g = GameState::new();
+        let result = Ok(stored.as_bytes().to_vec());
+        g.inputs.tick(&g.keymap, [InputEvent::Fetched { key: KEYMAP_KEY.into(), result }].into_iter());
+        g.handle_fetched();
+        g
+    }
+
+    fn press(g: &mut GameState, key: Button) {
+        g.inputs.tick(&g.keymap, [InputEvent::Key { button: key, direction: KeyDir::Down }].into_iter());
+    }
+
+    #[gtest]
+    fn store_only_overrides() {
+        let mut g = GameState::new();
+        g.keymap.bind(Chord::new(Mods { shift: true, ..Mods::CTRL }, button!("s")), K_QUICKSAVE);
+        g.keymap.bind(button!("f3").into(), K_INSPECT);
+        g.keymap.unbind(button!("f2").into());
+        g.store_keymap().unwrap();
+
+        let Some(ShellRequest::Store { key, data }) = g.requests.pop() else { panic!("expected Store request") };
+        expect_that!(key, eq(KEYMAP_KEY));
+        let overrides = KeymapOverrides::parse(&String::from_utf8(data.clone()).unwrap()).unwrap();
+        expect_that!(
+            overrides,
+            eq(&KeymapOverrides {
+                bind: BTreeMap::from([("ctrl+shift+s".into(), "quicksave".into()), ("f3".into(), "inspect".into())]),
+                unbind: vec!["f2".into()],
+            })
+        );
+
+        let loaded = fetched(&String::from_utf8(data).unwrap());
+        expect_that!(loaded.keymap, eq(&g.keymap));
+    }
+
+    #[gtest]
+    fn stored_keymap_without_cli_still_opens_console() {
+        let mut g = fetched(r#"{"f2": "inspect", "ctrl+s": "quicksave"}"#); // 👈 stored in full, before K_CLI was bound
+        press(&mut g, button!("backquote"));
+        _ = g.console.tick(&g.inputs, &*COMMANDS);
+        expect_that!(g.console.active, eq(true));
+    }
+
+    #[gtest]
+    fn skip_bad_entries() {
+        let g = fetched(r#"(bind: {"super+s": "quicksave", "f3": "inspect", "f4": "an_action_name_too_long"}, unbind: ["ctrl+", "f2"])"#);
+        expect_that!(g.keymap.0.get(&button!("f3").into()), some(eq(&K_INSPECT)));
+        expect_that!(g.keymap.0.get(&button!("f2").into()), none());
+        expect_that!(g.keymap.0.get(&Chord::new(Mods::CTRL, button!("s"))), some(eq(&K_QUICKSAVE))); // 👈 defaults still there
+        expect_that!(g.console.output.len(), eq(3));
+
+        let mut g = fetched("not a keymap");
+        expect_that!(g.keymap, eq(&default_keymap()));
+        press(&mut g, button!("backquote"));
+        _ = g.console.tick(&g.inputs, &*COMMANDS);
+        expect_that!(g.console.active, eq(true));
+    }
+
+    #[gtest]
+    fn renamed_keys() {
+        let g = fetched(r#"{"graveaccent": "cli", "key8": "tool_wall", "ctrl+`": "inspect"}"#); // 👈 names from before canonical key names
+        expect_that!(g.keymap.0.get(&button!("8").into()), some(eq(&Tool::Wall.button())));
+        expect_that!(g.keymap.0.get(&Chord::new(Mods::CTRL, button!("backquote"))), some(eq(&K_INSPECT)));
+        expect_that!(g.keymap.0.get(&button!("graveaccent").into()), none());
+    }
+}
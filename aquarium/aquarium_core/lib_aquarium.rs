@@ -1,13 +1,27 @@
 pub(crate) mod prelude;
 
+mod commands;
 mod critter;
+mod export;
+mod fonts;
 mod gamestate;
+mod keys;
+mod migrations;
 mod neural_net;
 mod physics2d;
+mod picking;
+mod tools;
 mod world;
 
+use commands::*;
 pub use critter::*;
+use export::*;
+pub use fonts::*;
 pub use gamestate::*;
+pub use keys::*;
+use migrations::*;
 pub use neural_net::*;
 pub use physics2d::*;
+pub use picking::*;
+pub use tools::*;
 pub use world::*;
@@ -0,0 +1,239 @@
+//! 💾 Old save formats, upgraded by `GameState::migrate`.
+use crate::prelude::*;
+
+/// `GameState` before `autosave` was added.
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+pub(crate) struct GameStateV1 {
+    now_secs: f64,
+    dt: f32,
+    speed: u32,
+    tick: u64,
+    paused: bool,
+    _keymap: KeymapV1,
+    console: Console,
+    inspector: Inspector,
+    script: Script,
+    world: WorldV1,
+    selected_critter: Option<usize>,
+    follow_mouse: bool,
+    food_follows_mouse: bool,
+    _mouse_filter: [vec2f; 3],
+}
+
+impl From<GameStateV1> for GameState {
+    fn from(v1: GameStateV1) -> Self {
+        GameState::new().with(|g| {
+            g.now_secs = v1.now_secs;
+            g.dt = v1.dt;
+            g.speed = v1.speed;
+            g.tick = v1.tick;
+            g.paused = v1.paused;
+            g.console = v1.console;
+            g.inspector = v1.inspector;
+            g.script = v1.script;
+            g.world = v1.world.into();
+            g.selected_critter = v1.selected_critter;
+            g.follow_mouse = v1.follow_mouse;
+            g.tool = tool_from_food_follows_mouse(v1.food_follows_mouse);
+        })
+    }
+}
+
+/// `GameState` before `food_follows_mouse` became `tool`.
+#[derive(Deserialize)]
+pub(crate) struct GameStateV2 {
+    now_secs: f64,
+    dt: f32,
+    speed: u32,
+    tick: u64,
+    paused: bool,
+    _keymap: KeymapV1,
+    console: Console,
+    inspector: Inspector,
+    script: Script,
+    autosave: Autosave,
+    world: WorldV1,
+    selected_critter: Option<usize>,
+    follow_mouse: bool,
+    food_follows_mouse: bool,
+    _mouse_filter: [vec2f; 3],
+}
+
+impl From<GameStateV2> for GameState {
+    fn from(v2: GameStateV2) -> Self {
+        GameState::new().with(|g| {
+            g.now_secs = v2.now_secs;
+            g.dt = v2.dt;
+            g.speed = v2.speed;
+            g.tick = v2.tick;
+            g.paused = v2.paused;
+            g.console = v2.console;
+            g.inspector = v2.inspector;
+            g.script = v2.script;
+            g.autosave = v2.autosave;
+            g.world = v2.world.into();
+            g.selected_critter = v2.selected_critter;
+            g.follow_mouse = v2.follow_mouse;
+            g.tool = tool_from_food_follows_mouse(v2.food_follows_mouse);
+        })
+    }
+}
+
+/// `GameState` before `World::walls` was added.
+#[derive(Deserialize)]
+pub(crate) struct GameStateV3 {
+    now_secs: f64,
+    dt: f32,
+    speed: u32,
+    tick: u64,
+    paused: bool,
+    _keymap: KeymapV1,
+    console: Console,
+    inspector: Inspector,
+    script: Script,
+    autosave: Autosave,
+    world: WorldV1,
+    selected_critter: Option<usize>,
+    follow_mouse: bool,
+    tool: Tool,
+    _mouse_filter: [vec2f; 3],
+}
+
+impl From<GameStateV3> for GameState {
+    fn from(v3: GameStateV3) -> Self {
+        GameState::new().with(|g| {
+            g.now_secs = v3.now_secs;
+            g.dt = v3.dt;
+            g.speed = v3.speed;
+            g.tick = v3.tick;
+            g.paused = v3.paused;
+            g.console = v3.console;
+            g.inspector = v3.inspector;
+            g.script = v3.script;
+            g.autosave = v3.autosave;
+            g.world = v3.world.into();
+            g.selected_critter = v3.selected_critter;
+            g.follow_mouse = v3.follow_mouse;
+            g.tool = v3.tool;
+        })
+    }
+}
+
+/// `GameState` before the keymap moved out of the save game.
+#[derive(Deserialize)]
+pub(crate) struct GameStateV4 {
+    now_secs: f64,
+    dt: f32,
+    speed: u32,
+    tick: u64,
+    paused: bool,
+    _keymap: KeymapV1,
+    console: Console,
+    inspector: Inspector,
+    script: Script,
+    autosave: Autosave,
+    world: World,
+    selected_critter: Option<usize>,
+    follow_mouse: bool,
+    tool: Tool,
+    _mouse_filter: [vec2f; 3],
+}
+
+impl From<GameStateV4> for GameState {
+    fn from(v4: GameStateV4) -> Self {
+        GameState::new().with(|g| {
+            g.now_secs = v4.now_secs;
+            g.dt = v4.dt;
+            g.speed = v4.speed;
+            g.tick = v4.tick;
+            g.paused = v4.paused;
+            g.console = v4.console;
+            g.inspector = v4.inspector;
+            g.script = v4.script;
+            g.autosave = v4.autosave;
+            g.world = v4.world;
+            g.selected_critter = v4.selected_critter;
+            g.follow_mouse = v4.follow_mouse;
+            g.tool = v4.tool;
+        })
+    }
+}
+
+/// `Keymap` before modifiers: physical key -> action. Not migrated (it was never user-editable).
+type KeymapV1 = HashMap<Button, Button>;
+
+/// `World` before `walls` was added.
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+pub(crate) struct WorldV1 {
+    critters: Vec<Critter>,
+    food: Vec<vec2f>,
+}
+
+impl From<WorldV1> for World {
+    fn from(v1: WorldV1) -> Self {
+        World {
+            critters: v1.critters,
+            food: v1.food,
+            walls: vec![],
+        }
+    }
+}
+
+fn tool_from_food_follows_mouse(food_follows_mouse: bool) -> Tool {
+    if food_follows_mouse { Tool::Feed } else { Tool::Select }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// Lets `encode_save` write a version 1 save, as the game did before `autosave`.
+    impl Migrate for GameStateV1 {
+        const TYPE_ID: &'static str = GameState::TYPE_ID;
+        const VERSION: u32 = 1;
+    }
+
+    #[gtest]
+    fn migrate_v1_to_current() {
+        let g = GameState::new();
+        let v1 = GameStateV1 {
+            now_secs: 12.5,
+            dt: 0.02,
+            speed: 3,
+            tick: 625,
+            paused: true,
+            _keymap: HashMap::from_iter([(K_ARROW_UP, K_ARROW_DOWN)]),
+            console: g.console,
+            inspector: g.inspector,
+            script: g.script,
+            world: WorldV1 {
+                critters: vec![Critter::new(8, 6)],
+                food: vec![vec2(1.0, 2.0)],
+            },
+            selected_critter: None,
+            follow_mouse: true,
+            food_follows_mouse: true,
+            _mouse_filter: [vec2f::ZERO; 3],
+        };
+
+        let g = decode_save::<GameState>(&encode_save(&v1).unwrap()).unwrap();
+        expect_that!(g.now_secs, eq(12.5));
+        expect_that!(g.dt, eq(0.02));
+        expect_that!(g.speed, eq(3));
+        expect_that!(g.tick, eq(625));
+        expect_that!(g.paused, eq(true));
+        expect_that!(g.world.critters.len(), eq(1));
+        expect_that!(g.world.food, eq(&[vec2(1.0, 2.0)]));
+        expect_that!(g.world.walls, is_empty());
+        expect_that!(g.selected_critter, none());
+        expect_that!(g.follow_mouse, eq(true));
+        expect_that!(g.tool, eq(Tool::Feed));
+
+        // 👇 and it saves as the current version
+        let g = decode_save::<GameState>(&encode_save(&g).unwrap()).unwrap();
+        expect_that!(g.tick, eq(625));
+    }
+}
@@ -21,7 +21,7 @@ use crate::prelude::*;
 
 */
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Reflect, Clone)]
 pub struct Brain {
     pub inputs: Vec2D<f32>,
     pub signals: Vec2D<f32>,
@@ -29,7 +29,7 @@ pub struct Brain {
     pub neurons: Vec2D<Neuron>,
 }
 
-#[derive(Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize, Reflect)]
 pub struct Neuron {
     pub bias: f32,
     pub weights: Vec<(u8, f32)>,
@@ -95,7 +95,8 @@ impl Brain {
                 let idx = self.neurons.reverse_index(i.as_());
                 let end = self.neuron_to_screen_pos(idx);
                 let off = Self::NEURON_SCREEN_SIZE / 2;
-                out.draw_line_screen(L_SPRITES + 2, Line::new(start, end).translated(off).with_color(RGBA([255, 255, 255, 128])));
+                let line = Line::new(start, end).translated(off).with_color(RGBA::WHITE);
+                out.draw_line_screen(L_SPRITES + 2, line.with_opacity(0.5 * w.abs().min(1.0)).with_blend(Blend::Add)); // 👈 strong connections glow
             }
         }
     }
@@ -82,3 +82,34 @@ impl<T: Copy> Vec2D<T> {
         cross(0..w, 0..h).map(move |(x, y)| (vec2(x, y), self.values[self.index(vec2(x, y))]))
     }
 }
+
+/// Elements are named by their 2D index, e.g. `brain.neurons.3,8.bias`.
+impl<T: Reflect> Reflect for Vec2D<T> {
+    fn fields(&self) -> Vec<String> {
+        let (w, h) = self.size().into();
+        cross(0..w, 0..h).map(|(x, y)| format!("{x},{y}")).collect()
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        let idx = self.parse_index(name)?;
+        Some(self.at(idx))
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        let idx = self.parse_index(name)?;
+        Some(self.at_mut(idx))
+    }
+
+    fn get_string(&self) -> String {
+        format!("[{}x{} items]", self.size().x(), self.size().y())
+    }
+}
+
+impl<T> Vec2D<T> {
+    /// Parse reflection field name `"x,y"`, if in bounds.
+    fn parse_index(&self, name: &str) -> Option<vec2u> {
+        let (x, y) = name.split_once(',')?;
+        let idx = vec2(x.trim().parse().ok()?, y.trim().parse().ok()?);
+        self.in_bounds(idx).then_some(idx.as_u32())
+    }
+}
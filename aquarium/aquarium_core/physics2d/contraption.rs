@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 /// A contraption made of rigid bodies connected via springs.
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Reflect, Clone)]
 pub struct Contraption {
     pub g: f32,
     pub bones: Vec<RigidBody>,
@@ -73,7 +73,7 @@ impl Contraption {
     }
 
     pub fn draw(&self, out: &mut Out) {
-        for b in &self.bones {
+        for b in 0..self.bones.len() {
             self.draw_bone(out, b)
         }
 
@@ -82,6 +82,33 @@ impl Contraption {
         }
     }
 
+    /// Kinetic + spring + gravitational energy (the bending stiffness is not included).
+    /// Without drag, damping and stiffness, this is conserved (see test `energy_conserved`).
+    /// The gravitational part is relative to y = 0, so only changes in energy mean something.
+    pub fn energy(&self) -> f32 {
+        let gravity = self.bones.iter().map(|b| -b.mass * self.g * b.position.y()).sum::<f32>(); // 👈 g points down (+y)
+        self.kinetic_energy() + gravity + self.spring_energy()
+    }
+
+    /// Translational + rotational kinetic energy of all bones.
+    pub fn kinetic_energy(&self) -> f32 {
+        self.bones.iter().map(RigidBody::kinetic_energy).sum()
+    }
+
+    /// Energy stored in stretched springs.
+    pub fn spring_energy(&self) -> f32 {
+        self.springs.iter().map(|s| 0.5 * s.k * (self.bones[s.ib].transform_rel_pos(s.anchor_b) - self.bones[s.ia].transform_rel_pos(s.anchor_a)).len2()).sum()
+    }
+
+    /// Velocity of the center of mass (zero without bones).
+    pub fn velocity(&self) -> vec2f {
+        let mass = self.bones.iter().map(|b| b.mass).sum::<f32>();
+        if mass == 0.0 {
+            return vec2f::ZERO;
+        }
+        self.bones.iter().map(|b| b.mass * b.velocity).fold(vec2f::ZERO, |a, b| a + b) / mass
+    }
+
     pub(crate) fn tick(&mut self, dt: f32) {
         for _i in 0..10 {
             self.minor_tick(dt);
@@ -199,15 +226,20 @@ impl Contraption {
         let anchor_a = self.bones[ia].transform_rel_pos(spring.anchor_a);
         let anchor_b = self.bones[ib].transform_rel_pos(spring.anchor_b);
 
-        out.draw_line_screen(L_SPRITES, Line::new(anchor_a.as_(), anchor_b.as_()).with_color(color).with_width(2));
+        out.draw_line(L_SPRITES, Line::new(anchor_a, anchor_b).with_color(color).with_width(2));
     }
 
-    fn draw_bone(&self, out: &mut Out, bone: &RigidBody) {
-        let bone_len = self.bone_len;
+    fn draw_bone(&self, out: &mut Out, i: usize) {
         let color = RGBA::YELLOW;
-        let start = bone.transform_rel_pos(vec2(-bone_len / 2.0, 0.0)).as_i32();
-        let end = bone.transform_rel_pos(vec2(bone_len / 2.0, 0.0)).as_i32();
-        out.draw_line_screen(L_SPRITES, Line::new(start, end).with_color(color).with_width(3));
+        let (start, end) = self.bone_ends(i);
+        out.draw_line(L_SPRITES, Line::new(start, end).with_color(color).with_width(3));
+    }
+
+    /// World positions of both ends of bone `i`.
+    pub fn bone_ends(&self, i: usize) -> (vec2f, vec2f) {
+        let bone = &self.bones[i];
+        let half = vec2(self.bone_len / 2.0, 0.0);
+        (bone.transform_rel_pos(-half), bone.transform_rel_pos(half))
     }
 }
 
@@ -218,3 +250,41 @@ fn cross(a: vec2f, b: vec2f) -> f32 {
 fn rot90(v: vec2f) -> vec2f {
     vec2(v.y(), -v.x())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn energy_conserved() {
+        let mut c = Contraption::rope(4).with(|c| {
+            c.g = 2.0;
+            c.stiffness = 0.0; // 👈 not part of `energy`
+            c.bones.iter_mut().for_each(|b| b.position -= vec2(55.0, 150.0)); // 👈 around the origin, for f32 precision
+            c.bones[0].velocity_half = vec2(3.0, -2.0);
+            c.bones[0].rot_velocity_half = 0.2;
+            c.bones[3].velocity_half = vec2(0.0, 4.0);
+        });
+        let dt = 0.005;
+        // 👇 `energy` needs velocities at the same time as positions: finish the half step.
+        let synced = |c: &Contraption| {
+            c.clone().with(|c| {
+                c.update_forces();
+                c.bones.iter_mut().for_each(|b| b.update_accel());
+                c.bones.iter_mut().for_each(|b| b.update_velocity_verlet(dt));
+            })
+        };
+        let (start, kinetic) = (synced(&c).energy(), synced(&c).kinetic_energy());
+        expect_that!(kinetic, gt(10.0));
+
+        for tick in 0..4000 {
+            c.update_forces(); // 👈 `minor_tick` without drag and damping
+            c.verlet_tick(dt);
+            if tick % 100 == 0 {
+                expect_that!(synced(&c).energy(), near(start, 0.01 * kinetic), "tick {tick}");
+            }
+        }
+        expect_that!(c.spring_energy(), gt(0.0)); // 👈 it did wiggle
+    }
+}
@@ -3,7 +3,7 @@ use core_util::With as _;
 use matrix::*;
 
 // comment
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Reflect)]
 pub struct RigidBody {
     pub mass: f32,
     pub position: vec2f,
@@ -47,6 +47,11 @@ impl RigidBody {
         Self::new(vec2::ZERO, mass, rot_inertia)
     }
 
+    /// Translational + rotational kinetic energy.
+    pub fn kinetic_energy(&self) -> f32 {
+        0.5 * self.mass * self.velocity.len2() + 0.5 * self.rot_inertia * self.rot_velocity.powi(2)
+    }
+
     pub fn update_accel(&mut self) {
         self.acceleration = self.force / self.mass;
         self.rot_accel = self.torque / self.rot_inertia;
@@ -178,17 +183,17 @@ fn wrap_angle(theta: f32) -> f32 {
 }
 
 fn draw_body(out: &mut Out, body: &RigidBody) {
-    let pos = body.position.as_i32();
+    let pos = body.position;
     let color = RGBA::WHITE;
 
     // draw center
-    let s = vec2(2, 2);
-    out.draw_rect_screen(L_SPRITES, Rectangle::new((pos - s, pos + s), color));
+    let s = vec2(2.0, 2.0);
+    out.draw_rect(L_SPRITES, Rectangle::new((pos - s, pos + s), color));
 
     // draw frame/axes
     let ax_len = 15.0;
-    let x = body.transform_rel_pos(vec2::EX * ax_len).as_i32();
-    let y = body.transform_rel_pos(vec2::EY * ax_len).as_i32();
-    out.draw_line_screen(L_SPRITES, Line::new(pos, x).with_color(color));
-    out.draw_line_screen(L_SPRITES, Line::new(pos, y).with_color(color));
+    let x = body.transform_rel_pos(vec2::EX * ax_len);
+    let y = body.transform_rel_pos(vec2::EY * ax_len);
+    out.draw_line(L_SPRITES, Line::new(pos, x).with_color(color));
+    out.draw_line(L_SPRITES, Line::new(pos, y).with_color(color));
 }
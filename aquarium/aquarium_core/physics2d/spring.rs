@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Reflect, Clone)]
 pub struct Spring {
     pub ia: usize,
     pub ib: usize,
@@ -0,0 +1,185 @@
+//! 🖱 What's under the mouse: picking critter bones and food in world space.
+use crate::prelude::*;
+
+/// Something in the world that can be hovered, selected or dragged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pick {
+    Bone { critter: usize, bone: usize },
+    Food(usize),
+    Wall(usize),
+}
+
+/// How close (in screen pixels, regardless of zoom) the mouse must be to pick something.
+pub const PICK_RADIUS: f32 = 8.0;
+
+const HOVER_COLOR: RGBA = RGBA([255, 255, 255, 160]);
+const SELECTED_COLOR: RGBA = RGBA([0, 255, 255, 255]);
+
+impl World {
+    /// Nearest bone or food item within `radius` of world position `pos`.
+    pub fn pick(&self, pos: vec2f, radius: f32) -> Option<Pick> {
+        let bones = self.critters.iter().enumerate().flat_map(|(critter, c)| {
+            (0..c.body.bones.len()).map(move |bone| {
+                let (start, end) = c.body.bone_ends(bone);
+                (Pick::Bone { critter, bone }, pos.distance_to(closest_on_segment(pos, start, end)))
+            })
+        });
+        let food = self.food.iter().enumerate().map(|(i, f)| (Pick::Food(i), f.distance_to(pos)));
+        let walls = self
+            .walls
+            .iter()
+            .enumerate()
+            .flat_map(|(i, w)| w.iter().copied().tuple_windows().map(move |(a, b)| (Pick::Wall(i), pos.distance_to(closest_on_segment(pos, a, b)))));
+
+        bones.chain(food).chain(walls).filter(|(_, dist)| *dist <= radius).min_by(|a, b| a.1.total_cmp(&b.1)).map(|(pick, _)| pick)
+    }
+
+    /// Move a picked bone or food item (e.g. dragging), stopping bones dead.
+    /// Walls don't move.
+    pub fn move_pick(&mut self, pick: Pick, pos: vec2f) {
+        match pick {
+            Pick::Bone { critter, bone } => {
+                if let Some(b) = self.critters.get_mut(critter).and_then(|c| c.body.bones.get_mut(bone)) {
+                    b.position = pos;
+                    b.velocity = vec2f::ZERO;
+                    b.velocity_half = vec2f::ZERO;
+                }
+            }
+            Pick::Food(i) => {
+                if let Some(food) = self.food.get_mut(i) {
+                    *food = pos;
+                }
+            }
+            Pick::Wall(_) => (),
+        }
+    }
+
+    /// Remove a picked critter, food item or wall.
+    /// Returns the removed critter's index, so that indices can be updated.
+    pub fn remove_pick(&mut self, pick: Pick) -> Option<usize> {
+        match pick {
+            Pick::Bone { critter, .. } if critter < self.critters.len() => {
+                self.critters.remove(critter);
+                return Some(critter);
+            }
+            Pick::Food(i) if i < self.food.len() => drop(self.food.remove(i)),
+            Pick::Wall(i) if i < self.walls.len() => drop(self.walls.remove(i)),
+            _ => (),
+        }
+        None
+    }
+
+    /// Outline hovered things and the selected critter.
+    pub(crate) fn draw_picks(&self, out: &mut Out, hover: Option<Pick>, selected: Option<usize>) {
+        if let Some(c) = selected.and_then(|i| self.critters.get(i)) {
+            self.draw_outline(out, c.body.bones.iter().map(|b| b.position), SELECTED_COLOR);
+        }
+        match hover {
+            Some(Pick::Bone { critter, .. }) => {
+                if let Some(c) = self.critters.get(critter) {
+                    self.draw_outline(out, c.body.bones.iter().map(|b| b.position), HOVER_COLOR)
+                }
+            }
+            Some(Pick::Food(i)) => {
+                if let Some(&food) = self.food.get(i) {
+                    self.draw_outline(out, [food], HOVER_COLOR)
+                }
+            }
+            Some(Pick::Wall(i)) => {
+                if let Some(wall) = self.walls.get(i) {
+                    self.draw_outline(out, wall.iter().copied(), HOVER_COLOR)
+                }
+            }
+            None => (),
+        }
+    }
+
+    /// Rectangle around points, with some margin.
+    fn draw_outline(&self, out: &mut Out, points: impl IntoIterator<Item = vec2f>, color: RGBA) {
+        let margin = vec2(6.0, 6.0);
+        let Some((min, max)) = points.into_iter().fold(None, |acc: Option<(vec2f, vec2f)>, p| match acc {
+            None => Some((p, p)),
+            Some((min, max)) => Some((min.zip_with(p, f32::min), max.zip_with(p, f32::max))),
+        }) else {
+            return;
+        };
+        out.draw_rect(L_UI_FG, Rectangle::new((min - margin, max + margin), color));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// Critter with horizontal bones centered at x = 100, 85, 70, ... at height `y`.
+    fn critter(y: f32) -> Critter {
+        Critter::new(8, 6).with(|c| c.body.bones.iter_mut().for_each(|b| b.position[1] = y))
+    }
+
+    /// Critter at y = 150, food below it.
+    fn world() -> World {
+        World {
+            critters: vec![critter(150.0)],
+            food: vec![vec2(100.0, 170.0)],
+            walls: vec![],
+        }
+    }
+
+    #[gtest]
+    fn pick_nearest() {
+        let w = world();
+        expect_that!(w.pick(vec2(100.0, 152.0), PICK_RADIUS), some(eq(Pick::Bone { critter: 0, bone: 0 }))); // 👈 bone 1 is in range too
+        expect_that!(w.pick(vec2(72.0, 148.0), PICK_RADIUS), some(eq(Pick::Bone { critter: 0, bone: 2 })));
+        expect_that!(w.pick(vec2(100.0, 164.0), PICK_RADIUS), some(eq(Pick::Food(0)))); // 👈 food 6 away, bone 14
+    }
+
+    #[gtest]
+    fn pick_outside_radius() {
+        let w = world();
+        expect_that!(w.pick(vec2(100.0, 160.0), 4.0), none()); // 👈 bone and food both 10 away
+        expect_that!(w.pick(vec2(500.0, 500.0), PICK_RADIUS), none());
+        expect_that!(World { critters: vec![], food: vec![], walls: vec![] }.pick(vec2(0.0, 0.0), PICK_RADIUS), none());
+    }
+
+    #[gtest]
+    fn pick_wall() {
+        let w = world().with(|w| w.walls.push(vec![vec2(0.0, 300.0), vec2(200.0, 300.0), vec2(200.0, 400.0)]));
+        expect_that!(w.pick(vec2(50.0, 297.0), PICK_RADIUS), some(eq(Pick::Wall(0))));
+        expect_that!(w.pick(vec2(205.0, 350.0), PICK_RADIUS), some(eq(Pick::Wall(0)))); // 👈 second segment
+        expect_that!(w.pick(vec2(50.0, 290.0), 4.0), none());
+    }
+
+    #[gtest]
+    fn remove_pick() {
+        let mut w = world().with(|w| {
+            w.critters.push(critter(500.0));
+            w.walls.push(vec![vec2(0.0, 300.0), vec2(200.0, 300.0)]);
+        });
+        expect_that!(w.remove_pick(Pick::Bone { critter: 0, bone: 1 }), some(eq(0)));
+        expect_that!(w.critters.len(), eq(1));
+        expect_that!(w.critters[0].body.bones[0].position.y(), eq(500.0)); // 👈 the other critter moved down
+        expect_that!(w.remove_pick(Pick::Food(0)), none());
+        expect_that!(w.food, is_empty());
+        expect_that!(w.remove_pick(Pick::Wall(0)), none());
+        expect_that!(w.walls, is_empty());
+
+        // out of range: nothing happens
+        expect_that!(w.remove_pick(Pick::Bone { critter: 5, bone: 0 }), none());
+        expect_that!(w.remove_pick(Pick::Food(0)), none());
+        expect_that!(w.critters.len(), eq(1));
+    }
+
+    #[gtest]
+    fn move_pick() {
+        let mut w = world();
+        w.critters[0].body.bones[1].velocity = vec2(3.0, 4.0);
+        w.move_pick(Pick::Bone { critter: 0, bone: 1 }, vec2(10.0, 20.0));
+        let bone = &w.critters[0].body.bones[1];
+        expect_that!((bone.position, bone.velocity), eq((vec2(10.0, 20.0), vec2f::ZERO))); // 👈 stopped dead
+        w.move_pick(Pick::Food(0), vec2(1.0, 2.0));
+        expect_that!(w.food, eq(&[vec2(1.0, 2.0)]));
+        w.move_pick(Pick::Food(7), vec2(1.0, 2.0)); // 👈 out of range: nothing happens
+        w.move_pick(Pick::Bone { critter: 3, bone: 0 }, vec2(1.0, 2.0));
+    }
+}
@@ -4,10 +4,11 @@ pub use core_util::cross;
 pub use gamelib::*;
 pub use geometry::linterp;
 pub use proc_macros::*;
+pub use reflect::{Reflect, ReflectPath};
 pub use shell_api::*;
 pub use vector::*;
 
-pub use anyhow::{Error, Result, anyhow};
+pub use anyhow::{Error, Result, anyhow, bail};
 pub use itertools::Itertools as _;
 pub use num_traits::AsPrimitive as _;
 pub use serde::{Deserialize, Serialize};
@@ -0,0 +1,375 @@
+//! 🛠 What the mouse does in the world.
+use crate::prelude::*;
+use std::str::FromStr;
+
+/// Current mouse tool, see the `tool` command.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tool {
+    /// Hover highlights, click selects a critter, drag moves critters and food.
+    #[default]
+    Select,
+    /// The first food item follows the mouse.
+    Feed,
+    /// Click places a food item.
+    Food,
+    /// Hold to spray food around the mouse.
+    Spray,
+    /// Click spawns a copy of the selected critter (or a new one).
+    Spawn,
+    /// Click deletes a critter, food item or wall.
+    Delete,
+    /// Drag to draw a wall (obstacle polyline).
+    Wall,
+}
+
+impl Tool {
+    pub const ALL: [Self; 7] = [Self::Select, Self::Feed, Self::Food, Self::Spray, Self::Spawn, Self::Delete, Self::Wall];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Feed => "feed",
+            Self::Food => "food",
+            Self::Spray => "spray",
+            Self::Spawn => "spawn",
+            Self::Delete => "delete",
+            Self::Wall => "wall",
+        }
+    }
+
+    /// Virtual button that selects this tool (bound to number keys, see `Tool::keymap`).
+    pub fn button(self) -> Button {
+        match self {
+            Self::Select => button!("tool_select"),
+            Self::Feed => button!("tool_feed"),
+            Self::Food => button!("tool_food"),
+            Self::Spray => button!("tool_spray"),
+            Self::Spawn => button!("tool_spawn"),
+            Self::Delete => button!("tool_delete"),
+            Self::Wall => button!("tool_wall"),
+        }
+    }
+
+    /// Default shortcuts: number keys 1..=7 select tools.
+    pub(crate) fn keymap() -> impl Iterator<Item = (Button, Button)> {
+        let keys = [button!("1"), button!("2"), button!("3"), button!("4"), button!("5"), button!("6"), button!("7")];
+        keys.into_iter().zip(Self::ALL.map(Self::button))
+    }
+}
+
+impl FromStr for Tool {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == s).ok_or_else(|| anyhow!("unknown tool {s:?}, have: {}", Self::ALL.map(Tool::name).join(", ")))
+    }
+}
+
+/// Food items added per tick by `Tool::Spray`, and their spread (world units).
+const SPRAY_RATE: usize = 2;
+const SPRAY_RADIUS: f32 = 20.0;
+
+/// Minimum distance between wall points (screen pixels).
+const WALL_STEP: f32 = 8.0;
+
+/// 🎨 Tool palette, bottom-left of the screen.
+const PALETTE_CHARS: usize = 10;
+const PALETTE_BG: RGBA = RGBA([0, 0, 0, 184]);
+const PALETTE_SEL: RGBA = RGBA([255, 255, 255, 64]);
+
+impl GameState {
+    /// Apply the current tool. `mouse_world`: mouse position in world coordinates.
+    pub(crate) fn tick_tool(&mut self, mouse_world: vec2f) {
+        if self.tick_palette() {
+            return;
+        }
+        let radius = PICK_RADIUS / self.camera.camera.zoom;
+        let mouse1 = self.inputs.just_pressed(K_MOUSE1);
+        if !matches!(self.tool, Tool::Select | Tool::Delete) {
+            self.hover = None;
+        }
+        if self.tool != Tool::Select {
+            self.dragging = None;
+        }
+        if self.tool != Tool::Wall {
+            self.drawing_wall = None;
+        }
+        match self.tool {
+            Tool::Select => {
+                self.hover = self.world.pick(mouse_world, radius);
+                if mouse1 {
+                    self.dragging = self.hover;
+                    if let Some(Pick::Bone { critter, .. }) = self.hover {
+                        self.selected_critter = Some(critter);
+                    }
+                }
+                if !self.inputs.is_down(K_MOUSE1) {
+                    self.dragging = None;
+                }
+                if let Some(pick) = self.dragging {
+                    self.world.move_pick(pick, mouse_world);
+                }
+            }
+            Tool::Feed => self.world.move_pick(Pick::Food(0), mouse_world),
+            Tool::Food => {
+                if mouse1 {
+                    self.world.food.push(mouse_world);
+                }
+            }
+            Tool::Spray => {
+                if self.inputs.is_down(K_MOUSE1) {
+                    for _ in 0..SPRAY_RATE {
+                        let jitter = vec2(self.rng.gen_range(-1.0..=1.0), self.rng.gen_range(-1.0..=1.0)) * SPRAY_RADIUS;
+                        self.world.food.push(mouse_world + jitter);
+                    }
+                }
+            }
+            Tool::Spawn => {
+                if mouse1 {
+                    self.spawn_critter(mouse_world);
+                }
+            }
+            Tool::Delete => {
+                self.hover = self.world.pick(mouse_world, radius);
+                if let (true, Some(pick)) = (mouse1, self.hover) {
+                    self.delete_pick(pick);
+                }
+            }
+            Tool::Wall => self.tick_wall_tool(mouse_world),
+        }
+    }
+
+    /// Copy of the selected critter (or a new one), head at `pos`. Selects it.
+    pub(crate) fn spawn_critter(&mut self, pos: vec2f) {
+        let mut critter = self.selected_critter().cloned().unwrap_or_else(|_| Critter::new(8, 6));
+        let head = critter.body.bones.first().map(|b| b.position).unwrap_or_default();
+        for bone in &mut critter.body.bones {
+            bone.position += pos - head;
+            bone.velocity = vec2::ZERO;
+            bone.velocity_half = vec2::ZERO;
+        }
+        self.world.critters.push(critter);
+        self.selected_critter = Some(self.world.critters.len() - 1);
+    }
+
+    /// Delete a critter, food item or wall, keeping the selection pointing at the same critter.
+    pub(crate) fn delete_pick(&mut self, pick: Pick) {
+        self.hover = None;
+        self.dragging = None;
+        if let Some(removed) = self.world.remove_pick(pick) {
+            self.selected_critter = match self.selected_critter {
+                Some(sel) if sel == removed => None,
+                Some(sel) if sel > removed => Some(sel - 1),
+                sel => sel,
+            };
+        }
+    }
+
+    /// Press to start a wall, drag to add points, release to finish.
+    fn tick_wall_tool(&mut self, mouse_world: vec2f) {
+        if self.inputs.just_pressed(K_MOUSE1) {
+            self.world.walls.push(vec![mouse_world]);
+            self.drawing_wall = Some(self.world.walls.len() - 1);
+        }
+        if !self.inputs.is_down(K_MOUSE1) {
+            if let Some(i) = self.drawing_wall.take().filter(|&i| self.world.walls.get(i).is_some_and(|w| w.len() < 2)) {
+                self.world.walls.remove(i); // 👈 just a click, not a wall
+            }
+            return;
+        }
+        let step = WALL_STEP / self.camera.camera.zoom;
+        let wall = self.drawing_wall.and_then(|i| self.world.walls.get_mut(i));
+        if let Some(wall) = wall.filter(|w| w.last().is_none_or(|last| last.distance_to(mouse_world) > step)) {
+            wall.push(mouse_world);
+        }
+    }
+
+    /// Number key shortcuts and palette clicks.
+    /// Returns `true` if the mouse click was eaten by the palette.
+    fn tick_palette(&mut self) -> bool {
+        if let Some(tool) = Tool::ALL.into_iter().find(|t| self.inputs.just_pressed(t.button())) {
+            self.tool = tool;
+        }
+        match palette_row(self.inputs.mouse_position(), self.viewport) {
+            Some(i) => {
+                if self.inputs.just_pressed(K_MOUSE1) {
+                    self.tool = Tool::ALL[i];
+                    self.inputs.consume(K_MOUSE1);
+                }
+                self.hover = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn draw_palette(&self, out: &mut Out) {
+        let char_size = EMBEDDED_CHAR_SIZE.as_i32();
+        let (pos, size) = palette_bounds(out.viewport_size);
+        out.draw_rect_screen(L_UI_BG, Rectangle::with_size(pos, size, RGBA::TRANSPARENT).with_fill(PALETTE_BG));
+        for (i, tool) in Tool::ALL.into_iter().enumerate() {
+            let row = pos + vec2(0, i as i32 * char_size.y());
+            if tool == self.tool {
+                out.draw_rect_screen(L_UI_BG, Rectangle::with_size(row, (size.x(), char_size.y()), RGBA::TRANSPARENT).with_fill(PALETTE_SEL));
+            }
+            out.draw_text_screen(L_UI, row, format!("{} {}", i + 1, tool.name()));
+        }
+    }
+}
+
+pub(crate) fn tool_rng() -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(0)
+}
+
+/// Top-left corner and size of the palette.
+fn palette_bounds(viewport: vec2u) -> (vec2i, vec2i) {
+    let char_size = EMBEDDED_CHAR_SIZE.as_i32();
+    let size = vec2(PALETTE_CHARS as i32 * char_size.x(), Tool::ALL.len() as i32 * char_size.y());
+    (vec2(0, viewport.y() as i32 - size.y()), size)
+}
+
+/// Palette row under the mouse, if any.
+fn palette_row(mouse: vec2i, viewport: vec2u) -> Option<usize> {
+    let (pos, size) = palette_bounds(viewport);
+    let rel = mouse - pos;
+    (rel.x() >= 0 && rel.y() >= 0 && rel.x() < size.x() && rel.y() < size.y()).then(|| (rel.y() / EMBEDDED_CHAR_SIZE.y() as i32) as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// Game with one critter (head at (100, 150)), one food item, no walls.
+    fn game() -> GameState {
+        GameState::new().with(|g| {
+            g.viewport = vec2(640, 480);
+            g.world.food = vec![vec2(120.0, 230.0)];
+        })
+    }
+
+    /// Press (`Some(true)`) or release (`Some(false)`) mouse button 1 (or neither) with the mouse at `world`.
+    /// The mouse is well clear of the palette, its screen position does not matter for tools.
+    fn mouse(g: &mut GameState, world: (f32, f32), button: Option<bool>) {
+        let mut events = vec![InputEvent::MouseMove { position: vec2(400, 100) }];
+        if let Some(down) = button {
+            let direction = if down { KeyDir::Down } else { KeyDir::Up };
+            events.push(InputEvent::Key { button: K_MOUSE1, direction });
+        }
+        g.inputs.tick(&g.keymap, events.into_iter());
+        g.tick_tool(vec2(world.0, world.1));
+    }
+
+    fn key(g: &mut GameState, button: Button) {
+        let events = [KeyDir::Down, KeyDir::Up].map(|direction| InputEvent::Key { button, direction });
+        for event in events {
+            g.inputs.tick(&g.keymap, [event].into_iter());
+            g.tick_tool(vec2f::ZERO);
+        }
+    }
+
+    #[gtest]
+    fn number_keys_pick_tool() {
+        let mut g = game();
+        key(&mut g, button!("3"));
+        expect_that!(g.tool, eq(Tool::Food));
+        key(&mut g, button!("7"));
+        expect_that!(g.tool, eq(Tool::Wall));
+    }
+
+    #[gtest]
+    fn food_tool() {
+        let mut g = game().with(|g| g.tool = Tool::Food);
+        mouse(&mut g, (10.0, 20.0), Some(true));
+        mouse(&mut g, (30.0, 40.0), None); // 👈 held: no more food
+        mouse(&mut g, (30.0, 40.0), Some(false));
+        expect_that!(g.world.food, eq(&[vec2(120.0, 230.0), vec2(10.0, 20.0)]));
+    }
+
+    #[gtest]
+    fn spray_tool_while_paused() {
+        let mut g = game().with(|g| {
+            g.tool = Tool::Spray;
+            g.paused = true;
+            g.world.food.clear();
+        });
+        mouse(&mut g, (0.0, 0.0), Some(true));
+        mouse(&mut g, (0.0, 0.0), None);
+        expect_that!(g.world.food.len(), eq(2 * SPRAY_RATE));
+        expect_that!(g.world.food.iter().all(|f| f.len() <= SPRAY_RADIUS * 2.0_f32.sqrt()), eq(true));
+        expect_that!(g.world.food.iter().tuple_combinations().all(|(a, b)| a != b), eq(true)); // 👈 `tick` did not advance
+    }
+
+    #[gtest]
+    fn spawn_tool() {
+        let mut g = game().with(|g| g.tool = Tool::Spawn);
+        g.selected_critter = None;
+        mouse(&mut g, (300.0, 40.0), Some(true));
+        expect_that!(g.world.critters.len(), eq(2));
+        expect_that!(g.selected_critter, some(eq(1)));
+        expect_that!(g.world.critters[1].body.bones[0].position, eq(vec2(300.0, 40.0)));
+
+        mouse(&mut g, (300.0, 40.0), Some(false));
+        mouse(&mut g, (0.0, 400.0), Some(true)); // 👈 copies the selected critter
+        expect_that!(g.world.critters.len(), eq(3));
+        expect_that!(g.selected_critter, some(eq(2)));
+        expect_that!(g.world.critters[2].body.bones[1].position, eq(vec2(-15.0, 400.0)));
+    }
+
+    #[gtest]
+    fn delete_tool() {
+        let mut g = game().with(|g| g.tool = Tool::Delete);
+        g.world.walls.push(vec![vec2(0.0, 300.0), vec2(200.0, 300.0)]);
+
+        mouse(&mut g, (500.0, 500.0), Some(true)); // 👈 nothing there
+        mouse(&mut g, (120.0, 232.0), None); // 👈 hovering only
+        expect_that!(g.hover, some(eq(Pick::Food(0))));
+        expect_that!(g.world.food.len(), eq(1));
+
+        let click = |g: &mut GameState, pos| {
+            mouse(g, pos, Some(false));
+            mouse(g, pos, Some(true));
+        };
+        click(&mut g, (120.0, 232.0));
+        expect_that!(g.world.food, is_empty());
+        click(&mut g, (50.0, 303.0));
+        expect_that!(g.world.walls, is_empty());
+        click(&mut g, (100.0, 152.0));
+        expect_that!(g.world.critters.len(), eq(0));
+        expect_that!(g.selected_critter, none());
+    }
+
+    #[gtest]
+    fn delete_pick_keeps_selection() {
+        let mut g = game();
+        g.world.critters = (0..4).map(|i| Critter::new(8, 6).with(|c| c.body.bones.iter_mut().for_each(|b| b.position[1] = i as f32 * 100.0))).collect();
+        let selected_y = |g: &GameState| g.selected_critter.map(|i| g.world.critters[i].body.bones[0].position.y());
+
+        g.selected_critter = Some(2);
+        g.delete_pick(Pick::Bone { critter: 3, bone: 0 }); // 👈 after the selection
+        expect_that!(g.selected_critter, some(eq(2)));
+        g.delete_pick(Pick::Bone { critter: 0, bone: 0 }); // 👈 before the selection
+        expect_that!(g.selected_critter, some(eq(1)));
+        expect_that!(selected_y(&g), some(eq(200.0)));
+        g.delete_pick(Pick::Food(0));
+        expect_that!(g.selected_critter, some(eq(1)));
+        g.delete_pick(Pick::Bone { critter: 1, bone: 2 }); // 👈 the selection itself
+        expect_that!(g.selected_critter, none());
+        expect_that!(g.world.critters.len(), eq(1));
+    }
+
+    #[gtest]
+    fn wall_tool() {
+        let mut g = game().with(|g| g.tool = Tool::Wall);
+        mouse(&mut g, (0.0, 0.0), Some(true));
+        mouse(&mut g, (4.0, 0.0), None); // 👈 too close to the last point
+        mouse(&mut g, (20.0, 0.0), None);
+        mouse(&mut g, (20.0, 20.0), None);
+        mouse(&mut g, (20.0, 20.0), Some(false));
+        expect_that!(g.world.walls, eq(&[vec![vec2(0.0, 0.0), vec2(20.0, 0.0), vec2(20.0, 20.0)]]));
+
+        mouse(&mut g, (50.0, 50.0), Some(true)); // 👈 just a click
+        mouse(&mut g, (50.0, 50.0), Some(false));
+        expect_that!(g.world.walls.len(), eq(1));
+    }
+}
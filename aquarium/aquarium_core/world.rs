@@ -1,17 +1,26 @@
 use crate::prelude::*;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Reflect)]
 pub struct World {
     pub critters: Vec<Critter>,
     pub food: Vec<vec2f>,
+    /// Obstacles (polylines) that critters bump into.
+    #[serde(default)] // 👈 older RON exports
+    pub walls: Vec<Vec<vec2f>>,
 }
 
+/// Distance (screen pixels) between a critter's head and the top of its label.
+const LABEL_OFFSET: f32 = 32.0;
+
+/// How close bones can get to a wall.
+const WALL_RADIUS: f32 = 4.0;
+
 impl World {
     pub fn test1() -> Self {
         let critters = vec![Critter::new(8, 6)];
         let food = vec![vec2(120.0, 230.0)]; //, vec2(110.0, 55.0), vec2(410.0, 100.0)];
 
-        Self { critters, food }
+        Self { critters, food, walls: vec![] }
     }
 
     /// Test world with a dummy creature that is just a harmonic oscillator,
@@ -20,23 +29,63 @@ impl World {
         let critters = vec![Critter::harmonic_osc()];
         let food = vec![];
 
-        Self { critters, food }
+        Self { critters, food, walls: vec![] }
     }
 
     pub fn tick(&mut self, now: f64, dt: f32) {
         self.critters.iter_mut().for_each(|v| v.tick(now, dt, &self.food));
+        self.collide_walls();
+    }
+
+    /// Push bones out of walls, stop them moving into the wall.
+    fn collide_walls(&mut self) {
+        let segments = self.wall_segments().collect_vec();
+        for bone in self.critters.iter_mut().flat_map(|c| c.body.bones.iter_mut()) {
+            for &(a, b) in &segments {
+                let closest = closest_on_segment(bone.position, a, b);
+                let dist = bone.position.distance_to(closest);
+                if dist < WALL_RADIUS && dist > 0.0 {
+                    let normal = (bone.position - closest) / dist;
+                    bone.position = closest + WALL_RADIUS * normal;
+                    bone.velocity -= f32::min(0.0, bone.velocity.dot(normal)) * normal;
+                    bone.velocity_half -= f32::min(0.0, bone.velocity_half.dot(normal)) * normal;
+                }
+            }
+        }
+    }
+
+    /// All line segments of all walls.
+    pub fn wall_segments(&self) -> impl Iterator<Item = (vec2f, vec2f)> + '_ {
+        self.walls.iter().flat_map(|w| w.iter().copied().tuple_windows())
     }
 
     pub(crate) fn draw(&self, out: &mut Out) {
         self.draw_background(out);
         self.critters.iter().for_each(|v| v.draw(out));
         self.food.iter().for_each(|v| self.draw_food(out, *v));
+        self.draw_walls(out);
+        self.draw_labels(out);
+    }
+
+    /// Critter numbers above their heads, in screen space so that they stay readable when zoomed out.
+    fn draw_labels(&self, out: &mut Out) {
+        let style = TextStyle::new(&DEJAVU_SANS).with_align(Align::Center).with_color(RGBA([255, 255, 255, 192]));
+        for (i, critter) in self.critters.iter().enumerate() {
+            if let Some(head) = critter.body.bones.first() {
+                let pos = out.camera.world_to_screen(head.position, out.viewport_size) - vec2(0.0, LABEL_OFFSET);
+                out.draw_text_styled(L_TEXT, pos, format!("critter {i}"), style);
+            }
+        }
+    }
+
+    fn draw_walls(&self, out: &mut Out) {
+        for wall in &self.walls {
+            out.draw_path(L_SPRITES, Path::polyline(wall.iter().copied()).with_color(RGBA::GRAY).with_width(2 * WALL_RADIUS as u8));
+        }
     }
 
     fn draw_food(&self, out: &mut Out, pos: vec2f) {
-        let color = RGBA::GREEN;
-        let radius = vec2(2, 2);
-        out.draw_rect_screen(L_SPRITES + 1, Rectangle::with_radius(pos.as_i32(), radius, color));
+        out.draw_circle(L_SPRITES + 1, Circle::filled(pos, 2.5, RGBA::GREEN));
     }
 
     fn draw_background(&self, out: &mut Out) {
@@ -45,3 +94,10 @@ impl World {
         out.draw_rect_screen(0, Rectangle::from((((0, 0), (w, h)), bg)).with_fill(bg));
     }
 }
+
+/// Point on line segment `a`-`b` nearest to `p`.
+pub(crate) fn closest_on_segment(p: vec2f, a: vec2f, b: vec2f) -> vec2f {
+    let d = b - a;
+    let t = if d.len2() == 0.0 { 0.0 } else { ((p - a).dot(d) / d.len2()).clamp(0.0, 1.0) };
+    a + t * d
+}
@@ -0,0 +1,156 @@
+//! 🗺️ Packs all sprites in an assets directory into one texture atlas (`ATLAS_IMAGE` + `ATLAS_INDEX`).
+//! Saves hundreds of HTTP requests (web) and texture switches (both shells).
+//!
+//! Run offline for the web shell (`cargo run -p atlas_builder -- aquarium/assets`),
+//! the macroquad shell builds it in memory at startup.
+//!
+//! Also bakes TrueType fonts into glyph atlases (`--font`, see `bake_font`).
+mod bake_font;
+
+pub use bake_font::*;
+
+use anyhow::{Context, Result, bail};
+use image::RgbaImage;
+use shell_api::*;
+use std::path::Path;
+use vector::*;
+
+/// Pack all `*.png` files under `dir` (recursively, except a previously built atlas).
+/// Sprites are named by their path relative to `dir`, without `.png`, e.g. `font`, `ui/button`.
+/// Sprite sheet metadata (`<sprite>.sheet.ron`, see `SpriteSheet`) is added to the index.
+pub fn build_atlas(dir: impl AsRef<Path>) -> Result<(RgbaImage, AtlasIndex)> {
+    let dir = dir.as_ref();
+    let mut images = Vec::new();
+    let mut sheets = Vec::new();
+    find_assets(dir, dir, &mut images, &mut sheets)?;
+
+    let mut index = AtlasIndex::pack(images.iter().map(|(name, img)| (name.clone(), vec2(img.width(), img.height()))))?;
+    for (name, sheet) in sheets {
+        let Some(sprite) = index.sprites.get(&name) else {
+            bail!("{name}.{SHEET_EXTENSION}: no {name}.png");
+        };
+        let size = sheet.frame_size.map(|v| v.max(1)); // 👈 as in `SpriteSheet::cell_rect`
+        let (cols, rows) = (u32::max(1, sprite.size.x() / size.x()), u32::max(1, sprite.size.y() / size.y()));
+        for (animation, clip) in &sheet.animations {
+            if let Some(f) = clip.frames.iter().map(|f| f.cell).find(|&f| f >= cols * rows) {
+                bail!("{name}.{SHEET_EXTENSION}: animation {animation:?}: frame {f} outside {cols}x{rows} grid");
+            }
+        }
+        for animation in sheet.animations.keys() {
+            if let Some(other) = index.sheets.iter().find_map(|(other, s)| s.animations.contains_key(animation).then_some(other)) {
+                bail!("{name}.{SHEET_EXTENSION}: animation {animation:?} is already in {other}.{SHEET_EXTENSION}"); // 👈 names must be unique, see `Animation`
+            }
+        }
+        index.sheets.insert(name, sheet);
+    }
+
+    let mut atlas = RgbaImage::new(index.size, index.size);
+    for (name, img) in &images {
+        let rect = index.sprites[name];
+        image::imageops::replace(&mut atlas, img, rect.pos.x().into(), rect.pos.y().into());
+    }
+    Ok((atlas, index))
+}
+
+/// `build_atlas`, and write the result to `dir`.
+pub fn write_atlas(dir: impl AsRef<Path>) -> Result<AtlasIndex> {
+    let dir = dir.as_ref();
+    let (atlas, index) = build_atlas(dir)?;
+    atlas.save(dir.join(ATLAS_IMAGE)).context(ATLAS_IMAGE)?;
+    std::fs::write(dir.join(ATLAS_INDEX), index.to_ron()? + "\n").context(ATLAS_INDEX)?;
+    Ok(index)
+}
+
+fn find_assets(root: &Path, dir: &Path, images: &mut Vec<(String, RgbaImage)>, sheets: &mut Vec<(String, SpriteSheet)>) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir).with_context(|| format!("{dir:?}"))?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|e| e.path()); // 👈 deterministic output
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            find_assets(root, &path, images, sheets)?;
+        } else if path.extension().is_some_and(|ext| ext == "png") && path != root.join(ATLAS_IMAGE) {
+            let name = path.strip_prefix(root)?.with_extension("").to_string_lossy().replace('\\', "/");
+            let img = image::open(&path).with_context(|| format!("{path:?}"))?.to_rgba8();
+            images.push((name, img));
+        } else if let Some(name) = path.strip_prefix(root)?.to_string_lossy().strip_suffix(&format!(".{SHEET_EXTENSION}")) {
+            let sheet = SpriteSheet::from_ron(&std::fs::read_to_string(&path)?).with_context(|| format!("{path:?}"))?;
+            sheets.push((name.replace('\\', "/"), sheet));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+    use itertools::Itertools as _;
+
+    /// Fresh, empty directory for a test's assets.
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas_builder_{name}_{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("ui")).unwrap();
+        dir
+    }
+
+    #[gtest]
+    fn pack_directory() {
+        let dir = temp_dir("pack_directory");
+        for (name, w, h) in [("a", 10, 20), ("ui/b", 30, 5), ("crab", 64, 32)] {
+            RgbaImage::from_pixel(w, h, image::Rgba([1, 2, 3, 255])).save(dir.join(format!("{name}.png"))).unwrap();
+        }
+        std::fs::write(dir.join("crab.sheet.ron"), r#"(frame_size: (32, 32), animations: {"crab_walk": (frames: [(cell: 0, duration: 0.1), (cell: 1, duration: 0.1)])})"#).unwrap();
+
+        let index = write_atlas(&dir).unwrap();
+        expect_that!(index.sprites.keys().collect::<Vec<_>>(), eq(&["a", "crab", "ui/b"]));
+        expect_that!(index.sprites["a"].size, eq(vec2(10, 20)));
+        expect_that!(index.sprites["ui/b"].size, eq(vec2(30, 5)));
+        expect_that!(index.sprites["crab"].size, eq(vec2(64, 32)));
+        for (name, r) in &index.sprites {
+            expect_that!((r.pos + r.size).iter().all(|v| v <= index.size), eq(true), "{name}");
+        }
+        for ((a, ra), (b, rb)) in index.sprites.iter().tuple_combinations() {
+            let apart = (0..2).any(|i| ra.pos[i] + ra.size[i] <= rb.pos[i] || rb.pos[i] + rb.size[i] <= ra.pos[i]);
+            expect_that!(apart, eq(true), "{a} overlaps {b}");
+        }
+        expect_that!(index.sheets["crab"].animations["crab_walk"].frames.len(), eq(2));
+
+        let ron = std::fs::read_to_string(dir.join(ATLAS_INDEX)).unwrap();
+        expect_that!(ron, ends_with("\n"));
+        expect_that!(AtlasIndex::from_ron(&ron).unwrap(), eq(&index));
+        expect_that!(AtlasIndex::from_ron(&index.to_ron().unwrap()).unwrap(), eq(&index));
+        expect_that!(image::open(dir.join(ATLAS_IMAGE)).unwrap().width(), eq(index.size));
+
+        expect_that!(write_atlas(&dir).unwrap(), eq(&index)); // 👈 the atlas itself is not packed again
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[gtest]
+    fn duplicate_animation() {
+        let dir = temp_dir("duplicate_animation");
+        for name in ["crab", "ui/crab"] {
+            RgbaImage::new(64, 32).save(dir.join(format!("{name}.png"))).unwrap();
+            std::fs::write(dir.join(format!("{name}.sheet.ron")), r#"(frame_size: (32, 32), animations: {"crab_walk": (frames: [(cell: 0, duration: 0.1)])})"#).unwrap();
+        }
+        expect_that!(build_atlas(&dir).map(drop), err(displays_as(eq(r#"ui/crab.sheet.ron: animation "crab_walk" is already in crab.sheet.ron"#))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[gtest]
+    fn frame_outside_grid() {
+        let dir = temp_dir("frame_outside_grid");
+        RgbaImage::new(64, 32).save(dir.join("crab.png")).unwrap();
+        std::fs::write(dir.join("crab.sheet.ron"), r#"(frame_size: (32, 16), animations: {"crab_walk": (frames: [(cell: 3, duration: 0.1), (cell: 4, duration: 0.1)])})"#).unwrap();
+        expect_that!(build_atlas(&dir).map(drop), err(displays_as(eq(r#"crab.sheet.ron: animation "crab_walk": frame 4 outside 2x2 grid"#))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[gtest]
+    fn sheet_without_sprite() {
+        let dir = temp_dir("sheet_without_sprite");
+        std::fs::write(dir.join("ui/fish.sheet.ron"), "(frame_size: (8, 8), animations: {})").unwrap();
+        expect_that!(build_atlas(&dir).map(drop), err(displays_as(eq("ui/fish.sheet.ron: no ui/fish.png"))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+//! 🔤 Bakes a TrueType font into a glyph atlas (`<name>.png`) + BMFont metrics (`<name>.fnt`, see `Font::from_bmfont`).
+//! The glyph atlas is then packed into the texture atlas like any other sprite.
+use anyhow::{Context, Result, anyhow, bail};
+use binpack2d::*;
+use image::{Rgba, RgbaImage};
+use shell_api::*;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use vector::*;
+
+/// Characters baked by default: ASCII, Latin-1, Latin Extended-A, Greek, general punctuation, arrows.
+pub const DEFAULT_CHARSET: &[RangeInclusive<char>] = &[' '..='~', '\u{a0}'..='\u{17f}', '\u{391}'..='\u{3c9}', '\u{2010}'..='\u{2027}', '\u{2190}'..='\u{2195}'];
+
+/// Rasterize `chars` (those that are in the font) at `size` pixels.
+/// White glyphs (coverage as alpha), so that they can be tinted (`TextStyle::color`).
+pub fn bake_font(ttf: &[u8], size: f32, name: &str, chars: impl IntoIterator<Item = char>) -> Result<(RgbaImage, Font)> {
+    let ttf = fontdue::Font::from_bytes(ttf, fontdue::FontSettings { scale: size, ..Default::default() }).map_err(|e| anyhow!("{e}"))?;
+    let line = ttf.horizontal_line_metrics(size).context("font has no horizontal metrics")?;
+    let chars = chars.into_iter().filter(|&c| c == ' ' || ttf.lookup_glyph_index(c) != 0).collect::<Vec<_>>();
+    let glyphs = chars.iter().map(|&c| (c, ttf.rasterize(c, size))).collect::<Vec<_>>();
+
+    // 👇 pack the non-empty glyphs, doubling the atlas size until they fit.
+    let sizes = glyphs.iter().filter(|(_, (m, _))| m.width != 0 && m.height != 0).map(|(c, (m, _))| (vec2(m.width, m.height).as_u32(), *c)).collect::<Vec<_>>();
+    let mut atlas_size = 64;
+    let placed = loop {
+        let mut packer = MaxRectsPacker::new(vec2(atlas_size, atlas_size), PackOptions::default().with_margin(1));
+        match binpack2d(&mut packer, sizes.iter().copied()) {
+            Ok(placed) => break placed.into_iter().collect::<HashMap<_, _>>(),
+            Err(e) if atlas_size >= MAX_ATLAS_SIZE => bail!("{} glyphs don't fit: {e}", sizes.len()),
+            Err(_) => atlas_size *= 2,
+        }
+    };
+
+    let mut image = RgbaImage::new(atlas_size, atlas_size);
+    let sprite = Sprite {
+        file: FromStr::from_str(name).map_err(|e| anyhow!("font name {name:?}: {e}"))?,
+    };
+    let mut font = Font {
+        sprite,
+        size,
+        line_height: line.new_line_size.ceil(),
+        glyphs: Default::default(),
+        kerning: Default::default(),
+    };
+    let ascent = line.ascent.round();
+    for (c, (m, coverage)) in &glyphs {
+        let pos = placed.get(c).map(|p| p.pos).unwrap_or_default();
+        for (i, &alpha) in coverage.iter().enumerate() {
+            image.put_pixel(pos.x() + (i % m.width) as u32, pos.y() + (i / m.width) as u32, Rgba([255, 255, 255, alpha]));
+        }
+        let glyph = Glyph {
+            src_pos: pos.as_u16(),
+            size: vec2(m.width, m.height).as_u16(),
+            offset: vec2(m.xmin as f32, ascent - (m.ymin + m.height as i32) as f32),
+            advance: m.advance_width.round(),
+        };
+        font.glyphs.insert(*c, glyph);
+    }
+    for &a in &chars {
+        for &b in &chars {
+            if let Some(kern) = ttf.horizontal_kern(a, b, size).map(f32::round)
+                && kern != 0.0
+            {
+                font.kerning.insert((a, b), kern);
+            }
+        }
+    }
+    Ok((image, font))
+}
+
+/// `bake_font` with `DEFAULT_CHARSET`, writing `<out>.png` and `<out>.fnt`. The file name of `out` is the font's sprite name.
+pub fn write_font(ttf: impl AsRef<Path>, size: f32, out: impl AsRef<Path>) -> Result<Font> {
+    let (ttf, out) = (ttf.as_ref(), out.as_ref());
+    let name = out.file_name().context("output name")?.to_string_lossy();
+    let data = std::fs::read(ttf).with_context(|| format!("{ttf:?}"))?;
+    let (image, font) = bake_font(&data, size, &name, DEFAULT_CHARSET.iter().cloned().flatten())?;
+    image.save(out.with_extension("png")).context("png")?;
+    std::fs::write(out.with_extension("fnt"), font.to_bmfont()).context("fnt")?;
+    Ok(font)
+}
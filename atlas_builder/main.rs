@@ -0,0 +1,17 @@
+use anyhow::{Result, bail};
+
+fn main() -> Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.as_slice() {
+        [dir] => {
+            let index = atlas_builder::write_atlas(dir)?;
+            println!("packed {} sprites in a {}x{} atlas", index.sprites.len(), index.size, index.size);
+        }
+        [flag, ttf, size, out] if flag == "--font" => {
+            let font = atlas_builder::write_font(ttf, size.parse()?, out)?;
+            println!("baked {} glyphs, {} kerning pairs", font.glyphs.len(), font.kerning.len());
+        }
+        _ => bail!("usage: atlas_builder <assets dir> | atlas_builder --font <file.ttf> <size px> <assets dir>/<name>"),
+    }
+    Ok(())
+}
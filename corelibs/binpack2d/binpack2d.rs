@@ -1,97 +1,207 @@
+mod maxrects;
+mod shelf;
+mod skyline;
+
+pub use maxrects::*;
+pub use shelf::*;
+pub use skyline::*;
+
+use anyhow::{Result, bail};
+use core_util::*;
 use vector::*;
 
-/// Pack rectangles of given sizes into a square.
-/// Return each rectangle's position inside the square.
-/// E.g.
+/// Places rectangles, one by one, inside a fixed-size bin (e.g. a texture atlas or glyph cache).
+pub trait Packer {
+    /// Place a rectangle of given size.
+    /// `Err` if it does not fit (anymore), in which case the packer is unchanged.
+    fn insert(&mut self, size: vec2u) -> Result<Placement>;
+
+    /// Fraction of the bin area (0.0 ..= 1.0) covered by inserted rectangles (not counting margins).
+    fn fullness(&self) -> f32;
+}
+
+/// Where a rectangle was placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Placement {
+    /// Top-left corner.
+    pub pos: vec2u,
+    /// Rotated by 90 degrees (so it occupies `size.yx()`), only with `PackOptions::rotate`.
+    pub rotated: bool,
+}
+
+/// Spacing and rotation, common to all packers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PackOptions {
+    /// Empty space between rectangles (e.g. against texture bleeding).
+    pub margin: u32,
+    /// Empty space between rectangles and the edges of the bin.
+    pub padding: u32,
+    /// Allow rotating rectangles by 90 degrees if that packs better.
+    pub rotate: bool,
+}
+
+impl PackOptions {
+    pub fn with_margin(self, margin: u32) -> Self {
+        self.with(|o| o.margin = margin)
+    }
+
+    pub fn with_padding(self, padding: u32) -> Self {
+        self.with(|o| o.padding = padding)
+    }
+
+    pub fn with_rotation(self, rotate: bool) -> Self {
+        self.with(|o| o.rotate = rotate)
+    }
+
+    /// Packers work in this area, placing rectangles grown by `margin`
+    /// (the last margin may overlap the padding, hence `+ margin`).
+    pub(crate) fn inner_size(&self, bin: vec2u) -> vec2u {
+        bin.map(|v| (v + self.margin).saturating_sub(2 * self.padding))
+    }
+
+    /// Rectangle size as seen by the packers (see `inner_size`).
+    pub(crate) fn outer_size(&self, size: vec2u) -> vec2u {
+        size.map(|v| v + self.margin)
+    }
+
+    /// Position in the bin of a position in the inner area.
+    pub(crate) fn bin_pos(&self, inner: vec2u) -> vec2u {
+        inner.map(|v| v + self.padding)
+    }
+}
+
+impl Placement {
+    /// Size occupied in the bin by a rectangle of given size.
+    pub fn placed_size(&self, size: vec2u) -> vec2u {
+        match self.rotated {
+            false => size,
+            true => vec2(size.y(), size.x()),
+        }
+    }
+}
+
+/// Pack rectangles of given sizes, larger ones first (which packs much tighter).
+/// Return each rectangle's placement, or `Err` if they don't all fit.
+/// E.g. with `ShelfPacker`
 /// input: `[((16,16), "sprite1"), ((16,16) "sprite2"), ((32, 32), "bigsprite")]`
 ///
-/// output: `[("bigsprite", (0,0)), ("sprite1", (32, 0)), ("sprite2", (32, 16))]`
+/// output: `[("bigsprite", (0,0)), ("sprite1", (32, 0)), ("sprite2", (48, 0))]`
 ///
 /// ```art
-/// +--------------+
-/// | big     | sp1|
-/// |sprite   +----+
-/// |         | sp2|
-/// +---------+----+
+/// +--------------------+
+/// | big     | sp1| sp2 |
+/// |sprite   +----+-----+
+/// |         |          |
+/// +---------+----------+
 /// ```
 ///
 /// Rectangle IDs can be of any type.
-pub fn binpack2d<ID>(size: u32, sizes: impl Iterator<Item = (vec2u, ID)>) -> impl Iterator<Item = (ID, vec2u)> {
-    let rectangles = sizes.map(|(size, id)| Rectangle { size, id }).collect();
-    let mut allocator = LightmapAllocator::new(size);
-    let allocated = allocator.alloc_all(rectangles);
-    allocated.into_iter().map(|(rectangle, pos)| (rectangle.id, pos))
+pub fn binpack2d<ID>(packer: &mut (impl Packer + ?Sized), sizes: impl IntoIterator<Item = (vec2u, ID)>) -> Result<Vec<(ID, Placement)>> {
+    let mut sizes = sizes.into_iter().collect::<Vec<_>>();
+    sizes.sort_by_key(|(size, _)| std::cmp::Reverse((size.x().max(size.y()), size.x() * size.y())));
+    let mut placed = Vec::with_capacity(sizes.len());
+    for (i, (size, id)) in sizes.into_iter().enumerate() {
+        match packer.insert(size) {
+            Ok(placement) => placed.push((id, placement)),
+            Err(e) => bail!("{e} (after placing {i} rectangles)"),
+        }
+    }
+    Ok(placed)
 }
 
-struct LightmapAllocator {
-    size: u32,
-    curr: vec2u,
-    next_y: u32,
+/// Error for a rectangle that does not fit.
+pub(crate) fn overflow(size: vec2u, bin: vec2u, fullness: f32) -> anyhow::Error {
+    anyhow::anyhow!("{}x{} rectangle does not fit in {}x{} bin ({:.0}% full)", size.x(), size.y(), bin.x(), bin.y(), fullness * 100.0)
 }
 
-#[derive(PartialEq, Eq, Clone, Hash, Debug)]
-struct Rectangle<ID> {
-    pub size: vec2u,
-    pub id: ID,
+/// Fraction of the bin covered by `used` area.
+pub(crate) fn fullness(used: u64, bin: vec2u) -> f32 {
+    used as f32 / (bin.x() as u64 * bin.y() as u64).max(1) as f32
 }
 
-/// Additional margin between islands.
-/// An absolute minimum of 1 is needed to avoid light bleeding.
-/// 1 additional texel is added to avoid minuscule light bleeding due to round-off under grazing incidence
-/// (see fn VoxelWorld::add_borders).
-const MARGIN: u32 = 2;
-
-impl LightmapAllocator {
-    fn new(size: u32) -> Self {
-        Self { size, curr: vec2(1, 1), next_y: 1 }
-    }
-
-    /// Call `alloc` on each face.
-    /// Does not necessarily conserve the original ordering.
-    fn alloc_all<ID>(&mut self, mut faces: Vec<Rectangle<ID>>) -> Vec<(Rectangle<ID>, vec2u)> {
-        faces.sort_by_key(|r| r.size.y()); // reduces lightmap fullness 3-10x
-        faces
-            .into_iter()
-            .map(move |face| {
-                let uv = self.alloc(face.size);
-                (face, uv)
-            })
-            .collect()
-    }
-
-    /// Allocate a `(W+1)x(H+1)` island for a `WxH` face.
-    ///
-    /// texel 0   1   2   3   4   5   6...
-    /// .   0 .   .   .   .   .   .   .   
-    /// .       +---+       +-------+     
-    /// .   1 . | . | .   . | .   . | .   
-    /// .       +---+       |       |     
-    /// .   2 .   .   .   . | .   . | .   
-    /// .                   +-------+        
-    /// .   3 .   .   .   .   .   .   .   
-    /// .                                 
-    /// .   4 .   .   .   .   .   .   .   
-    fn alloc(&mut self, size: vec2u) -> vec2u {
-        debug_assert!(size.x() > 0 && size.y() > 0);
-
-        let size = size + vec2(1, 1);
-
-        if self.curr.x() + size.x() >= self.size {
-            // next line
-            self.curr[0] = MARGIN;
-            self.curr[1] = self.next_y;
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// All packers, for the same bin and options.
+    fn packers(bin: vec2u, options: PackOptions) -> Vec<(&'static str, Box<dyn Packer>)> {
+        vec![
+            ("shelf", Box::new(ShelfPacker::new(bin, options))),
+            ("maxrects", Box::new(MaxRectsPacker::new(bin, options))),
+            ("skyline", Box::new(SkylinePacker::new(bin, options))),
+        ]
+    }
+
+    /// Placed rectangles (incl. margin) must be inside the bin (minus padding) and must not overlap.
+    fn check_valid(name: &str, bin: vec2u, options: PackOptions, placed: &[(vec2u, Placement)]) {
+        let rects = placed.iter().map(|(size, p)| (p.pos, p.pos + p.placed_size(*size))).collect::<Vec<_>>();
+        for (i, &(min, max)) in rects.iter().enumerate() {
+            assert!(min.x() >= options.padding && min.y() >= options.padding, "{name}: {min:?} in padding");
+            assert!(max.x() + options.padding <= bin.x() && max.y() + options.padding <= bin.y(), "{name}: {max:?} outside bin");
+            for &(min2, max2) in &rects[..i] {
+                let separated = |a_min: u32, a_max: u32, b_min: u32, b_max: u32| a_max + options.margin <= b_min || b_max + options.margin <= a_min;
+                assert!(separated(min.x(), max.x(), min2.x(), max2.x()) || separated(min.y(), max.y(), min2.y(), max2.y()), "{name}: overlap");
+            }
         }
+    }
 
-        self.next_y = u32::max(self.next_y, self.curr.y() + size.y() + MARGIN);
+    /// Pseudo-random sizes.
+    fn sizes(n: u32) -> Vec<vec2u> {
+        (0..n).map(|i| vec2(1 + (i * 7919) % 23, 1 + (i * 104729) % 17)).collect()
+    }
 
-        let result = self.curr;
-        self.curr[0] += size.x() + MARGIN;
-        result
+    #[gtest]
+    fn valid_packing() {
+        let bin = vec2(128, 96);
+        for options in [PackOptions::default(), PackOptions::default().with_margin(2).with_padding(1).with_rotation(true)] {
+            for (name, mut packer) in packers(bin, options) {
+                let placed = binpack2d(packer.as_mut(), sizes(30).into_iter().map(|s| (s, s))).unwrap();
+                expect_that!(placed.len(), eq(30));
+                check_valid(name, bin, options, &placed);
+            }
+        }
+    }
+
+    #[gtest]
+    fn overflow_is_error() {
+        for (name, mut packer) in packers(vec2(64, 64), PackOptions::default()) {
+            expect_that!(packer.insert(vec2(65, 1)), err(anything()), "{name}");
+            expect_that!(binpack2d(packer.as_mut(), sizes(200).into_iter().map(|s| (s, ()))), err(anything()), "{name}");
+        }
+    }
+
+    #[gtest]
+    fn fullness() {
+        for (name, mut packer) in packers(vec2(64, 64), PackOptions::default()) {
+            expect_that!(packer.fullness(), eq(0.0), "{name}");
+            packer.insert(vec2(32, 32)).unwrap();
+            expect_that!(packer.fullness(), eq(0.25), "{name}");
+            for _ in 0..3 {
+                packer.insert(vec2(32, 32)).unwrap();
+            }
+            expect_that!(packer.fullness(), eq(1.0), "{name}");
+            expect_that!(packer.insert(vec2(1, 1)), err(anything()), "{name}");
+        }
+    }
+
+    #[gtest]
+    fn rotation() {
+        for (name, mut packer) in packers(vec2(10, 100), PackOptions::default().with_rotation(true)) {
+            let p = packer.insert(vec2(100, 10)).unwrap();
+            expect_that!(p, eq(Placement { pos: vec2(0, 0), rotated: true }), "{name}");
+        }
     }
 
-    /*
-    pub fn fullness(&self) -> f32 {
-        (self.current.y as f32) / (self.size as f32)
+    #[gtest]
+    fn maxrects_is_tighter() {
+        // 👇 fill a bin until full, compare how much fits.
+        let fill = |mut packer: Box<dyn Packer>| {
+            sizes(1000).into_iter().take_while(|&s| packer.insert(s).is_ok()).count();
+            packer.fullness()
+        };
+        let [shelf, maxrects, skyline] = packers(vec2(128, 128), PackOptions::default()).into_iter().map(|(_, p)| fill(p)).collect::<Vec<_>>().try_into().unwrap();
+        expect_that!(maxrects, ge(shelf));
+        expect_that!(skyline, gt(shelf));
     }
-    */
 }
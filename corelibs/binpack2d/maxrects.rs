@@ -0,0 +1,101 @@
+use crate::*;
+
+/// Tracks all maximal free rectangles (which may overlap) and places each rectangle
+/// in the free rectangle it fits best ("best short side fit").
+/// Slower than the others, but packs tightest.
+/// See Jukka Jylänki, "A Thousand Ways to Pack the Bin".
+pub struct MaxRectsPacker {
+    bin: vec2u,
+    options: PackOptions,
+    /// Maximal free rectangles `(min, max)` in the inner area.
+    free: Vec<(vec2u, vec2u)>,
+    used: u64,
+}
+
+impl MaxRectsPacker {
+    pub fn new(bin: vec2u, options: PackOptions) -> Self {
+        let inner = options.inner_size(bin);
+        let free = if inner.x() > 0 && inner.y() > 0 { vec![(vec2(0, 0), inner)] } else { vec![] };
+        Self { bin, options, free, used: 0 }
+    }
+
+    /// Remove `used` from the free rectangles, splitting the ones it overlaps.
+    fn split_free(&mut self, used: (vec2u, vec2u)) {
+        let (umin, umax) = used;
+        let mut new = Vec::new();
+        self.free.retain(|&(fmin, fmax)| {
+            let overlaps = umin.x() < fmax.x() && umax.x() > fmin.x() && umin.y() < fmax.y() && umax.y() > fmin.y();
+            if !overlaps {
+                return true;
+            }
+            // 👇 up to 4 maximal leftovers: left, right, above, below `used`.
+            if umin.x() > fmin.x() {
+                new.push((fmin, vec2(umin.x(), fmax.y())));
+            }
+            if umax.x() < fmax.x() {
+                new.push((vec2(umax.x(), fmin.y()), fmax));
+            }
+            if umin.y() > fmin.y() {
+                new.push((fmin, vec2(fmax.x(), umin.y())));
+            }
+            if umax.y() < fmax.y() {
+                new.push((vec2(fmin.x(), umax.y()), fmax));
+            }
+            false
+        });
+        self.free.extend(new);
+        self.prune();
+    }
+
+    /// Remove free rectangles contained in another one.
+    fn prune(&mut self) {
+        let contains = |(amin, amax): (vec2u, vec2u), (bmin, bmax): (vec2u, vec2u)| amin.x() <= bmin.x() && amin.y() <= bmin.y() && amax.x() >= bmax.x() && amax.y() >= bmax.y();
+        let mut i = 0;
+        while i < self.free.len() {
+            let a = self.free[i];
+            // 👇 of two equal rectangles, keep the first.
+            let redundant = self.free.iter().enumerate().any(|(j, &b)| j != i && contains(b, a) && (a != b || j < i));
+            match redundant {
+                true => drop(self.free.swap_remove(i)),
+                false => i += 1,
+            }
+        }
+    }
+}
+
+/// Leftover on the short side, on the long side, then position (y, x). Lower is better.
+type Score = (u32, u32, u32, u32);
+
+impl Packer for MaxRectsPacker {
+    fn insert(&mut self, size: vec2u) -> Result<Placement> {
+        let orientations = [(false, size), (true, vec2(size.y(), size.x()))];
+        let orientations = &orientations[..if self.options.rotate { 2 } else { 1 }];
+
+        // 👇 best = least leftover on the short side, then on the long side, then top-left.
+        let mut best: Option<(Score, vec2u, bool, vec2u)> = None;
+        for &(rotated, placement_size) in orientations {
+            let outer = self.options.outer_size(placement_size);
+            for &(fmin, fmax) in &self.free {
+                let free_size = fmax - fmin;
+                if outer.x() <= free_size.x() && outer.y() <= free_size.y() {
+                    let (dx, dy) = (free_size.x() - outer.x(), free_size.y() - outer.y());
+                    let score = (dx.min(dy), dx.max(dy), fmin.y(), fmin.x());
+                    if best.is_none_or(|(best_score, ..)| score < best_score) {
+                        best = Some((score, fmin, rotated, outer));
+                    }
+                }
+            }
+        }
+
+        let Some((_, pos, rotated, outer)) = best else {
+            return Err(overflow(size, self.bin, self.fullness()));
+        };
+        self.split_free((pos, pos + outer));
+        self.used += size.x() as u64 * size.y() as u64;
+        Ok(Placement { pos: self.options.bin_pos(pos), rotated })
+    }
+
+    fn fullness(&self) -> f32 {
+        fullness(self.used, self.bin)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::*;
+
+/// Fills the bin row by row ("shelves"), left to right.
+/// Fast, but wastes space above rectangles lower than their shelf: insert in order of decreasing height.
+/// With rotation, rectangles are laid flat (wider than high) where they fit.
+///
+/// ```art
+/// +------------------+
+/// |+----++--++------+|
+/// ||    ||  ||      ||
+/// |+----+|  |+------+|
+/// |      +--+        |
+/// |+-----++-+        |  <- current shelf
+/// |+-----++-+        |
+/// +------------------+
+/// ```
+pub struct ShelfPacker {
+    bin: vec2u,
+    options: PackOptions,
+    /// Inner size, see `PackOptions::inner_size`.
+    inner: vec2u,
+    /// Where the next rectangle goes on the current shelf.
+    curr: vec2u,
+    /// Top of the next shelf.
+    next_y: u32,
+    used: u64,
+}
+
+impl ShelfPacker {
+    pub fn new(bin: vec2u, options: PackOptions) -> Self {
+        Self {
+            bin,
+            options,
+            inner: options.inner_size(bin),
+            curr: vec2(0, 0),
+            next_y: 0,
+            used: 0,
+        }
+    }
+}
+
+impl Packer for ShelfPacker {
+    fn insert(&mut self, size: vec2u) -> Result<Placement> {
+        // 👇 with rotation: flat if possible, else upright.
+        let flat = size.y() > size.x();
+        let orientations = match self.options.rotate {
+            true => vec![flat, !flat],
+            false => vec![false],
+        };
+        for rotated in orientations {
+            let outer = self.options.outer_size(if rotated { vec2(size.y(), size.x()) } else { size });
+            let mut pos = self.curr;
+            if pos.x() + outer.x() > self.inner.x() {
+                pos = vec2(0, self.next_y); // 👈 next shelf
+            }
+            if pos.x() + outer.x() > self.inner.x() || pos.y() + outer.y() > self.inner.y() {
+                continue;
+            }
+
+            self.curr = pos + vec2(outer.x(), 0);
+            self.next_y = u32::max(self.next_y, pos.y() + outer.y());
+            self.used += size.x() as u64 * size.y() as u64;
+            return Ok(Placement { pos: self.options.bin_pos(pos), rotated });
+        }
+        Err(overflow(size, self.bin, self.fullness()))
+    }
+
+    fn fullness(&self) -> f32 {
+        fullness(self.used, self.bin)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::*;
+
+/// Keeps track of the "skyline" (top edge of the filled area) and places each rectangle
+/// as low as possible, then as far left as possible (bottom-left rule, with y pointing down: top-left).
+/// Good for incremental packing, e.g. glyph caches.
+///
+/// ```art
+/// +------------------+
+/// |        +---+     |
+/// |        |   |     |  <- skyline: ▔ tops of the columns
+/// |+--+    |   |+---+|
+/// ||  |+--+|   ||   ||
+/// +------------------+
+/// ```
+/// (drawn upside-down: y grows downwards from the top of the bin).
+pub struct SkylinePacker {
+    bin: vec2u,
+    options: PackOptions,
+    inner: vec2u,
+    /// Skyline segments `(x, y, width)`, left to right, covering the whole width.
+    skyline: Vec<(u32, u32, u32)>,
+    used: u64,
+}
+
+impl SkylinePacker {
+    pub fn new(bin: vec2u, options: PackOptions) -> Self {
+        let inner = options.inner_size(bin);
+        Self {
+            bin,
+            options,
+            inner,
+            skyline: vec![(0, 0, inner.x())],
+            used: 0,
+        }
+    }
+
+    /// Lowest y where a rectangle of width `w` fits starting at segment `i`, if it fits.
+    fn fit(&self, i: usize, size: vec2u) -> Option<u32> {
+        let (x, _, _) = self.skyline[i];
+        if x + size.x() > self.inner.x() {
+            return None;
+        }
+        let mut y = 0;
+        let mut remaining = size.x() as i64;
+        for &(_, seg_y, seg_w) in &self.skyline[i..] {
+            if remaining <= 0 {
+                break;
+            }
+            y = y.max(seg_y);
+            remaining -= seg_w as i64;
+        }
+        (y + size.y() <= self.inner.y()).then_some(y)
+    }
+
+    /// Raise the skyline under a newly placed rectangle.
+    fn add_level(&mut self, i: usize, pos: vec2u, size: vec2u) {
+        self.skyline.insert(i, (pos.x(), pos.y() + size.y(), size.x()));
+        // 👇 shrink or remove the segments now covered by the new one.
+        let end = pos.x() + size.x();
+        while let Some(&(x, y, w)) = self.skyline.get(i + 1) {
+            if x >= end {
+                break;
+            }
+            match x + w <= end {
+                true => drop(self.skyline.remove(i + 1)),
+                false => {
+                    self.skyline[i + 1] = (end, y, x + w - end);
+                    break;
+                }
+            }
+        }
+        // 👇 merge neighbours of equal height.
+        self.skyline.dedup_by(|b, a| {
+            let mergeable = a.1 == b.1;
+            if mergeable {
+                a.2 += b.2;
+            }
+            mergeable
+        });
+    }
+}
+
+impl Packer for SkylinePacker {
+    fn insert(&mut self, size: vec2u) -> Result<Placement> {
+        let orientations = [(false, size), (true, vec2(size.y(), size.x()))];
+        let orientations = &orientations[..if self.options.rotate { 2 } else { 1 }];
+
+        // 👇 best = lowest top edge, then leftmost.
+        let mut best: Option<(u32, u32, usize, bool, vec2u)> = None;
+        for &(rotated, placement_size) in orientations {
+            let outer = self.options.outer_size(placement_size);
+            for i in 0..self.skyline.len() {
+                if let Some(y) = self.fit(i, outer) {
+                    let x = self.skyline[i].0;
+                    if best.is_none_or(|(by, bx, ..)| (y + outer.y(), x) < (by, bx)) {
+                        best = Some((y + outer.y(), x, i, rotated, outer));
+                    }
+                }
+            }
+        }
+
+        let Some((bottom, x, i, rotated, outer)) = best else {
+            return Err(overflow(size, self.bin, self.fullness()));
+        };
+        let pos = vec2(x, bottom - outer.y());
+        self.add_level(i, pos, outer);
+        self.used += size.x() as u64 * size.y() as u64;
+        Ok(Placement { pos: self.options.bin_pos(pos), rotated })
+    }
+
+    fn fullness(&self) -> f32 {
+        fullness(self.used, self.bin)
+    }
+}
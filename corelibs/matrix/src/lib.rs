@@ -8,9 +8,6 @@ mod transform2;
 mod transforms;
 pub use transforms::*;
 
-//#[cfg(feature = "inspect")]
-//mod inspect;
-
 use bytemuck::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, Index, IndexMut, Mul};
@@ -3,6 +3,13 @@ pub fn derive_setters(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     proc_macros_impl::derive_setters(input.into()).into()
 }
 
+/// Path-based reflection, see crate `reflect`.
+/// Fields can be hidden with `#[reflect(skip)]`.
+#[proc_macro_derive(Reflect, attributes(reflect))]
+pub fn derive_reflect(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    proc_macros_impl::derive_reflect(input.into()).into()
+}
+
 /// Compile-time equivalent of `Str::from_str`
 #[proc_macro]
 pub fn str16(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
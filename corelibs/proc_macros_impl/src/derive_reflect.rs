@@ -0,0 +1,75 @@
+use quote::quote;
+use syn::{DeriveInput, Fields, Index, parse_quote};
+
+/// `#[derive(Reflect)]`: expose struct fields by name for path-based reflection (see crate `reflect`).
+/// Fields marked `#[reflect(skip)]` are hidden (e.g. because their type does not implement `Reflect`).
+/// Tuple struct fields are named by index ("0", "1", ...).
+pub fn derive_reflect(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut input: DeriveInput = syn::parse2(input).expect("parse");
+    let strct_type = &input.ident;
+
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => return quote! { compile_error!("derive(Reflect) only supports structs"); },
+    };
+
+    let (names, members): (Vec<String>, Vec<proc_macro2::TokenStream>) = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter(|f| !is_skipped(&f.attrs))
+            .map(|f| f.ident.as_ref().expect("named field"))
+            .map(|ident| (ident.to_string(), quote! {#ident}))
+            .unzip(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !is_skipped(&f.attrs))
+            .map(|(i, _)| {
+                let index = Index::from(i);
+                (i.to_string(), quote! {#index})
+            })
+            .unzip(),
+        Fields::Unit => (vec![], vec![]),
+    };
+
+    // Require `T: Reflect` for all type parameters.
+    for param in input.generics.type_params_mut() {
+        param.bounds.push(parse_quote!(reflect::Reflect));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let summary = format!("{strct_type} {{..}}");
+
+    quote! {
+        impl #impl_generics reflect::Reflect for #strct_type #ty_generics #where_clause {
+            fn fields(&self) -> Vec<String> {
+                vec![#(#names.to_string()),*]
+            }
+
+            fn field(&self, name: &str) -> Option<&dyn reflect::Reflect> {
+                match name {
+                    #(#names => Some(&self.#members),)*
+                    _ => None,
+                }
+            }
+
+            fn field_mut(&mut self, name: &str) -> Option<&mut dyn reflect::Reflect> {
+                match name {
+                    #(#names => Some(&mut self.#members),)*
+                    _ => None,
+                }
+            }
+
+            fn get_string(&self) -> String {
+                #summary.to_string()
+            }
+        }
+    }
+}
+
+/// Has attribute `#[reflect(skip)]`?
+fn is_skipped(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path.is_ident("reflect")).any(|attr| attr.parse_args::<syn::Ident>().map(|arg| arg == "skip").unwrap_or(false))
+}
@@ -1,5 +1,7 @@
+mod derive_reflect;
 mod derive_setters;
 mod fixed_str_macros;
 
+pub use derive_reflect::*;
 pub use derive_setters::*;
 pub use fixed_str_macros::*;
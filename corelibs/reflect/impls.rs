@@ -0,0 +1,211 @@
+//! `Reflect` implementations for primitives, std containers, `Cel` and `vector` types.
+use crate::*;
+use cel::{Cel, Set as _};
+use std::fmt::Display;
+use std::str::FromStr;
+use vector::Vector;
+
+// ---------- primitives: leaf values, parsed with `FromStr`.
+
+macro_rules! impl_reflect_leaf {
+    ($($t:ty),*) => {
+        $(
+            impl Reflect for $t {
+                fn get_string(&self) -> String {
+                    self.to_string()
+                }
+
+                fn set_string(&mut self, value: &str) -> Result<()> {
+                    *self = parse(value)?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_reflect_leaf!(bool, char, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, String);
+
+fn parse<T>(value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.trim().parse().map_err(|e| anyhow!("{value:?}: {e}"))
+}
+
+// ---------- lists: children are named by index ("0", "1", ...).
+
+fn index_names(len: usize) -> Vec<String> {
+    (0..len).map(|i| i.to_string()).collect()
+}
+
+impl<T: Reflect> Reflect for Vec<T> {
+    fn fields(&self) -> Vec<String> {
+        index_names(self.len())
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        self.get(name.parse::<usize>().ok()?).map(|v| v as &dyn Reflect)
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        self.get_mut(name.parse::<usize>().ok()?).map(|v| v as &mut dyn Reflect)
+    }
+
+    fn get_string(&self) -> String {
+        format!("[{} items]", self.len())
+    }
+}
+
+impl<T: Reflect, const N: usize> Reflect for [T; N] {
+    fn fields(&self) -> Vec<String> {
+        index_names(N)
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        self.get(name.parse::<usize>().ok()?).map(|v| v as &dyn Reflect)
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        self.get_mut(name.parse::<usize>().ok()?).map(|v| v as &mut dyn Reflect)
+    }
+
+    fn get_string(&self) -> String {
+        format!("[{N} items]")
+    }
+}
+
+impl<A: Reflect, B: Reflect> Reflect for (A, B) {
+    fn fields(&self) -> Vec<String> {
+        index_names(2)
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match name {
+            "0" => Some(&self.0),
+            "1" => Some(&self.1),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        match name {
+            "0" => Some(&mut self.0),
+            "1" => Some(&mut self.1),
+            _ => None,
+        }
+    }
+
+    fn get_string(&self) -> String {
+        format!("({}, {})", self.0.get_string(), self.1.get_string())
+    }
+}
+
+// ---------- vectors: children are named "x", "y", "z", "w".
+// The whole vector can be set at once, e.g. `1,2` or `[1, 2]`.
+
+const XYZW: [&str; 4] = ["x", "y", "z", "w"];
+
+fn vector_index(name: &str) -> Option<usize> {
+    XYZW.iter().position(|&v| v == name).or_else(|| name.parse().ok())
+}
+
+impl<T: Reflect + Clone, const N: usize> Reflect for Vector<T, N> {
+    fn fields(&self) -> Vec<String> {
+        (0..N).map(|i| XYZW.get(i).map(|v| v.to_string()).unwrap_or_else(|| i.to_string())).collect()
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        self.0.get(vector_index(name)?).map(|v| v as &dyn Reflect)
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        self.0.get_mut(vector_index(name)?).map(|v| v as &mut dyn Reflect)
+    }
+
+    fn get_string(&self) -> String {
+        format!("[{}]", self.0.iter().map(|v| v.get_string()).collect::<Vec<_>>().join(", "))
+    }
+
+    fn set_string(&mut self, value: &str) -> Result<()> {
+        let elements = value.trim().trim_start_matches(['[', '(']).trim_end_matches([']', ')']).split([',', ' ']).filter(|v| !v.is_empty()).collect::<Vec<_>>();
+        if elements.len() != N {
+            bail!("{value:?}: need {N} components");
+        }
+        // 👇 parse into a copy first, so that we don't partially assign on error.
+        let mut parsed = self.0.clone();
+        for (dst, src) in parsed.iter_mut().zip(elements) {
+            dst.set_string(src)?;
+        }
+        self.0 = parsed;
+        Ok(())
+    }
+}
+
+// ---------- wrappers: transparent.
+
+impl<T: Reflect> Reflect for Option<T> {
+    fn fields(&self) -> Vec<String> {
+        self.as_ref().map(T::fields).unwrap_or_default()
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        self.as_ref()?.field(name)
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        self.as_mut()?.field_mut(name)
+    }
+
+    fn get_string(&self) -> String {
+        match self {
+            Some(v) => v.get_string(),
+            None => "None".into(),
+        }
+    }
+
+    fn set_string(&mut self, value: &str) -> Result<()> {
+        match self {
+            Some(v) => v.set_string(value),
+            None => bail!("cannot assign {value:?} to None"),
+        }
+    }
+}
+
+impl<T: Reflect> Reflect for Box<T> {
+    fn fields(&self) -> Vec<String> {
+        self.as_ref().fields()
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        self.as_ref().field(name)
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        self.as_mut().field_mut(name)
+    }
+
+    fn get_string(&self) -> String {
+        self.as_ref().get_string()
+    }
+
+    fn set_string(&mut self, value: &str) -> Result<()> {
+        self.as_mut().set_string(value)
+    }
+}
+
+/// A `Cel` is a leaf: its contents cannot be borrowed,
+/// so nested fields are not accessible, but the value as a whole can be set.
+impl<T: Reflect + Copy> Reflect for Cel<T> {
+    fn get_string(&self) -> String {
+        self.get().get_string()
+    }
+
+    fn set_string(&mut self, value: &str) -> Result<()> {
+        let mut v = self.get();
+        v.set_string(value)?;
+        self.set(v);
+        Ok(())
+    }
+}
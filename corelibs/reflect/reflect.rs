@@ -0,0 +1,223 @@
+//! Path-based reflection: query and modify (nested) fields by a dotted path
+//! like `world.critters.0.body.stiffness`.
+//!
+//! Derive with `#[derive(Reflect)]` (from `proc_macros`),
+//! skip fields with `#[reflect(skip)]`.
+mod impls;
+
+use anyhow::{Result, anyhow, bail};
+
+// So that `#[derive(Reflect)]` (which refers to `reflect::Reflect`) also works inside this crate.
+extern crate self as reflect;
+
+/// A value whose fields can be listed, read and written by name.
+pub trait Reflect {
+    /// Names of the direct children (struct fields, list indices, ...).
+    /// Empty for leaf values like numbers.
+    fn fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Direct child by name (see `fields`).
+    fn field(&self, _name: &str) -> Option<&dyn Reflect> {
+        None
+    }
+
+    /// Direct child by name (see `fields`).
+    fn field_mut(&mut self, _name: &str) -> Option<&mut dyn Reflect> {
+        None
+    }
+
+    /// Human-readable value. Structs and lists only show a short summary.
+    fn get_string(&self) -> String;
+
+    /// Parse a value from a string (as typed into the console) and assign it.
+    fn set_string(&mut self, _value: &str) -> Result<()> {
+        bail!("cannot assign a value to {}", std::any::type_name::<Self>())
+    }
+}
+
+/// Path-based access, automatically implemented for every `Reflect` type.
+///
+/// Paths are dot-separated field names, e.g. `critters.0.body.stiffness`.
+/// The empty path refers to the value itself.
+pub trait ReflectPath {
+    /// Get the (nested) value at `path`.
+    fn at_path(&self, path: &str) -> Result<&dyn Reflect>;
+
+    /// Get the (nested) value at `path`, mutably.
+    fn at_path_mut(&mut self, path: &str) -> Result<&mut dyn Reflect>;
+
+    /// Human-readable value at `path`.
+    fn get_path(&self, path: &str) -> Result<String> {
+        Ok(self.at_path(path)?.get_string())
+    }
+
+    /// Names of the children at `path`.
+    fn list_path(&self, path: &str) -> Result<Vec<String>> {
+        Ok(self.at_path(path)?.fields())
+    }
+
+    /// Parse and assign the value at `path`.
+    /// A `*` path element matches all children, e.g. `springs.*.k`.
+    fn set_path(&mut self, path: &str, value: &str) -> Result<()>;
+}
+
+impl<T: Reflect> ReflectPath for T {
+    fn at_path(&self, path: &str) -> Result<&dyn Reflect> {
+        at_path(self, path)
+    }
+
+    fn at_path_mut(&mut self, path: &str) -> Result<&mut dyn Reflect> {
+        at_path_mut(self, path)
+    }
+
+    fn set_path(&mut self, path: &str, value: &str) -> Result<()> {
+        set_path(self, path, value)
+    }
+}
+
+impl ReflectPath for dyn Reflect + '_ {
+    fn at_path(&self, path: &str) -> Result<&dyn Reflect> {
+        at_path(self, path)
+    }
+
+    fn at_path_mut(&mut self, path: &str) -> Result<&mut dyn Reflect> {
+        at_path_mut(self, path)
+    }
+
+    fn set_path(&mut self, path: &str, value: &str) -> Result<()> {
+        set_path(self, path, value)
+    }
+}
+
+fn at_path<'a>(root: &'a dyn Reflect, path: &str) -> Result<&'a dyn Reflect> {
+    let mut curr = root;
+    for name in split_path(path) {
+        curr = curr.field(name).ok_or_else(|| no_such_field(path, name))?;
+    }
+    Ok(curr)
+}
+
+fn at_path_mut<'a>(root: &'a mut dyn Reflect, path: &str) -> Result<&'a mut dyn Reflect> {
+    let mut curr = root;
+    for name in split_path(path) {
+        curr = curr.field_mut(name).ok_or_else(|| no_such_field(path, name))?;
+    }
+    Ok(curr)
+}
+
+fn set_path(root: &mut dyn Reflect, path: &str, value: &str) -> Result<()> {
+    let segments = split_path(path).collect::<Vec<_>>();
+    return set_rec(root, path, &segments, value);
+
+    fn set_rec(v: &mut dyn Reflect, path: &str, segments: &[&str], value: &str) -> Result<()> {
+        match segments {
+            [] => v.set_string(value),
+            ["*", rest @ ..] => {
+                for name in v.fields() {
+                    set_rec(v.field_mut(&name).ok_or_else(|| no_such_field(path, &name))?, path, rest, value)?;
+                }
+                Ok(())
+            }
+            [name, rest @ ..] => set_rec(v.field_mut(name).ok_or_else(|| no_such_field(path, name))?, path, rest, value),
+        }
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('.').map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn no_such_field(path: &str, name: &str) -> anyhow::Error {
+    anyhow!("{path:?}: no such field: {name:?}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cel::Cel;
+    use googletest::prelude::*;
+    use proc_macros::Reflect;
+    use vector::*;
+
+    #[derive(Reflect, Default)]
+    struct World {
+        critters: Vec<Critter>,
+        selected: Option<usize>,
+        #[reflect(skip)]
+        _secret: u32,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Critter {
+        stiffness: f32,
+        position: vec2f,
+        energy: Cel<u32>,
+    }
+
+    fn world() -> World {
+        World {
+            critters: vec![Critter::default(), Critter::default()],
+            selected: Some(1),
+            _secret: 42,
+        }
+    }
+
+    #[gtest]
+    fn get() {
+        let w = world();
+        expect_eq!(w.get_path("critters.1.stiffness").unwrap(), "0");
+        expect_eq!(w.get_path("critters.0.position").unwrap(), "[0, 0]");
+        expect_eq!(w.get_path("critters.0.position.y").unwrap(), "0");
+        expect_eq!(w.get_path("selected").unwrap(), "1");
+        expect_eq!(w.get_path("critters").unwrap(), "[2 items]");
+        expect_eq!(w.get_path("").unwrap(), "World {..}");
+    }
+
+    #[gtest]
+    fn list() {
+        let w = world();
+        expect_eq!(w.list_path("").unwrap(), vec!["critters", "selected"]);
+        expect_eq!(w.list_path("critters").unwrap(), vec!["0", "1"]);
+        expect_eq!(w.list_path("critters.0").unwrap(), vec!["stiffness", "position", "energy"]);
+        expect_eq!(w.list_path("critters.0.position").unwrap(), vec!["x", "y"]);
+    }
+
+    #[gtest]
+    fn set() {
+        let mut w = world();
+        w.set_path("critters.1.stiffness", "50").unwrap();
+        expect_eq!(w.critters[1].stiffness, 50.0);
+
+        w.set_path("critters.1.position", "1, 2").unwrap();
+        expect_eq!(w.critters[1].position, vec2(1.0, 2.0));
+
+        w.set_path("critters.0.position.x", "3").unwrap();
+        expect_eq!(w.critters[0].position, vec2(3.0, 0.0));
+
+        w.set_path("critters.0.energy", "7").unwrap();
+        expect_eq!(w.critters[0].energy, 7);
+    }
+
+    #[gtest]
+    fn set_wildcard() {
+        let mut w = world();
+        w.set_path("critters.*.stiffness", "9").unwrap();
+        expect_eq!(w.critters[0].stiffness, 9.0);
+        expect_eq!(w.critters[1].stiffness, 9.0);
+    }
+
+    #[gtest]
+    fn errors() {
+        let mut w = world();
+        expect_true!(w.get_path("critters.2").is_err());
+        expect_true!(w.get_path("_secret").is_err());
+        expect_true!(w.set_path("critters.0.stiffness", "stiff").is_err());
+        expect_true!(w.set_path("critters.0", "1").is_err());
+
+        // vectors are not partially assigned
+        expect_true!(w.set_path("critters.0.position", "1, two").is_err());
+        expect_eq!(w.critters[0].position, vec2(0.0, 0.0));
+    }
+}
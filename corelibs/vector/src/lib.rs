@@ -3,9 +3,6 @@
 
 //! Crate provides 2, 3 and 4-component vector types similar to [WGSL](https://www.w3.org/TR/WGSL/).
 
-//#[cfg(feature = "inspect")]
-//mod inspect;
-
 use bytemuck::{Pod, Zeroable};
 use num_traits::AsPrimitive;
 use num_traits::Float;
@@ -0,0 +1,49 @@
+use crate::prelude::*;
+
+/// 💾 Periodic autosave to rotating save slots `autosave.0`, `autosave.1`, ...
+/// The oldest slot gets overwritten.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Autosave {
+    /// Seconds between autosaves. 0 disables autosave.
+    pub interval_secs: f64,
+
+    /// Number of slots to rotate through.
+    pub slots: usize,
+
+    /// Slot to write next.
+    next: usize,
+
+    /// Time of the last autosave (or of starting).
+    last_secs: Option<f64>,
+}
+
+impl Default for Autosave {
+    fn default() -> Self {
+        Self {
+            interval_secs: 300.0,
+            slots: 3,
+            next: 0,
+            last_secs: None,
+        }
+    }
+}
+
+impl Autosave {
+    /// Returns a save request when due.
+    /// `now_secs`: game time, may jump back to zero when the shell restarts.
+    pub fn tick(&mut self, now_secs: f64, tick: u64) -> Option<ShellRequest> {
+        let last = *self.last_secs.get_or_insert(now_secs);
+        if now_secs < last {
+            self.last_secs = Some(now_secs); // 👈 shell restarted
+            return None;
+        }
+        if self.interval_secs <= 0.0 || self.slots == 0 || now_secs - last < self.interval_secs {
+            return None;
+        }
+
+        self.last_secs = Some(now_secs);
+        let slot = format!("autosave.{}", self.next % self.slots);
+        self.next = (self.next + 1) % self.slots;
+        Some(ShellRequest::Save { slot, tick })
+    }
+}
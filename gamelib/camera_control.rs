@@ -0,0 +1,92 @@
+use crate::prelude::*;
+
+/// 🎥 Camera controlled by the mouse or touch screen:
+/// wheel or pinch zooms (around the cursor/fingers), dragging with `pan_button` or two fingers pans.
+/// Optionally follows a target (e.g. the selected critter), until panned away.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CameraControl {
+    pub camera: Camera,
+
+    /// Hold and drag to pan.
+    pub pan_button: Button,
+
+    /// Zoom factor per mouse wheel notch.
+    pub zoom_step: f32,
+
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+
+    /// Follow the target passed to `tick`.
+    pub follow: bool,
+
+    /// Mouse position of the previous tick while dragging.
+    #[serde(skip)]
+    drag_from: Option<vec2i>,
+}
+
+impl Default for CameraControl {
+    fn default() -> Self {
+        Self {
+            camera: Camera::default(),
+            pan_button: K_MOUSE3,
+            zoom_step: 1.2,
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            follow: false,
+            drag_from: None,
+        }
+    }
+}
+
+/// Fraction of the distance to the follow target covered per tick.
+const FOLLOW_SMOOTHING: f32 = 0.1;
+
+impl CameraControl {
+    /// Handle wheel and drag, follow `target` (world position) if enabled,
+    /// and pass the camera on to `out`.
+    pub fn tick(&mut self, inputs: &Inputs, target: Option<vec2f>, out: &mut Out) {
+        let viewport = out.viewport_size;
+        let mouse = inputs.mouse_position();
+
+        let wheel = inputs.mouse_wheel();
+        if wheel != 0.0 {
+            let zoom = (self.camera.zoom * self.zoom_step.powf(wheel)).clamp(self.min_zoom, self.max_zoom);
+            self.camera.zoom_at(mouse.as_f32(), viewport, zoom / self.camera.zoom);
+        }
+
+        if let Some(center) = inputs.touch_center() {
+            let zoom = (self.camera.zoom * inputs.pinch()).clamp(self.min_zoom, self.max_zoom);
+            self.camera.zoom_at(center, viewport, zoom / self.camera.zoom);
+            if inputs.touch_pan() != vec2f::ZERO {
+                self.follow = false;
+                self.camera.pan_screen(inputs.touch_pan());
+            }
+        }
+
+        match (self.drag_from, inputs.is_down(self.pan_button)) {
+            (Some(from), true) => {
+                self.camera.pan_screen((mouse - from).as_f32());
+                self.drag_from = Some(mouse);
+            }
+            (None, true) => {
+                self.follow = false; // 👈 user takes over
+                self.drag_from = Some(mouse);
+            }
+            (_, false) => self.drag_from = None,
+        }
+
+        if self.follow
+            && let Some(target) = target
+        {
+            self.camera.pos += FOLLOW_SMOOTHING * (target - self.camera.pos);
+        }
+
+        out.camera = self.camera;
+    }
+
+    /// Set zoom (clamped to `min_zoom..=max_zoom`), keeping the viewport center in place.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.camera.zoom = zoom.clamp(self.min_zoom, self.max_zoom);
+    }
+}
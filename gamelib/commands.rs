@@ -0,0 +1,336 @@
+use crate::prelude::*;
+use crate::*;
+use anyhow::{anyhow, bail};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Registry of console commands for game state `G`.
+/// Provides argument checking, usage errors, `help` and tab-completion.
+/// E.g. `Command::new("speed", |g, args| ...).arg("n", ArgType::Int).help("simulation steps per frame")`.
+pub struct Commands<G> {
+    commands: Vec<Command<G>>,
+}
+
+/// A console command: name, arguments, help text and the function that executes it.
+pub struct Command<G> {
+    pub name: &'static str,
+    pub aliases: Vec<&'static str>,
+    pub args: Vec<Arg>,
+    pub help: &'static str,
+    pub run: fn(&mut G, &Args) -> Result<()>,
+}
+
+/// Declared command argument.
+#[derive(Debug, Clone)]
+pub struct Arg {
+    pub name: &'static str,
+    pub ty: ArgType,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+    Int,
+    Float,
+    Bool,
+    Str,
+    /// All remaining words, joined by spaces. Must be last.
+    Rest,
+}
+
+/// Arguments passed to a command, already checked against the declared `ArgType`s.
+#[derive(Debug)]
+pub struct Args {
+    values: Vec<String>,
+}
+
+impl<G> Commands<G> {
+    pub fn new() -> Self {
+        Self { commands: Vec::new() }
+    }
+
+    /// Add a command (builder style).
+    pub fn register(mut self, cmd: Command<G>) -> Self {
+        debug_assert!(self.find(cmd.name).is_none(), "duplicate command {}", cmd.name);
+        self.commands.push(cmd);
+        self
+    }
+
+    /// Parse and execute a command line.
+    /// Returns the text to print, if any (`help` output).
+    pub fn exec(&self, g: &mut G, line: &str) -> Result<Option<String>> {
+        let words = line.split_ascii_whitespace().collect::<Vec<_>>();
+        match words.as_slice() {
+            [] => Ok(None),
+            ["help" | "?"] => Ok(Some(self.help_all())),
+            ["help" | "?", name] => Ok(Some(self.find(name).ok_or_else(|| unknown_command(name))?.usage_and_help())),
+            [name, args @ ..] => {
+                let cmd = self.find(name).ok_or_else(|| unknown_command(name))?;
+                let args = cmd.check_args(args)?;
+                (cmd.run)(g, &args).map(|()| None)
+            }
+        }
+    }
+
+    /// Command by name or alias.
+    pub fn find(&self, name: &str) -> Option<&Command<G>> {
+        self.commands.iter().find(|cmd| cmd.name == name || cmd.aliases.contains(&name))
+    }
+
+    /// Names of all commands (and `help`), in order of registration.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        ["help"].into_iter().chain(self.commands.iter().map(|cmd| cmd.name))
+    }
+
+    fn help_all(&self) -> String {
+        let width = self.commands.iter().map(|cmd| cmd.usage().len()).max().unwrap_or_default();
+        self.commands.iter().map(|cmd| format!("{:width$}  {}", cmd.usage(), cmd.help)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl<G> Complete for Commands<G> {
+    /// Complete command names (not arguments).
+    fn complete(&self, input: &str) -> Vec<String> {
+        if input.contains(' ') {
+            return Vec::new();
+        }
+        self.names().chain(self.commands.iter().flat_map(|cmd| cmd.aliases.iter().copied())).filter(|name| name.starts_with(input)).map(str::to_owned).collect()
+    }
+}
+
+impl<G> Default for Commands<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G> Command<G> {
+    pub fn new(name: &'static str, run: fn(&mut G, &Args) -> Result<()>) -> Self {
+        Self {
+            name,
+            aliases: Vec::new(),
+            args: Vec::new(),
+            help: "",
+            run,
+        }
+    }
+
+    pub fn alias(self, alias: &'static str) -> Self {
+        self.with(|c| c.aliases.push(alias))
+    }
+
+    /// Declare a required argument.
+    pub fn arg(self, name: &'static str, ty: ArgType) -> Self {
+        debug_assert!(self.args.iter().all(|arg| !arg.optional), "required argument after optional argument");
+        self.with(|c| c.args.push(Arg { name, ty, optional: false }))
+    }
+
+    /// Declare an optional argument (must come after the required ones).
+    pub fn opt_arg(self, name: &'static str, ty: ArgType) -> Self {
+        self.with(|c| c.args.push(Arg { name, ty, optional: true }))
+    }
+
+    pub fn help(self, help: &'static str) -> Self {
+        self.with(|c| c.help = help)
+    }
+
+    /// E.g. `speed <n:int>`
+    pub fn usage(&self) -> String {
+        let mut usage = self.aliases.iter().fold(self.name.to_owned(), |acc, alias| acc + "|" + alias);
+        for arg in &self.args {
+            let name = match arg.ty {
+                ArgType::Str => arg.name.to_owned(),
+                ArgType::Rest => format!("{}...", arg.name),
+                ty => format!("{}:{ty}", arg.name),
+            };
+            usage += &match arg.optional {
+                true => format!(" [{name}]"),
+                false => format!(" <{name}>"),
+            };
+        }
+        usage
+    }
+
+    fn usage_and_help(&self) -> String {
+        format!("usage: {}\n{}", self.usage(), self.help)
+    }
+
+    fn check_args(&self, words: &[&str]) -> Result<Args> {
+        let rest = self.args.last().is_some_and(|arg| arg.ty == ArgType::Rest);
+        let required = self.args.iter().filter(|arg| !arg.optional).count();
+        if words.len() < required || (words.len() > self.args.len() && !rest) {
+            bail!("usage: {}", self.usage());
+        }
+
+        let mut values = Vec::new();
+        for (i, arg) in self.args.iter().enumerate() {
+            let Some(word) = words.get(i) else { break };
+            let ok = match arg.ty {
+                ArgType::Int => word.parse::<i64>().is_ok(),
+                ArgType::Float => word.parse::<f64>().is_ok(),
+                ArgType::Bool => word.parse::<bool>().is_ok(),
+                ArgType::Str | ArgType::Rest => true,
+            };
+            if !ok {
+                bail!("{}: expected {}, got {word:?}\nusage: {}", arg.name, arg.ty, self.usage());
+            }
+            match arg.ty {
+                ArgType::Rest => values.push(words[i..].join(" ")),
+                _ => values.push(word.to_string()),
+            }
+        }
+        Ok(Args { values })
+    }
+}
+
+impl Args {
+    /// Number of arguments passed (optional arguments may be missing).
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Argument `i`, parsed.
+    pub fn get<T>(&self, i: usize) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.str(i)?;
+        value.parse().map_err(|e| anyhow!("argument {}: {value:?}: {e}", i + 1))
+    }
+
+    /// Optional argument `i`, parsed.
+    pub fn get_opt<T>(&self, i: usize) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match i < self.len() {
+            true => self.get(i).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    /// Argument `i`, unparsed.
+    pub fn str(&self, i: usize) -> Result<&str> {
+        self.values.get(i).map(String::as_str).ok_or_else(|| anyhow!("missing argument {}", i + 1))
+    }
+}
+
+impl Display for ArgType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ArgType::Int => "int",
+            ArgType::Float => "float",
+            ArgType::Bool => "bool",
+            ArgType::Str => "string",
+            ArgType::Rest => "text",
+        };
+        f.write_str(name)
+    }
+}
+
+fn unknown_command(name: &str) -> anyhow::Error {
+    anyhow!("unknown command: {name:?} (try `help`)")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[derive(Default)]
+    struct Game {
+        speed: u32,
+        dt: f32,
+        paused: bool,
+        said: String,
+    }
+
+    fn commands() -> Commands<Game> {
+        use ArgType::*;
+        Commands::new()
+            .register(
+                Command::new("speed", |g: &mut Game, args| {
+                    g.speed = args.get(0)?;
+                    Ok(())
+                })
+                .alias("sp")
+                .arg("n", Int)
+                .help("simulation steps per frame"),
+            )
+            .register(
+                Command::new("dt", |g: &mut Game, args| {
+                    g.dt = args.get(0)?;
+                    Ok(())
+                })
+                .arg("dt", Float)
+                .help("physics time step"),
+            )
+            .register(
+                Command::new("pause", |g: &mut Game, args| {
+                    g.paused = args.get_opt(0)?.unwrap_or(!g.paused);
+                    Ok(())
+                })
+                .opt_arg("on", Bool)
+                .help("pause/unpause"),
+            )
+            .register(
+                Command::new("say", |g: &mut Game, args| {
+                    g.said = args.str(0)?.to_owned();
+                    Ok(())
+                })
+                .arg("text", Rest),
+            )
+    }
+
+    #[gtest]
+    fn lookup() {
+        let (commands, mut g) = (commands(), Game::default());
+        expect_that!(commands.exec(&mut g, "speed 3"), ok(none()));
+        expect_that!(commands.exec(&mut g, "sp 4"), ok(none())); // 👈 alias
+        expect_that!(commands.exec(&mut g, "  dt   0.5 "), ok(none()));
+        expect_that!(commands.exec(&mut g, "say hello  world"), ok(none()));
+        expect_that!((g.speed, g.dt, g.said.as_str()), eq((4, 0.5, "hello world")));
+        expect_that!(commands.exec(&mut g, ""), ok(none()));
+        expect_that!(commands.exec(&mut g, "warp 9"), err(displays_as(eq("unknown command: \"warp\" (try `help`)"))));
+        expect_that!(commands.complete("s"), eq(&["speed", "say", "sp"]));
+        expect_that!(commands.complete("speed 1"), is_empty());
+    }
+
+    #[gtest]
+    fn optional_args() {
+        let (commands, mut g) = (commands(), Game::default());
+        expect_that!(commands.exec(&mut g, "pause"), ok(none()));
+        expect_that!(g.paused, eq(true));
+        expect_that!(commands.exec(&mut g, "pause true"), ok(none()));
+        expect_that!(g.paused, eq(true));
+        expect_that!(commands.exec(&mut g, "pause false"), ok(none()));
+        expect_that!(g.paused, eq(false));
+    }
+
+    #[gtest]
+    fn argument_errors() {
+        let (commands, mut g) = (commands(), Game::default());
+        expect_that!(commands.exec(&mut g, "speed"), err(displays_as(eq("usage: speed|sp <n:int>"))));
+        expect_that!(commands.exec(&mut g, "speed 1 2"), err(displays_as(eq("usage: speed|sp <n:int>"))));
+        expect_that!(commands.exec(&mut g, "speed fast"), err(displays_as(eq("n: expected int, got \"fast\"\nusage: speed|sp <n:int>"))));
+        expect_that!(commands.exec(&mut g, "speed -1"), err(displays_as(starts_with("argument 1: \"-1\"")))); // 👈 an int, but not a u32
+        expect_that!(commands.exec(&mut g, "pause maybe"), err(anything()));
+        expect_that!(g.speed, eq(0));
+    }
+
+    #[gtest]
+    fn help() {
+        let (commands, mut g) = (commands(), Game::default());
+        let all = "speed|sp <n:int>  simulation steps per frame\ndt <dt:float>     physics time step\npause [on:bool]   pause/unpause\nsay <text...>     ";
+        expect_that!(commands.exec(&mut g, "help"), ok(some(eq(all))));
+        expect_that!(commands.exec(&mut g, "? dt"), ok(some(eq("usage: dt <dt:float>\nphysics time step"))));
+        expect_that!(commands.exec(&mut g, "help sp"), ok(some(eq("usage: speed|sp <n:int>\nsimulation steps per frame"))));
+        expect_that!(commands.exec(&mut g, "help warp"), err(anything()));
+    }
+}
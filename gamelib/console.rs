@@ -1,8 +1,14 @@
 use crate::prelude::*;
 use std::mem::take;
+use std::ops::Range;
 
 /// 📺 Game text console. Type game commands & cheats.
-#[derive(Serialize, Deserialize, Default)]
+///
+/// Left/Right/Home/End/Delete edit the input line, Up/Down browse history,
+/// PageUp/PageDown scroll the output, Tab completes.
+/// Shift+Left/Right/Home/End (or `K_SELECT_ALL`) select, `K_COPY`/`K_CUT` copy/cut, typing replaces the selection.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Console {
     /// Activates the console
     pub hotkey: Option<Button>,
@@ -11,27 +17,95 @@ pub struct Console {
     pub active: bool,
 
     /// line of text currently being typed into console.
+    /// Use `set_input` to replace it, so that the cursor stays valid.
     pub input_buffer: String,
 
-    /// output scrollback ringbuffer. Up to MAX_SCROLLBACK lines.
+    /// Cursor position in `input_buffer` (byte index, on a char boundary).
+    #[serde(skip)]
+    cursor: usize,
+
+    /// Other end of the selection (from here to `cursor`), if any.
+    #[serde(skip)]
+    anchor: Option<usize>,
+
+    /// Text copied (or cut) during the last tick, for the shell's clipboard.
+    #[serde(skip)]
+    copied: Option<String>,
+
+    /// output scrollback ringbuffer. Up to `scrollback` lines.
     pub output: CDeque<String>,
+
+    /// Maximum number of `output` lines to keep.
+    pub scrollback: usize,
+
+    /// Number of (wrapped) output rows scrolled up from the bottom.
+    #[serde(skip)]
+    scroll: usize,
+
+    /// Text being composed by an input method (IME), shown at the cursor.
+    #[serde(skip)]
+    composition: String,
+
+    /// Number of output rows that fit on screen, remembered from the last draw (for PageUp/PageDown).
+    #[serde(skip)]
+    page_rows: usize,
+
+    /// Previously entered commands, oldest first. Up to MAX_HISTORY lines.
+    pub history: Vec<String>,
+
+    /// Position in `history` while browsing with up/down.
+    #[serde(skip)]
+    history_pos: Option<usize>,
+}
+
+/// Tab-completion of console input.
+pub trait Complete {
+    /// All possible completions of `input`.
+    fn complete(&self, input: &str) -> Vec<String>;
 }
 
-const BACKSPACE: char = '\u{08}';
-const BACKSPACE_MAC: char = '\u{7F}';
-const ENTER: char = '\u{0D}';
-const ESCAPE: char = '\u{1B}';
+const DEFAULT_SCROLLBACK: usize = 1024;
+const MAX_HISTORY: usize = 100;
 
-const MAX_SCROLLBACK: usize = 64;
+const CONSOLE_BG: RGBA = RGBA([0, 0, 0, 184]);
+const SELECTION_BG: RGBA = RGBA([80, 160, 255, 128]);
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            hotkey: None,
+            active: false,
+            input_buffer: String::new(),
+            cursor: 0,
+            anchor: None,
+            copied: None,
+            output: CDeque::new(),
+            scrollback: DEFAULT_SCROLLBACK,
+            scroll: 0,
+            composition: String::new(),
+            page_rows: 0,
+            history: Vec::new(),
+            history_pos: None,
+        }
+    }
+}
 
 impl Console {
     pub fn with_hotkey(hotkey: Button) -> Self {
         Self::default().with(|v| v.hotkey = Some(hotkey))
     }
 
+    /// Keep up to `lines` lines of output.
+    pub fn with_scrollback(self, lines: usize) -> Self {
+        self.with(|v| v.scrollback = lines)
+    }
+
     #[must_use = "returns command"]
-    pub fn tick_and_draw(&mut self, inputs: &Inputs, out: &mut Out) -> Option<String> {
-        let cmd = self.tick(inputs);
+    pub fn tick_and_draw(&mut self, inputs: &Inputs, completer: &impl Complete, out: &mut Out) -> Option<String> {
+        let cmd = self.tick(inputs, completer);
+        if let Some(text) = self.copied.take() {
+            out.requests.push(ShellRequest::SetClipboard { text });
+        }
         self.draw(out);
         cmd
     }
@@ -40,72 +114,366 @@ impl Console {
         let v = v.as_ref();
         for line in v.lines() {
             self.output.push_back(line.to_owned());
-            while self.output.len() > MAX_SCROLLBACK {
-                self.output.pop_front();
-            }
+        }
+        self.trim_scrollback();
+    }
+
+    /// Change the number of output lines to keep, dropping the oldest if needed.
+    pub fn set_scrollback(&mut self, lines: usize) {
+        self.scrollback = lines;
+        self.trim_scrollback();
+    }
+
+    fn trim_scrollback(&self) {
+        while self.output.len() > self.scrollback {
+            self.output.pop_front();
+        }
+    }
+
+    /// Replace the input line, cursor at the end.
+    pub fn set_input(&mut self, text: impl Into<String>) {
+        self.input_buffer = text.into();
+        self.cursor = self.input_buffer.len();
+        self.anchor = None;
+    }
+
+    /// Selected byte range of `input_buffer` (empty if nothing is selected).
+    pub fn selection(&self) -> Range<usize> {
+        match self.anchor {
+            Some(anchor) => anchor.min(self.cursor)..anchor.max(self.cursor),
+            None => self.cursor..self.cursor,
         }
     }
 
-    // 🪲 TODO: JS: addEventListener("input") + macroquad equivalent to get actual characters + no keymapping
     #[must_use = "returns command"]
-    pub fn tick(&mut self, inputs: &Inputs) -> Option<String> {
+    pub fn tick(&mut self, inputs: &Inputs, completer: &impl Complete) -> Option<String> {
         if let Some(key) = self.hotkey
             && inputs.just_pressed(key)
         {
-            toggle(&mut self.active)
+            toggle(&mut self.active);
+            return None; // 👈 don't type the hotkey character into the console
         }
 
         if !self.active {
             return None;
         }
 
-        for chr in inputs.input_characters().chars() {
-            match chr {
-                BACKSPACE | BACKSPACE_MAC => drop(self.input_buffer.pop()), // backspace (linux, windows | mac)
-                ENTER => return Some(take(&mut self.input_buffer)),         // 👈
-                ESCAPE => self.active = false,
-                chr if !chr.is_ascii_control() => self.input_buffer.push(chr),
-                _ => (),
+        // input_buffer may have been assigned directly
+        if !self.input_buffer.is_char_boundary(self.cursor) {
+            self.cursor = self.input_buffer.len();
+        }
+        if self.anchor.is_some_and(|anchor| !self.input_buffer.is_char_boundary(anchor)) {
+            self.anchor = None;
+        }
+
+        if inputs.just_pressed(K_UP) {
+            self.browse_history(-1);
+        }
+        if inputs.just_pressed(K_DOWN) {
+            self.browse_history(1);
+        }
+        if inputs.just_pressed(K_TAB) {
+            self.complete(completer);
+        }
+
+        // cursor movement, selecting while shift is held
+        let shift = inputs.mods().shift;
+        if inputs.pressed_or_repeated(K_LEFT) {
+            let to = if self.anchor.is_some() && !shift { self.selection().start } else { self.prev_boundary() };
+            self.move_cursor(to, shift);
+        }
+        if inputs.pressed_or_repeated(K_RIGHT) {
+            let to = if self.anchor.is_some() && !shift { self.selection().end } else { self.next_boundary() };
+            self.move_cursor(to, shift);
+        }
+        if inputs.just_pressed(K_HOME) {
+            self.move_cursor(0, shift);
+        }
+        if inputs.just_pressed(K_END) {
+            self.move_cursor(self.input_buffer.len(), shift);
+        }
+
+        // clipboard (`K_COPY` etc. are bound in the game's keymap, e.g. to ctrl+c)
+        if inputs.just_pressed(K_SELECT_ALL) {
+            self.anchor = Some(0);
+            self.cursor = self.input_buffer.len();
+        }
+        if inputs.just_pressed(K_COPY) || inputs.just_pressed(K_CUT) {
+            let selected = &self.input_buffer[self.selection()];
+            if !selected.is_empty() {
+                self.copied = Some(selected.to_owned());
             }
         }
+        if inputs.just_pressed(K_CUT) {
+            self.delete_selection();
+        }
+
+        if inputs.pressed_or_repeated(K_DELETE) && !self.delete_selection() {
+            self.input_buffer.replace_range(self.cursor..self.next_boundary(), "");
+        }
+
+        // scrolling
+        let page = usize::max(1, self.page_rows.saturating_sub(1));
+        if inputs.just_pressed(K_PAGE_UP) {
+            self.scroll += page; // 👈 clamped when drawing
+        }
+        if inputs.just_pressed(K_PAGE_DOWN) {
+            self.scroll = self.scroll.saturating_sub(page);
+        }
+
+        if inputs.pressed_or_repeated(K_BACKSPACE) && !self.delete_selection() {
+            self.backspace();
+        }
+        if inputs.just_pressed(K_ESC) {
+            self.active = false;
+        }
+
+        if !inputs.text().is_empty() {
+            self.delete_selection(); // 👈 typing replaces the selection
+            self.input_buffer.insert_str(self.cursor, inputs.text());
+            self.cursor += inputs.text().len();
+        }
+        self.composition = inputs.composition().to_owned();
+
+        if inputs.just_pressed(K_ENTER) {
+            return self.submit(); // 👈
+        }
 
         None
     }
 
-    pub fn draw(&self, out: &mut Out) {
+    /// Move the cursor, extending the selection if `select`, else clearing it.
+    fn move_cursor(&mut self, to: usize, select: bool) {
+        match select {
+            true => _ = self.anchor.get_or_insert(self.cursor),
+            false => self.anchor = None,
+        }
+        self.cursor = to;
+        if self.anchor == Some(self.cursor) {
+            self.anchor = None;
+        }
+    }
+
+    /// Remove the selected text, if any. Returns `true` if there was a selection.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        if selection.is_empty() {
+            return false;
+        }
+        self.input_buffer.replace_range(selection.clone(), "");
+        self.cursor = selection.start;
+        true
+    }
+
+    fn backspace(&mut self) {
+        let prev = self.prev_boundary();
+        self.input_buffer.replace_range(prev..self.cursor, "");
+        self.cursor = prev;
+    }
+
+    /// Byte index of the character before the cursor.
+    fn prev_boundary(&self) -> usize {
+        self.input_buffer[..self.cursor].char_indices().next_back().map(|(i, _)| i).unwrap_or(0)
+    }
+
+    /// Byte index of the character after the cursor.
+    fn next_boundary(&self) -> usize {
+        self.input_buffer[self.cursor..].chars().next().map(|chr| self.cursor + chr.len_utf8()).unwrap_or(self.cursor)
+    }
+
+    /// Take the input buffer as command, echo and add it to history.
+    fn submit(&mut self) -> Option<String> {
+        let cmd = take(&mut self.input_buffer);
+        self.cursor = 0;
+        self.anchor = None;
+        self.scroll = 0;
+        self.history_pos = None;
+        if cmd.trim().is_empty() {
+            return None;
+        }
+        self.print(format!(">{cmd}"));
+        if self.history.last() != Some(&cmd) {
+            self.history.push(cmd.clone());
+        }
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        Some(cmd)
+    }
+
+    /// Replace input buffer by an older (`delta < 0`) or newer command from the history.
+    fn browse_history(&mut self, delta: isize) {
+        let len = self.history.len();
+        let pos = match (self.history_pos, delta < 0) {
+            (None, true) => len.checked_sub(1),
+            (None, false) => None,
+            (Some(pos), true) => Some(pos.saturating_sub(1)),
+            (Some(pos), false) => Some(pos + 1).filter(|&pos| pos < len),
+        };
+        self.history_pos = pos;
+        self.set_input(pos.and_then(|pos| self.history.get(pos)).cloned().unwrap_or_default());
+    }
+
+    /// Tab-complete the input buffer: complete a unique match,
+    /// otherwise extend to the longest common prefix and print the candidates.
+    fn complete(&mut self, completer: &impl Complete) {
+        let candidates = completer.complete(&self.input_buffer);
+        match candidates.as_slice() {
+            [] => (),
+            [unique] => self.set_input(format!("{unique} ")),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.as_str(), |common, v| &common[..common_prefix_len(common, v)]);
+                self.set_input(common);
+                self.print(candidates.join(" "));
+            }
+        }
+    }
+
+    pub fn draw(&mut self, out: &mut Out) {
         if !self.active {
             return;
         }
 
         let layer = L_CLI;
+        let char_size = EMBEDDED_CHAR_SIZE.as_i32();
+        let screen_size = (out.viewport_size / EMBEDDED_CHAR_SIZE.as_u32()).as_usize();
+        let width = usize::max(1, screen_size.x());
+
         // clear background
-        const CONSOLE_BG: RGBA = RGBA([0, 0, 0, 184]);
         out.draw_rect_screen(layer, Rectangle::new(Bounds2D::new(vec2(0, 0), out.viewport_size.as_()), RGBA::TRANSPARENT).with_fill(CONSOLE_BG));
 
-        // draw input buffer
-        let screen_size = (out.viewport_size / EMBEDDED_CHAR_SIZE.as_u32()).as_u16();
-        let text = ">".to_string() + &self.input_buffer + "_";
-        let buffer_height = text_height_lines(&text, screen_size.x());
-
-        let mut y = (screen_size.y() - buffer_height) * (EMBEDDED_CHAR_SIZE.y() as u16);
-        out.draw_text_screen(layer, vec2(0, y as i32), &text);
-
-        // draw tail of output buffer
-        let mut i = self.output.len();
-        while i > 0 && y > 0 {
-            i -= 1;
-            if let Some(line) = self.output.get(i) {
-                y -= text_height_lines(&line, screen_size.x()) * (EMBEDDED_CHAR_SIZE.y() as u16);
-                out.draw_text_screen(layer, vec2(0, y as i32), &line);
-            }
+        // input buffer at the bottom, cursor drawn over it (after the IME composition, if any).
+        let (before, after) = self.input_buffer.split_at(self.cursor.min(self.input_buffer.len()));
+        let input = format!(">{before}{}{after} ", self.composition);
+        let input_rows = wrap(&input, width).collect::<Vec<_>>();
+        let input_y = screen_size.y().saturating_sub(input_rows.len());
+        for (i, row) in input_rows.iter().enumerate() {
+            out.draw_text_screen(layer, vec2(0, (input_y + i) as i32 * char_size.y()), row);
+        }
+        let cursor = 1 + before.chars().count() + self.composition.chars().count();
+        let cursor_pos = vec2((cursor % width) as i32, (input_y + cursor / width) as i32) * char_size;
+        out.draw_text_screen(layer, cursor_pos, "_");
+
+        // 👇 selection highlighted behind the text (characters after the leading `>`).
+        let selection = self.selection();
+        let start = 1 + self.input_buffer[..selection.start].chars().count();
+        for i in start..start + self.input_buffer[selection].chars().count() {
+            let pos = vec2((i % width) as i32, (input_y + i / width) as i32) * char_size;
+            out.draw_rect_screen(layer, Rectangle::with_size(pos, char_size, RGBA::TRANSPARENT).with_fill(SELECTION_BG));
+        }
+
+        // output above, scrolled.
+        let output = self.output.iter().collect::<Vec<_>>();
+        let rows = output.iter().flat_map(|line| wrap(line, width)).collect::<Vec<_>>();
+        self.page_rows = input_y;
+        self.scroll = self.scroll.min(rows.len().saturating_sub(self.page_rows));
+
+        // 👇 when scrolled up, the bottom output row shows how much more there is.
+        let scrolled = self.scroll != 0 && self.page_rows != 0;
+        let avail = self.page_rows - scrolled as usize;
+        let end = rows.len() - self.scroll;
+        let visible = &rows[end.saturating_sub(avail)..end];
+        let top = avail - visible.len();
+        for (i, row) in visible.iter().enumerate() {
+            out.draw_text_screen(layer, vec2(0, (top + i) as i32 * char_size.y()), row);
+        }
+        if scrolled {
+            let more = format!("-- {} more (PageDown) --", self.scroll);
+            out.draw_text_screen(layer, vec2(0, (input_y - 1) as i32 * char_size.y()), more);
         }
     }
 }
 
-fn text_height_lines(txt: &str, screen_width_lines: u16) -> u16 {
-    let mut height = 0;
-    for line in txt.lines() {
-        height += line.len() as u16 / screen_width_lines + 1;
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices().zip(b.chars()).find(|((_, a), b)| a != b).map(|((i, _), _)| i).unwrap_or(usize::min(a.len(), b.len()))
+}
+
+/// Split text into rows of at most `width` characters (not bytes).
+/// Newlines start a new row, an empty line is one empty row.
+fn wrap(text: &str, width: usize) -> impl Iterator<Item = &str> {
+    text.split('\n').flat_map(move |line| {
+        let mut starts = line.char_indices().map(|(i, _)| i).step_by(width).collect::<Vec<_>>();
+        if starts.is_empty() {
+            starts.push(0);
+        }
+        let ends = starts.iter().skip(1).copied().chain([line.len()]).collect::<Vec<_>>();
+        starts.into_iter().zip(ends).map(move |(start, end)| &line[start..end])
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    struct NoCompletions;
+
+    impl Complete for NoCompletions {
+        fn complete(&self, _: &str) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    /// Press (and release) `keys` in order, typing `text`. Returns the requests the console made.
+    fn press(console: &mut Console, keys: &[&str], text: &str) -> Vec<ShellRequest> {
+        let keys = keys.iter().map(|k| k.parse::<Button>().unwrap()).collect::<Vec<_>>();
+        let mut inputs = Inputs::default();
+        let down = keys.iter().map(|&button| InputEvent::Key { button, direction: KeyDir::Down });
+        inputs.tick(&Keymap::default(), down.chain([InputEvent::Text(text.into())]));
+        let mut out = Out::default();
+        _ = console.tick_and_draw(&inputs, &NoCompletions, &mut out);
+        out.requests
+    }
+
+    fn console(input: &str) -> Console {
+        Console { active: true, ..Default::default() }.with(|c| c.set_input(input))
+    }
+
+    #[gtest]
+    fn edit_line() {
+        let mut c = console("héllo");
+        press(&mut c, &["left", "left"], "");
+        press(&mut c, &["backspace"], "");
+        expect_that!(c.input_buffer, eq("hélo"));
+        press(&mut c, &["home"], ">");
+        press(&mut c, &["delete"], "");
+        expect_that!((c.input_buffer.as_str(), c.cursor), eq((">élo", 1)));
+    }
+
+    #[gtest]
+    fn select_and_replace() {
+        let mut c = console("speed 10");
+        press(&mut c, &["shiftleft", "left"], "");
+        press(&mut c, &["shiftleft", "left"], "");
+        expect_that!(&c.input_buffer[c.selection()], eq("10"));
+        press(&mut c, &[], "3"); // 👈 typing replaces the selection
+        expect_that!(c.input_buffer, eq("speed 3"));
+        expect_that!(c.selection(), eq(&(7..7)));
+
+        press(&mut c, &["shiftleft", "home"], "");
+        expect_that!(&c.input_buffer[c.selection()], eq("speed 3"));
+        press(&mut c, &["right"], ""); // 👈 collapses to the end
+        expect_that!((c.selection(), c.cursor), eq(&(7..7, 7)));
+        press(&mut c, &["shiftleft", "left"], "");
+        press(&mut c, &["backspace"], "");
+        expect_that!(c.input_buffer, eq("speed "));
+    }
+
+    #[gtest]
+    fn copy_and_cut() {
+        let mut c = console("dt 0.5");
+        expect_that!(press(&mut c, &["copy"], ""), is_empty()); // 👈 nothing selected
+        press(&mut c, &["selectall"], "");
+        expect_that!(press(&mut c, &["copy"], ""), eq(&[ShellRequest::SetClipboard { text: "dt 0.5".into() }]));
+        expect_that!(c.input_buffer, eq("dt 0.5"));
+        press(&mut c, &["shiftleft", "home"], "");
+        press(&mut c, &["shiftleft", "end"], "");
+        expect_that!(c.selection(), eq(&(0..6)));
+        press(&mut c, &["home"], "");
+        press(&mut c, &["shiftleft", "right"], "");
+        press(&mut c, &["shiftleft", "right"], "");
+        expect_that!(press(&mut c, &["cut"], ""), eq(&[ShellRequest::SetClipboard { text: "dt".into() }]));
+        expect_that!((c.input_buffer.as_str(), c.cursor), eq((" 0.5", 0)));
     }
-    height
 }
@@ -1,4 +1,18 @@
 pub(crate) mod prelude;
 
+mod autosave;
+mod camera_control;
+mod commands;
 mod console;
+mod inspector;
+mod metrics;
+mod script;
+mod ui;
+pub use autosave::*;
+pub use camera_control::*;
+pub use commands::*;
 pub use console::*;
+pub use inspector::*;
+pub use metrics::*;
+pub use script::*;
+pub use ui::*;
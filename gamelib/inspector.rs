@@ -0,0 +1,309 @@
+use crate::prelude::*;
+use crate::*;
+use std::ops::RangeInclusive;
+
+/// 🔍 Property inspector panel: browse and live-edit the (nested) fields of a `Reflect` value.
+///
+/// Click a field to open it, click `..` to go back up.
+/// Numbers have `-`/`+` buttons, booleans toggle on click,
+/// other values open the console with a pre-filled `set` command.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Inspector {
+    /// Shows/hides the inspector.
+    pub hotkey: Option<Button>,
+
+    /// Is the inspector currently shown?
+    pub active: bool,
+
+    /// Path of the value currently shown, relative to the inspected root.
+    pub path: String,
+
+    /// Index of the first field shown (for long lists).
+    pub scroll: usize,
+}
+
+/// Panel width, in characters.
+const PANEL_CHARS: usize = 40;
+
+/// Character columns of the `[-]` and `[+]` buttons.
+const MINUS_BUTTON: RangeInclusive<usize> = (PANEL_CHARS - 7)..=(PANEL_CHARS - 5);
+const PLUS_BUTTON: RangeInclusive<usize> = (PANEL_CHARS - 4)..=(PANEL_CHARS - 2);
+
+/// Multiplication factor for the `-`/`+` buttons.
+const NUDGE_FACTOR: f64 = 1.1;
+
+/// `-`/`+` step for values that are (nearly) zero.
+const NUDGE_STEP: f64 = 0.1;
+
+const PANEL_BG: RGBA = RGBA([0, 0, 0, 184]);
+const HOVER_BG: RGBA = RGBA([255, 255, 255, 48]);
+
+enum Row {
+    Header,
+    Up,
+    ScrollUp,
+    ScrollDown,
+    Field { name: String, value: String, has_children: bool, numeric: bool },
+}
+
+impl Inspector {
+    pub fn with_hotkey(hotkey: Button) -> Self {
+        Self::default().with(|v| v.hotkey = Some(hotkey))
+    }
+
+    /// Handle mouse clicks on the panel and draw it.
+    /// `root_name` is the `set` path prefix used when pre-filling the console (e.g. `"sel"`).
+    /// Clicks handled by the inspector are consumed.
+    pub fn tick_and_draw(&mut self, inputs: &mut Inputs, console: &mut Console, root_name: &str, root: &mut dyn Reflect, out: &mut Out) {
+        if let Some(key) = self.hotkey
+            && inputs.just_pressed(key)
+        {
+            toggle(&mut self.active)
+        }
+
+        if !self.active {
+            return;
+        }
+
+        // inspected value may have disappeared (e.g. list got shorter)
+        if root.at_path(&self.path).is_err() {
+            self.path.clear();
+            self.scroll = 0;
+        }
+
+        let rows = self.rows(root, out);
+        if let Some(i) = self.hovered_row(inputs.mouse_position(), rows.len(), out)
+            && inputs.just_pressed(K_MOUSE1)
+        {
+            inputs.consume(K_MOUSE1);
+            let column = (inputs.mouse_position().x() - Self::panel_x(out)) / EMBEDDED_CHAR_SIZE.x() as i32;
+            self.click(&rows[i], column as usize, console, root_name, root);
+        }
+
+        self.draw(inputs, root, out);
+    }
+
+    fn click(&mut self, row: &Row, column: usize, console: &mut Console, root_name: &str, root: &mut dyn Reflect) {
+        let page = 8;
+        match row {
+            Row::Header => (),
+            Row::Up => {
+                self.path = self.path.rsplit_once('.').map(|(parent, _)| parent.to_owned()).unwrap_or_default();
+                self.scroll = 0;
+            }
+            Row::ScrollUp => self.scroll = self.scroll.saturating_sub(page),
+            Row::ScrollDown => self.scroll += page,
+            Row::Field { name, has_children: true, .. } => {
+                self.path = join_path(&self.path, name);
+                self.scroll = 0;
+            }
+            Row::Field { name, value, numeric, .. } => {
+                let path = join_path(&self.path, name);
+                let result = match column {
+                    c if *numeric && MINUS_BUTTON.contains(&c) => root.at_path_mut(&path).and_then(|v| nudge(v, false)),
+                    c if *numeric && PLUS_BUTTON.contains(&c) => root.at_path_mut(&path).and_then(|v| nudge(v, true)),
+                    _ if value.parse::<bool>().is_ok() => root.set_path(&path, &(value != "true").to_string()),
+                    _ => {
+                        console.active = true;
+                        console.set_input(format!("set {} {value}", join_path(root_name, &path)));
+                        Ok(())
+                    }
+                };
+                if let Err(e) = result {
+                    console.print(format!("{path}: {e}"));
+                }
+            }
+        }
+    }
+
+    fn rows(&self, root: &dyn Reflect, out: &Out) -> Vec<Row> {
+        let Ok(value) = root.at_path(&self.path) else { return vec![Row::Header] };
+
+        let mut rows = vec![Row::Header];
+        if !self.path.is_empty() {
+            rows.push(Row::Up);
+        }
+        if self.scroll > 0 {
+            rows.push(Row::ScrollUp);
+        }
+
+        let max_rows = Self::max_rows(out);
+        let fields = value.fields();
+        for (k, name) in fields.iter().enumerate().skip(self.scroll) {
+            if rows.len() == max_rows - 1 && k + 1 < fields.len() {
+                rows.push(Row::ScrollDown);
+                break;
+            }
+            let Some(field) = value.field(name) else { continue };
+            let value = field.get_string();
+            rows.push(Row::Field {
+                numeric: value.parse::<f64>().is_ok(),
+                has_children: !field.fields().is_empty(),
+                name: name.clone(),
+                value,
+            });
+        }
+        rows
+    }
+
+    fn draw(&self, inputs: &Inputs, root: &dyn Reflect, out: &mut Out) {
+        let rows = self.rows(root, out);
+        let char_size = EMBEDDED_CHAR_SIZE.as_i32();
+        let x = Self::panel_x(out);
+        let size = vec2(PANEL_CHARS as i32 * char_size.x(), rows.len() as i32 * char_size.y());
+        out.draw_rect_screen(L_UI_BG, Rectangle::with_size((x, 0), size, RGBA::TRANSPARENT).with_fill(PANEL_BG));
+
+        if let Some(i) = self.hovered_row(inputs.mouse_position(), rows.len(), out) {
+            let pos = vec2(x, i as i32 * char_size.y());
+            out.draw_rect_screen(L_UI_BG, Rectangle::with_size(pos, (size.x(), char_size.y()), RGBA::TRANSPARENT).with_fill(HOVER_BG));
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            let text = match row {
+                Row::Header => format!("[{}]", if self.path.is_empty() { "." } else { &self.path }),
+                Row::Up => "..".into(),
+                Row::ScrollUp => " ...".into(),
+                Row::ScrollDown => " ...".into(),
+                Row::Field { name, value, has_children: true, .. } => format!(" {name}/ {value}"),
+                Row::Field { name, value, numeric: true, .. } => format!(" {name}: {value:<w$.w$}[-][+]", w = PANEL_CHARS.saturating_sub(name.len() + 10)),
+                Row::Field { name, value, .. } => format!(" {name}: {value}"),
+            };
+            let text = text.chars().take(PANEL_CHARS).collect::<String>();
+            out.draw_text_screen(L_UI, (x, i as i32 * char_size.y()), text);
+        }
+    }
+
+    /// Index of the row under the mouse, if any.
+    fn hovered_row(&self, mouse: vec2i, num_rows: usize, out: &Out) -> Option<usize> {
+        let x = Self::panel_x(out);
+        let row = mouse.y() / EMBEDDED_CHAR_SIZE.y() as i32;
+        (mouse.x() >= x && mouse.y() >= 0 && (row as usize) < num_rows).then_some(row as usize)
+    }
+
+    /// Left edge of the panel, which is right-aligned to the viewport.
+    fn panel_x(out: &Out) -> i32 {
+        out.viewport_size.x() as i32 - (PANEL_CHARS as i32) * EMBEDDED_CHAR_SIZE.x() as i32
+    }
+
+    fn max_rows(out: &Out) -> usize {
+        usize::max(4, out.viewport_size.y() as usize / EMBEDDED_CHAR_SIZE.y() as usize)
+    }
+}
+
+/// Multiply a number by `NUDGE_FACTOR` (or divide), or step integers by one.
+fn nudge(v: &mut dyn Reflect, up: bool) -> Result<()> {
+    let old = v.get_string().parse::<f64>()?;
+    let new = match old {
+        _ if old.abs() < NUDGE_STEP => old + if up { NUDGE_STEP } else { -NUDGE_STEP },
+        _ if (old > 0.0) == up => old * NUDGE_FACTOR,
+        _ => old / NUDGE_FACTOR,
+    };
+    // 👇 integers don't parse "5.5", step by 1 instead.
+    v.set_string(&new.to_string()).or_else(|_| v.set_string(&(old + if up { 1.0 } else { -1.0 }).to_string()))
+}
+
+fn join_path(parent: &str, child: &str) -> String {
+    match parent {
+        "" => child.to_owned(),
+        parent => format!("{parent}.{child}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+    use proc_macros::Reflect;
+
+    #[derive(Reflect, Default)]
+    struct World {
+        critters: Vec<Critter>,
+        paused: bool,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Critter {
+        stiffness: f32,
+        legs: u32,
+        name: String,
+    }
+
+    fn world() -> World {
+        let critter = Critter { stiffness: 50.0, legs: 6, name: "crab".into() };
+        World { critters: vec![critter], paused: false }
+    }
+
+    fn out() -> Out {
+        Out {
+            viewport_size: vec2(640, 480),
+            ..Default::default()
+        }
+    }
+
+    /// Text of the `Field` rows: `name=value`.
+    fn fields(inspector: &Inspector, root: &dyn Reflect) -> Vec<String> {
+        let rows = inspector.rows(root, &out());
+        rows.iter().filter_map(|row| if let Row::Field { name, value, .. } = row { Some(format!("{name}={value}")) } else { None }).collect()
+    }
+
+    fn field(inspector: &Inspector, root: &dyn Reflect, name: &str) -> Row {
+        let rows = inspector.rows(root, &out());
+        rows.into_iter().find(|row| matches!(row, Row::Field { name: n, .. } if n == name)).unwrap()
+    }
+
+    #[gtest]
+    fn list_fields() {
+        let w = world();
+        let mut inspector = Inspector::default();
+        expect_that!(fields(&inspector, &w), eq(&["critters=[1 items]", "paused=false"]));
+        inspector.path = "critters.0".into();
+        expect_that!(fields(&inspector, &w), eq(&["stiffness=50", "legs=6", "name=crab"]));
+    }
+
+    #[gtest]
+    fn edit_fields() {
+        let (mut w, mut console) = (world(), Console::default());
+        let mut inspector = Inspector::default();
+
+        inspector.click(&field(&inspector, &w, "paused"), 0, &mut console, "w", &mut w); // 👈 booleans toggle
+        expect_that!(w.paused, eq(true));
+        inspector.click(&field(&inspector, &w, "critters"), 0, &mut console, "w", &mut w);
+        expect_that!(inspector.path, eq("critters"));
+
+        inspector.path = "critters.0".into();
+        inspector.click(&field(&inspector, &w, "stiffness"), *PLUS_BUTTON.start(), &mut console, "w", &mut w);
+        expect_that!(w.critters[0].stiffness, eq(55.0));
+        inspector.click(&field(&inspector, &w, "legs"), *MINUS_BUTTON.start(), &mut console, "w", &mut w);
+        expect_that!(w.critters[0].legs, eq(5)); // 👈 integers step by one
+
+        inspector.click(&field(&inspector, &w, "name"), 0, &mut console, "w", &mut w);
+        expect_that!(console.active, eq(true));
+        expect_that!(console.input_buffer, eq("set w.critters.0.name crab"));
+        expect_that!(console.output.len(), eq(0));
+    }
+
+    #[gtest]
+    fn reject_bad_path_or_value() {
+        let (mut w, mut console) = (world(), Console::default());
+        let mut inspector = Inspector {
+            active: true,
+            path: "critters.7".into(),
+            scroll: 3,
+            ..Default::default()
+        };
+        inspector.tick_and_draw(&mut Inputs::default(), &mut console, "w", &mut w, &mut out());
+        expect_that!((inspector.path.as_str(), inspector.scroll), eq(("", 0))); // 👈 back to the root
+
+        let field = Row::Field {
+            name: "legs".into(),
+            value: "-1".into(),
+            has_children: false,
+            numeric: true,
+        };
+        inspector.path = "critters.0".into();
+        w.critters[0].legs = 0;
+        inspector.click(&field, *MINUS_BUTTON.start(), &mut console, "w", &mut w);
+        expect_that!(w.critters[0].legs, eq(0));
+        expect_that!(console.output.len(), eq(1)); // 👈 u32 can't go negative
+    }
+}
@@ -0,0 +1,123 @@
+use crate::prelude::*;
+use crate::*;
+use anyhow::bail;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+
+/// 📈 Recent history of named metrics (e.g. population, energy), one ring buffer each.
+/// Games `record` values every tick, the console's `plot <metric>` command picks which ones `draw` shows.
+#[derive(Debug)]
+pub struct Metrics {
+    /// Number of samples kept per metric. Older samples are dropped.
+    pub capacity: usize,
+    series: BTreeMap<String, VecDeque<f32>>,
+    /// Metrics shown by `draw`, in the order they were added.
+    plotted: Vec<String>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            capacity: 600,
+            series: BTreeMap::new(),
+            plotted: Vec::new(),
+        }
+    }
+}
+
+impl Metrics {
+    /// Append a sample, dropping the oldest if at capacity.
+    pub fn record(&mut self, name: &str, value: f32) {
+        let series = match self.series.get_mut(name) {
+            Some(series) => series,
+            None => self.series.entry(name.to_owned()).or_default(),
+        };
+        while series.len() >= self.capacity.max(1) {
+            series.pop_front();
+        }
+        series.push_back(value);
+    }
+
+    /// Samples of a metric, oldest first.
+    pub fn values(&self, name: &str) -> Option<impl Iterator<Item = f32> + '_> {
+        self.series.get(name).map(|series| series.iter().copied())
+    }
+
+    pub fn latest(&self, name: &str) -> Option<f32> {
+        self.series.get(name).and_then(|series| series.back().copied())
+    }
+
+    /// All recorded metrics, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.series.keys().map(String::as_str)
+    }
+
+    pub fn plotted(&self) -> &[String] {
+        &self.plotted
+    }
+
+    /// Show or hide a metric's plot. Returns whether it's shown now.
+    pub fn toggle_plot(&mut self, name: &str) -> Result<bool> {
+        if !self.series.contains_key(name) {
+            bail!("no such metric: {name} (have: {})", self.names().collect::<Vec<_>>().join(", "))
+        }
+        match self.plotted.iter().position(|v| v == name) {
+            Some(i) => _ = self.plotted.remove(i),
+            None => self.plotted.push(name.to_owned()),
+        }
+        Ok(self.plotted.iter().any(|v| v == name))
+    }
+
+    /// Latest value of every metric, one per line (e.g. for `Out::debug`).
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        for (name, series) in &self.series {
+            if let Some(value) = series.back() {
+                _ = writeln!(summary, "{name}: {value:.3}");
+            }
+        }
+        summary
+    }
+
+    /// Plots of the metrics picked by `toggle_plot`, in a UI panel.
+    pub fn draw(&self, ui: &mut Ui) {
+        for name in &self.plotted {
+            if let Some(values) = self.values(name) {
+                ui.plot(name, values);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn ring_buffer() {
+        let mut metrics = Metrics { capacity: 3, ..Default::default() };
+        for v in 1..=5 {
+            metrics.record("energy", v as f32);
+        }
+        metrics.record("population", 7.0);
+        expect_that!(metrics.values("energy").unwrap().collect::<Vec<_>>(), eq(&[3.0, 4.0, 5.0])); // 👈 oldest dropped
+        expect_that!(metrics.latest("population"), some(eq(7.0)));
+        expect_that!(metrics.values("speed").is_none(), eq(true));
+        expect_that!(metrics.names().collect::<Vec<_>>(), eq(&["energy", "population"]));
+        expect_that!(metrics.summary(), eq("energy: 5.000\npopulation: 7.000\n"));
+    }
+
+    #[gtest]
+    fn toggle_plot() {
+        let mut metrics = Metrics::default();
+        metrics.record("energy", 1.0);
+        metrics.record("speed", 2.0);
+        expect_that!(metrics.toggle_plot("speed"), ok(eq(&true)));
+        expect_that!(metrics.toggle_plot("energy"), ok(eq(&true)));
+        expect_that!(metrics.plotted(), eq(&["speed", "energy"]));
+        expect_that!(metrics.toggle_plot("speed"), ok(eq(&false)));
+        expect_that!(metrics.plotted(), eq(&["energy"]));
+        expect_that!(metrics.toggle_plot("nope"), err(displays_as(eq("no such metric: nope (have: energy, speed)"))));
+    }
+}
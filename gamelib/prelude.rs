@@ -1,9 +1,11 @@
 pub use cel::*;
 pub use core_util::*;
 pub use geometry::*;
+pub use reflect::{Reflect, ReflectPath};
 pub use shell_api::*;
 pub use vector::*;
 
+pub use anyhow::Result;
 pub use serde::{Deserialize, Serialize};
 
 pub fn toggle(v: &mut bool) {
@@ -0,0 +1,127 @@
+use crate::prelude::*;
+use anyhow::{anyhow, bail};
+use std::collections::BTreeMap;
+
+/// 📜 Console scripting: `;`-separated command lists, variables and aliases.
+///
+/// ```text
+/// var k 50
+/// alias stiff "set sel.body.springs.*.k $$k; echo k=$$k"
+/// stiff
+/// ```
+/// `$name` is replaced by the variable's value, `$$` by a literal `$`
+/// (so that aliases can refer to the value at the time they run).
+/// Extra words after an alias are appended to (the last command of) its body.
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Script {
+    pub vars: BTreeMap<String, String>,
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// Protects against recursive aliases.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+impl Script {
+    /// Expand a line into the commands to run: split on `;`, substitute variables, expand aliases.
+    pub fn expand(&self, line: &str) -> Result<Vec<String>> {
+        let mut cmds = Vec::new();
+        self.expand_rec(line, 0, &mut cmds)?;
+        Ok(cmds)
+    }
+
+    fn expand_rec(&self, line: &str, depth: usize, cmds: &mut Vec<String>) -> Result<()> {
+        if depth > MAX_ALIAS_DEPTH {
+            bail!("alias nested too deep (recursive alias?)");
+        }
+        for cmd in split_commands(line) {
+            let cmd = self.substitute(cmd)?;
+            let (name, args) = cmd.trim().split_once(' ').unwrap_or((cmd.trim(), ""));
+            match self.aliases.get(name) {
+                Some(body) => self.expand_rec(&format!("{body} {args}"), depth + 1, cmds)?,
+                None if cmd.trim().is_empty() => (),
+                None => cmds.push(cmd.trim().to_owned()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace `$name` by the value of variable `name`, `$$` by `$`.
+    fn substitute(&self, cmd: &str) -> Result<String> {
+        let mut result = String::new();
+        let mut rest = cmd;
+        while let Some(i) = rest.find('$') {
+            result += &rest[..i];
+            rest = &rest[i + 1..];
+            if let Some(tail) = rest.strip_prefix('$') {
+                result.push('$');
+                rest = tail;
+                continue;
+            }
+            let len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+            let name = &rest[..len];
+            result += self.vars.get(name).ok_or_else(|| anyhow!("undefined variable: ${name}"))?;
+            rest = &rest[len..];
+        }
+        Ok(result + rest)
+    }
+}
+
+/// Split on `;`, except inside double quotes.
+fn split_commands(line: &str) -> Vec<&str> {
+    let mut cmds = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => toggle(&mut quoted),
+            ';' if !quoted => {
+                cmds.push(&line[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    cmds.push(&line[start..]);
+    cmds
+}
+
+/// Strip surrounding double quotes, if any.
+pub fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    fn script() -> Script {
+        let mut s = Script::default();
+        s.vars.insert("k".into(), "50".into());
+        s.aliases.insert("stiff".into(), "set k $k; echo done".into());
+        s.aliases.insert("sp".into(), "speed".into());
+        s.aliases.insert("loop".into(), "loop".into());
+        s
+    }
+
+    #[gtest]
+    fn expand() {
+        let s = script();
+        expect_eq!(s.expand("pause").unwrap(), vec!["pause"]);
+        expect_eq!(s.expand("pause; speed 2;").unwrap(), vec!["pause", "speed 2"]);
+        expect_eq!(s.expand("dt $k").unwrap(), vec!["dt 50"]);
+        expect_eq!(s.expand("echo $$k").unwrap(), vec!["echo $k"]);
+        expect_eq!(s.expand("stiff").unwrap(), vec!["set k 50", "echo done"]);
+        expect_eq!(s.expand("sp 4").unwrap(), vec!["speed 4"]);
+        expect_eq!(s.expand(r#"alias x "a; b""#).unwrap(), vec![r#"alias x "a; b""#]);
+    }
+
+    #[gtest]
+    fn errors() {
+        let s = script();
+        expect_true!(s.expand("dt $nope").is_err());
+        expect_true!(s.expand("loop").is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use crate::prelude::*;
+use std::ops::RangeInclusive;
+
+/// 🎛️ Immediate-mode UI: widgets are declared every tick, handle `Inputs` on the spot
+/// and draw themselves to the UI layers (panels on `L_UI_BG`, widgets on `L_UI`, highlights on `L_UI_FG`).
+///
+/// Only what must outlive a tick (the slider being dragged) is kept here.
+/// E.g. `ui.panel(inputs, out, pos, width, |ui| _ = ui.slider("dt", &mut dt, 0.001..=0.1))`.
+#[derive(Default)]
+pub struct UiState {
+    pub style: UiStyle,
+    /// Widget being dragged (mouse went down on it, and not up yet).
+    active: Option<WidgetId>,
+}
+
+/// Identifies a widget across ticks: its panel's position, and its place in the panel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WidgetId {
+    panel: vec2f,
+    index: usize,
+}
+
+/// Colors, font and spacing of all widgets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiStyle {
+    pub text: TextStyle,
+    /// Space around and between widgets (pixels).
+    pub padding: f32,
+    pub panel_bg: RGBA,
+    pub widget_bg: RGBA,
+    pub hover_bg: RGBA,
+    /// Slider fill, checkbox check, plot line.
+    pub accent: RGBA,
+}
+
+impl Default for UiStyle {
+    fn default() -> Self {
+        Self {
+            text: TextStyle::default(),
+            padding: 4.0,
+            panel_bg: RGBA([0, 0, 0, 184]),
+            widget_bg: RGBA([255, 255, 255, 24]),
+            hover_bg: RGBA([255, 255, 255, 64]),
+            accent: RGBA([80, 160, 255, 255]),
+        }
+    }
+}
+
+/// Lays out widgets top to bottom in a panel, see `UiState::panel`.
+pub struct Ui<'a> {
+    state: &'a mut UiState,
+    inputs: &'a mut Inputs,
+    out: &'a mut Out,
+    /// Top-left corner of the panel.
+    pos: vec2f,
+    width: f32,
+    /// Top of the next widget, relative to `pos`.
+    cursor_y: f32,
+    /// Number of widgets so far, see `WidgetId`.
+    widgets: usize,
+}
+
+impl UiState {
+    /// A panel of widgets (added by `f`), with top-left corner `pos` and given width. Its height fits the widgets.
+    /// Clicks on the panel are consumed, so they don't reach the game. Returns the panel's bounds.
+    pub fn panel(&mut self, inputs: &mut Inputs, out: &mut Out, pos: impl IntoPos, width: f32, f: impl FnOnce(&mut Ui)) -> Bounds2Df {
+        // 👇 drag ended (can't use `is_down`: the press was consumed), or a new one may start.
+        if inputs.just_released(K_MOUSE1) || inputs.just_pressed(K_MOUSE1) {
+            self.active = None;
+        }
+        let style = self.style;
+        let mut ui = Ui {
+            state: self,
+            inputs,
+            out,
+            pos: pos.into_pos(),
+            width,
+            cursor_y: style.padding,
+            widgets: 0,
+        };
+        f(&mut ui);
+
+        let bounds = Bounds2D::with_size(ui.pos, vec2(width, ui.cursor_y));
+        ui.out.draw_rect_screen(L_UI_BG, Rectangle::new(bounds, RGBA::TRANSPARENT).with_fill(style.panel_bg));
+        if ui.hovered(bounds) && ui.inputs.just_pressed(K_MOUSE1) {
+            ui.inputs.consume(K_MOUSE1);
+        }
+        bounds
+    }
+}
+
+impl Ui<'_> {
+    /// Text, word-wrapped to the panel width.
+    pub fn label(&mut self, text: impl AsRef<str>) {
+        let style = self.state.style;
+        let size = style.text.text_size(text.as_ref(), Some(self.inner_width()));
+        let bounds = self.row(size.y());
+        self.out.draw_text_box(L_UI, bounds, text, style.text);
+    }
+
+    /// Returns `true` when clicked.
+    pub fn button(&mut self, text: impl AsRef<str>) -> bool {
+        let bounds = self.widget_row();
+        let clicked = self.clicked(bounds);
+        self.widget_bg(bounds);
+        self.text(bounds, text.as_ref(), Align::Center);
+        clicked
+    }
+
+    /// Toggles `value` when clicked. Returns `true` if changed.
+    pub fn checkbox(&mut self, text: impl AsRef<str>, value: &mut bool) -> bool {
+        let style = self.state.style;
+        let bounds = self.widget_row();
+        let clicked = self.clicked(bounds);
+        if clicked {
+            toggle(value);
+        }
+        self.widget_bg(bounds);
+
+        // 👇 square box on the left, the label next to it.
+        let side = bounds.size().y() - style.padding;
+        let check = Bounds2D::with_size(bounds.min + vec2(style.padding, style.padding) / 2.0, vec2(side, side));
+        self.out.draw_rect_screen(L_UI, Rectangle::new(check, style.text.color));
+        if *value {
+            let inner = Bounds2D::new(check.min + vec2(2.0, 2.0), check.max - vec2(2.0, 2.0));
+            self.out.draw_rect_screen(L_UI_FG, Rectangle::new(inner, RGBA::TRANSPARENT).with_fill(style.accent));
+        }
+        self.text(Bounds2D::new(bounds.min + vec2(side + style.padding, 0.0), bounds.max), text.as_ref(), Align::Left);
+        clicked
+    }
+
+    /// Drag (or click) to set `value` within `range`. Shows `text: value`. Returns `true` if changed.
+    /// The dragged slider is remembered by its panel's position and its place in the panel (not by `text`),
+    /// so a drag is lost when the panel moves, or widgets are added above the slider, while dragging.
+    pub fn slider(&mut self, text: impl AsRef<str>, value: &mut f32, range: RangeInclusive<f32>) -> bool {
+        self.slider_impl(text.as_ref(), value, range, |v| format!("{v:.3}"))
+    }
+
+    /// `slider` for whole numbers.
+    pub fn int_slider(&mut self, text: impl AsRef<str>, value: &mut u32, range: RangeInclusive<u32>) -> bool {
+        let mut v = *value as f32;
+        self.slider_impl(text.as_ref(), &mut v, (*range.start() as f32)..=(*range.end() as f32), |v| format!("{}", v.round()));
+        let changed = v.round() as u32 != *value;
+        *value = v.round() as u32;
+        changed
+    }
+
+    fn slider_impl(&mut self, text: &str, value: &mut f32, range: RangeInclusive<f32>, fmt: impl Fn(f32) -> String) -> bool {
+        let bounds = self.widget_row();
+        let id = WidgetId { panel: self.pos, index: self.widgets };
+        if self.clicked(bounds) {
+            self.state.active = Some(id);
+        }
+        let (start, end) = (*range.start(), *range.end());
+        let mut changed = false;
+        if self.state.active == Some(id) {
+            let t = ((self.mouse().x() - bounds.min.x()) / bounds.size().x()).clamp(0.0, 1.0);
+            let new = start + t * (end - start);
+            changed = new != *value;
+            *value = new;
+        }
+
+        self.widget_bg(bounds);
+        let t = match end > start {
+            true => ((*value - start) / (end - start)).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        let fill = Bounds2D::with_size(bounds.min, vec2(t * bounds.size().x(), bounds.size().y()));
+        self.out.draw_rect_screen(L_UI, Rectangle::new(fill, RGBA::TRANSPARENT).with_fill(self.state.style.accent.with_opacity(0.5)));
+        self.text(bounds, &format!("{text}: {}", fmt(*value)), Align::Left);
+        changed
+    }
+
+    /// Line plot of `values` (oldest first), scaled to fit. Shows `text` and the latest value.
+    pub fn plot(&mut self, text: impl AsRef<str>, values: impl IntoIterator<Item = f32>) {
+        let values = values.into_iter().collect::<Vec<_>>();
+        let style = self.state.style;
+        let bounds = self.row(3.0 * style.text.line_height());
+        self.widget_bg(bounds);
+        let latest = values.last().map(|v| format!(": {v:.3}")).unwrap_or_default();
+        self.text(bounds, &format!("{}{latest}", text.as_ref()), Align::Left);
+
+        let (min, max) = values.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| (min.min(v), max.max(v)));
+        let range = if max > min { max - min } else { 1.0 };
+        let size = bounds.size();
+        let point = |i: usize, v: f32| bounds.min + vec2(i as f32 / (values.len() - 1) as f32 * size.x(), (1.0 - (v - min) / range) * size.y());
+        for (i, w) in values.windows(2).enumerate() {
+            self.out.draw_line_screen(L_UI_FG, Line::new(point(i, w[0]), point(i + 1, w[1])).with_color(style.accent));
+        }
+    }
+
+    fn inner_width(&self) -> f32 {
+        self.width - 2.0 * self.state.style.padding
+    }
+
+    /// Bounds of the next widget, of given height. Advances the cursor.
+    fn row(&mut self, height: f32) -> Bounds2Df {
+        let padding = self.state.style.padding;
+        let bounds = Bounds2D::with_size(self.pos + vec2(padding, self.cursor_y), vec2(self.inner_width(), height));
+        self.cursor_y += height + padding;
+        self.widgets += 1;
+        bounds
+    }
+
+    /// A row for a single line widget (button, checkbox, slider).
+    fn widget_row(&mut self) -> Bounds2Df {
+        let style = self.state.style;
+        self.row(style.text.line_height() + style.padding)
+    }
+
+    fn mouse(&self) -> vec2f {
+        self.inputs.mouse_position().as_f32()
+    }
+
+    fn hovered(&self, bounds: Bounds2Df) -> bool {
+        bounds.contains(self.mouse())
+    }
+
+    /// Mouse went down on `bounds` (the click is consumed).
+    fn clicked(&mut self, bounds: Bounds2Df) -> bool {
+        let clicked = self.hovered(bounds) && self.inputs.just_pressed(K_MOUSE1);
+        if clicked {
+            self.inputs.consume(K_MOUSE1);
+        }
+        clicked
+    }
+
+    fn widget_bg(&mut self, bounds: Bounds2Df) {
+        let style = self.state.style;
+        let fill = if self.hovered(bounds) { style.hover_bg } else { style.widget_bg };
+        self.out.draw_rect_screen(L_UI, Rectangle::new(bounds, RGBA::TRANSPARENT).with_fill(fill));
+    }
+
+    /// Single line of text, vertically centered in a widget row.
+    fn text(&mut self, bounds: Bounds2Df, text: &str, align: Align) {
+        let style = self.state.style;
+        let y = (bounds.size().y() - style.text.line_height()) / 2.0;
+        let bounds = Bounds2D::new(bounds.min + vec2(style.padding, y), bounds.max - vec2(style.padding, 0.0));
+        self.out.draw_text_box(L_UI, bounds, text, style.text.with_align(align));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// Mouse moved to `pos`, with button 1 going down (`Some(true)`), up (`Some(false)`) or unchanged.
+    fn mouse(inputs: &mut Inputs, pos: (i32, i32), button: Option<bool>) {
+        let mut events = vec![InputEvent::MouseMove { position: vec2(pos.0, pos.1) }];
+        if let Some(down) = button {
+            let direction = if down { KeyDir::Down } else { KeyDir::Up };
+            events.push(InputEvent::Key { button: K_MOUSE1, direction });
+        }
+        inputs.tick(&Keymap::default(), events.into_iter());
+    }
+
+    /// Panel at (0, 0), 100 wide: widget rows are 20 high (16 + 4 padding), starting at y = 4, every 24 pixels.
+    fn panel(ui: &mut UiState, inputs: &mut Inputs, f: impl FnOnce(&mut Ui)) -> Bounds2Df {
+        ui.panel(inputs, &mut Out::default(), (0, 0), 100.0, f)
+    }
+
+    #[gtest]
+    fn button_and_checkbox() {
+        let (mut ui, mut inputs) = (UiState::default(), Inputs::default());
+        let mut checked = false;
+        mouse(&mut inputs, (50, 10), Some(true));
+        let (mut a, mut b) = (false, false);
+        let bounds = panel(&mut ui, &mut inputs, |ui| {
+            a = ui.button("a");
+            b = ui.checkbox("b", &mut checked);
+        });
+        expect_that!((a, b, checked), eq((true, false, false)));
+        expect_that!(bounds, eq(Bounds2D::new(vec2(0.0, 0.0), vec2(100.0, 52.0))));
+
+        mouse(&mut inputs, (50, 30), Some(false));
+        mouse(&mut inputs, (50, 30), Some(true));
+        panel(&mut ui, &mut inputs, |ui| {
+            a = ui.button("a");
+            b = ui.checkbox("b", &mut checked);
+        });
+        expect_that!((a, b, checked), eq((false, true, true)));
+    }
+
+    #[gtest]
+    fn slider_drag() {
+        let (mut ui, mut inputs) = (UiState::default(), Inputs::default());
+        let mut value = 0.0;
+        let mut speed = 1;
+        let mut tick = |inputs: &mut Inputs, value: &mut f32, speed: &mut u32| {
+            panel(&mut ui, inputs, |ui| {
+                ui.slider("value", value, 0.0..=10.0);
+                ui.int_slider("speed", speed, 1..=5);
+            })
+        };
+        mouse(&mut inputs, (50, 10), Some(true));
+        tick(&mut inputs, &mut value, &mut speed);
+        expect_that!(value, eq(5.0)); // 👈 halfway the 92 pixel wide track
+        mouse(&mut inputs, (200, 40), None); // 👈 dragged past the end, over the other slider
+        tick(&mut inputs, &mut value, &mut speed);
+        expect_that!((value, speed), eq((10.0, 1)));
+        mouse(&mut inputs, (200, 40), Some(false));
+        tick(&mut inputs, &mut value, &mut speed);
+        mouse(&mut inputs, (95, 40), Some(true));
+        tick(&mut inputs, &mut value, &mut speed);
+        expect_that!((value, speed), eq((10.0, 5)));
+    }
+
+    #[gtest]
+    fn sliders_with_same_label() {
+        let (mut ui, mut inputs) = (UiState::default(), Inputs::default());
+        let mut values = [0.0, 0.0];
+        let mut tick = |inputs: &mut Inputs, values: &mut [f32; 2]| {
+            panel(&mut ui, inputs, |ui| values.iter_mut().for_each(|v| _ = ui.slider("stiffness", v, 0.0..=10.0)));
+        };
+        mouse(&mut inputs, (50, 34), Some(true)); // 👈 the second one
+        tick(&mut inputs, &mut values);
+        mouse(&mut inputs, (96, 34), None);
+        tick(&mut inputs, &mut values);
+        expect_that!(values, eq([0.0, 10.0]));
+    }
+
+    #[gtest]
+    fn panel_consumes_clicks() {
+        let (mut ui, mut inputs) = (UiState::default(), Inputs::default());
+        mouse(&mut inputs, (50, 50), Some(true));
+        panel(&mut ui, &mut inputs, |ui| ui.label("hello"));
+        expect_that!(inputs.just_pressed(K_MOUSE1), eq(true)); // 👈 below the panel
+        mouse(&mut inputs, (50, 10), Some(false));
+        mouse(&mut inputs, (50, 10), Some(true));
+        panel(&mut ui, &mut inputs, |ui| ui.label("hello"));
+        expect_that!(inputs.just_pressed(K_MOUSE1), eq(false));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use macroquad::miniquad::{BlendFactor, BlendState, BlendValue, Equation};
 use num_traits::AsPrimitive as _;
 
 /// Draw scenegraph via macroquad.
@@ -7,10 +8,43 @@ pub(crate) fn mq_draw(res: &mut Resources, out: &Out) {
 
     //mq::clear_background(mq::LIGHTGRAY);
 
+    // 👇 draw to an offscreen frame if post-processing is on, see `PostProcessing::apply`.
+    let target = res.post.target(out);
+    if let Some(target) = &target {
+        mq::set_camera(&mq_screen_camera(target.texture.size(), Some(target.clone())));
+        mq::clear_background(mq::BLACK);
+    }
+
+    // 👇 reused for all sprites, see `draw_quad`.
+    let mut quad = mq::Mesh {
+        vertices: Vec::with_capacity(4),
+        indices: vec![0, 1, 2, 0, 2, 3],
+        texture: None,
+    };
+
     // Draw layers starting from 0 for correct Z-ordering.
-    for Layer { sprites, lines, rectangles } in &out.layers {
+    for (
+        Layer {
+            sprites,
+            lines,
+            rectangles,
+            circles,
+            polygons,
+            paths,
+            texts,
+        },
+        world,
+    ) in out.iter_layers()
+    {
+        match (world, &target) {
+            (true, _) => mq::set_camera(&mq_camera(&out.camera, out.viewport_size, target.clone())),
+            (false, Some(target)) => mq::set_camera(&mq_screen_camera(target.texture.size(), Some(target.clone()))),
+            (false, None) => mq::set_default_camera(),
+        }
+
         // ▭ rectangles
         for rect in rectangles {
+            res.blending.set(rect.blend);
             if rect.fill != RGBA::TRANSPARENT {
                 mq::draw_rectangle(
                     //_
@@ -36,63 +70,238 @@ pub(crate) fn mq_draw(res: &mut Resources, out: &Out) {
             }
         }
 
+        // ⬠ polygons
+        for poly in polygons {
+            res.blending.set(poly.blend);
+            let points = poly.points.iter().map(|p| mq::Vec2::new(p.x().as_(), p.y().as_())).collect::<Vec<_>>();
+            if poly.fill != RGBA::TRANSPARENT {
+                // 👇 triangle fan: only correct for convex polygons.
+                for i in 1..points.len().saturating_sub(1) {
+                    mq::draw_triangle(points[0], points[i], points[i + 1], mq_color(poly.fill));
+                }
+            }
+            if poly.stroke != RGBA::TRANSPARENT {
+                for (i, a) in points.iter().enumerate() {
+                    let b = points[(i + 1) % points.len()];
+                    mq::draw_line(a.x, a.y, b.x, b.y, poly.width.as_(), mq_color(poly.stroke));
+                }
+            }
+        }
+
+        // ● circles
+        for circle in circles {
+            res.blending.set(circle.blend);
+            let (x, y) = circle.center.as_f32().into();
+            if circle.fill != RGBA::TRANSPARENT {
+                mq::draw_circle(x, y, circle.radius, mq_color(circle.fill));
+            }
+            if circle.stroke != RGBA::TRANSPARENT {
+                mq::draw_circle_lines(x, y, circle.radius, circle.width.as_(), mq_color(circle.stroke));
+            }
+        }
+
         // 🦀 sprites
         for cmd in sprites {
-            if let Some(bitmap) = res.get(&cmd.sprite) {
-                let dst_size = match cmd.dst_size {
-                    None => vec2(bitmap.width(), bitmap.height()),
-                    Some(dst_size) => dst_size.map(|v| v.get().as_()),
-                };
-
-                let source = match cmd.src_pos {
-                    None => None,
-                    Some(src) => Some(mq::Rect::new(src.x().as_(), src.y().as_(), dst_size.x(), dst_size.y())),
-                };
-
-                mq::draw_texture_ex(
-                    bitmap,
-                    cmd.pos.x().as_(),
-                    cmd.pos.y().as_(),
-                    mq::WHITE,
-                    mq::DrawTextureParams {
-                        dest_size: Some(mq::Vec2::new(dst_size.x(), dst_size.y())),
-                        source,
-                        rotation: cmd.rot,
-                        ..Default::default()
-                    },
-                );
-
-                //ctx.draw_image_with_image_bitmap_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
-                //    bitmap,
-                //    0.0,                   // source x
-                //    0.0,                   // source y
-                //    bitmap.width().as_(),  // source width
-                //    bitmap.height().as_(), // source height
-                //    cmd.pos.x().as_(),     // dest x
-                //    cmd.pos.y().as_(),     // dest y
-                //    dst_size.x().as_(),    // dest width
-                //    dst_size.y().as_(),    // dest height
-                //)
-                //.expect("draw");
+            res.blending.set(cmd.blend);
+            if let Some((bitmap, src)) = res.source(cmd) {
+                draw_quad(&mut quad, bitmap, cmd, src);
             }
         }
 
         // ╱ lines
         for line in lines {
+            res.blending.set(line.blend);
             mq::draw_line(
                 //_
-                line.start.x().as_(),
-                line.start.y().as_(),
-                line.end.x().as_(),
-                line.end.y().as_(),
+                line.start.x(),
+                line.start.y(),
+                line.end.x(),
+                line.end.y(),
                 line.width.as_(),
                 mq_color(line.color),
             );
         }
+
+        // 〰 paths (curves flattened), round joins.
+        for path in paths {
+            res.blending.set(path.blend);
+            let color = mq_color(path.color);
+            let width: f32 = path.width.as_();
+            let points = path.flatten();
+            for [a, b] in points.array_windows() {
+                mq::draw_line(a.x(), a.y(), b.x(), b.y(), width, color);
+            }
+            if width > 2.0 {
+                for p in &points {
+                    mq::draw_circle(p.x(), p.y(), width / 2.0, color);
+                }
+            }
+        }
+
+        // 🔤 text
+        for text in texts {
+            res.blending.set(text.blend);
+            let size = text.font_size();
+            let baseline = mq::measure_text(&text.text, None, size as u16, 1.0).offset_y;
+            mq::draw_text(&text.text, text.pos.x(), text.pos.y() + baseline, size, mq_color(text.color));
+        }
     }
+    res.blending.set(Blend::Normal);
+    mq::set_default_camera();
+    res.post.apply(out);
+}
+
+/// Materials (pipelines) for the non-default blend modes.
+pub(crate) struct Blending {
+    add: mq::Material,
+    multiply: mq::Material,
+}
+
+impl Blending {
+    pub fn new() -> Self {
+        // 👇 additive: dst + src * alpha
+        let add = blend_material(FRAGMENT, BlendState::new(Equation::Add, BlendFactor::Value(BlendValue::SourceAlpha), BlendFactor::One));
+        // 👇 multiply: dst * src, the fragment shader fades src to white with alpha (like Canvas2D "multiply").
+        let multiply = blend_material(FRAGMENT_MULTIPLY, BlendState::new(Equation::Add, BlendFactor::Zero, BlendFactor::Value(BlendValue::SourceColor)));
+        Self { add, multiply }
+    }
+
+    pub fn set(&self, blend: Blend) {
+        match blend {
+            Blend::Normal => mq::gl_use_default_material(),
+            Blend::Add => mq::gl_use_material(&self.add),
+            Blend::Multiply => mq::gl_use_material(&self.multiply),
+        }
+    }
+}
+
+fn blend_material(fragment: &str, color_blend: BlendState) -> mq::Material {
+    let params = mq::MaterialParams {
+        pipeline_params: mq::PipelineParams {
+            color_blend: Some(color_blend),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    mq::load_material(mq::ShaderSource::Glsl { vertex: VERTEX, fragment }, params).expect("blend material")
+}
+
+/// Same as macroquad's default shader.
+pub(crate) const VERTEX: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec2 uv;
+varying lowp vec4 color;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+}"#;
+
+const FRAGMENT: &str = r#"#version 100
+varying lowp vec4 color;
+varying lowp vec2 uv;
+
+uniform sampler2D Texture;
+
+void main() {
+    gl_FragColor = color * texture2D(Texture, uv);
+}"#;
+
+const FRAGMENT_MULTIPLY: &str = r#"#version 100
+varying lowp vec4 color;
+varying lowp vec2 uv;
+
+uniform sampler2D Texture;
+
+void main() {
+    lowp vec4 c = color * texture2D(Texture, uv);
+    gl_FragColor = mix(vec4(1.0), c, c.a);
+}"#;
+
+/// Macroquad camera equivalent to `Camera::world_to_screen`.
+/// Draws to the screen, or to `render_target` (same orientation).
+fn mq_camera(camera: &Camera, viewport: vec2u, render_target: Option<mq::RenderTarget>) -> mq::Camera2D {
+    mq::Camera2D {
+        target: mq::Vec2::new(camera.pos.x(), camera.pos.y()),
+        // 👇 macroquad zooms to clip space (-1..1), not pixels.
+        zoom: mq::Vec2::new(2.0 * camera.zoom / viewport.x() as f32, 2.0 * camera.zoom / viewport.y() as f32),
+        rotation: camera.rot.to_degrees(),
+        render_target,
+        ..Default::default()
+    }
+}
+
+/// Like `set_default_camera` (pixel coordinates), but for drawing to a render target.
+/// (`Camera2D::from_display_rect` would render upside down).
+pub(crate) fn mq_screen_camera(size: mq::Vec2, render_target: Option<mq::RenderTarget>) -> mq::Camera2D {
+    mq::Camera2D {
+        target: size / 2.0,
+        zoom: 2.0 / size,
+        render_target,
+        ..Default::default()
+    }
+}
+
+/// Draw a sprite: the `src` rectangle of a texture (pixels), see `quad_vertices`.
+fn draw_quad(quad: &mut mq::Mesh, texture: &mq::Texture2D, cmd: &DrawSprite, src: AtlasRect) {
+    let tex_size = texture.size();
+    quad.vertices.clear();
+    quad.vertices.extend(quad_vertices(cmd, src, vec2(tex_size.x, tex_size.y)));
+    quad.texture = Some(texture.clone());
+    mq::draw_mesh(quad);
+}
+
+/// Vertices (top-left, top-right, bottom-right, bottom-left of the source) of a sprite quad:
+/// placed at `DrawSprite::corners`, textured with `src` of a texture of size `tex_size`, colored by `DrawSprite::color`.
+fn quad_vertices(cmd: &DrawSprite, src: AtlasRect, tex_size: vec2f) -> [mq::Vertex; 4] {
+    let size = match cmd.dst_size {
+        None => src.size.as_f32(),
+        Some(dst_size) => dst_size.map(|v| v.get().as_()),
+    };
+    let (u0, v0) = (src.pos.x() as f32 / tex_size.x(), src.pos.y() as f32 / tex_size.y());
+    let (u1, v1) = ((src.pos.x() + src.size.x()) as f32 / tex_size.x(), (src.pos.y() + src.size.y()) as f32 / tex_size.y());
+    let color = mq_color(cmd.color());
+    let (corners, uvs) = (cmd.corners(size), [(u0, v0), (u1, v0), (u1, v1), (u0, v1)]);
+    std::array::from_fn(|i| mq::Vertex::new(corners[i].x(), corners[i].y(), 0.0, uvs[i].0, uvs[i].1, color))
 }
 
 fn mq_color(c: RGBA) -> mq::Color {
     let RGBA([r, g, b, a]) = c;
     mq::Color::from_rgba(r, g, b, a)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// A 4x2 sprite at (10, 20). 👉 Geometry cases are in `DrawSprite`'s `corners` test.
+    fn sprite() -> DrawSprite {
+        DrawSprite::at_pos(sprite!("crab"), (10, 20))
+    }
+
+    #[gtest]
+    fn sprite_quad() {
+        let src = AtlasRect { pos: vec2(8, 4), size: vec2(4, 2) };
+        let cmd = sprite().with_flip(true, false).with_scale(1.5).with_pivot((0.25, 1.0)).with_rot(0.7);
+        let got = quad_vertices(&cmd, src, vec2(16.0, 8.0)).map(|v| vec2(v.position.x, v.position.y));
+        expect_that!(got, eq(cmd.corners(vec2(4.0, 2.0))));
+    }
+
+    #[gtest]
+    fn sprite_quad_texture_and_color() {
+        let src = AtlasRect { pos: vec2(8, 4), size: vec2(4, 2) };
+        let cmd = sprite().with_flip(true, true).with_tint(RGBA::RED).with_opacity(0.5);
+        let vertices = quad_vertices(&cmd, src, vec2(16.0, 8.0));
+        let uv = vertices.map(|v| (v.uv.x, v.uv.y));
+        expect_that!(uv, eq([(0.5, 0.5), (0.75, 0.5), (0.75, 0.75), (0.5, 0.75)])); // 👈 flipping moves vertices, not texture coordinates
+        expect_that!(vertices[0].color, eq([255, 0, 0, 128]));
+    }
+}
@@ -1,23 +1,137 @@
-use std::str::FromStr as _;
-
 use crate::*;
-use fixed_str::*;
+use macroquad::miniquad::{self, EventHandler, KeyMods};
+
+/// Receives keyboard and text events from macroquad, including OS key repeats
+/// (which `is_key_pressed` & co. hide).
+pub(crate) struct InputCapture {
+    subscriber: usize,
+    events: VecDeque<InputEvent>,
+    gamepads: Gamepads,
+}
+
+/// 🎮 Gamepad state as of the last poll, to turn it into events (macroquad has no gamepad API).
+/// All connected gamepads act as one, like in the web shell.
+struct Gamepads {
+    /// `None` if gamepads are not supported on this platform.
+    gilrs: Option<gilrs::Gilrs>,
+    buttons: [bool; GAMEPAD_BUTTONS.len()],
+    axes: [f32; GamepadAxis::ALL.len()],
+}
+
+/// `gilrs` buttons, in the order of `GAMEPAD_BUTTONS`.
+const GILRS_BUTTONS: [gilrs::Button; GAMEPAD_BUTTONS.len()] = {
+    use gilrs::Button::*;
+    [
+        South,
+        East,
+        West,
+        North,
+        LeftTrigger,
+        RightTrigger,
+        LeftTrigger2,
+        RightTrigger2,
+        Select,
+        Start,
+        LeftThumb,
+        RightThumb,
+        DPadUp,
+        DPadDown,
+        DPadLeft,
+        DPadRight,
+        Mode,
+    ]
+};
+
+/// `gilrs` axes, in the order of `GamepadAxis::ALL`.
+const GILRS_AXES: [gilrs::Axis; GamepadAxis::ALL.len()] = [gilrs::Axis::LeftStickX, gilrs::Axis::LeftStickY, gilrs::Axis::RightStickX, gilrs::Axis::RightStickY];
+
+impl InputCapture {
+    pub(crate) fn new() -> Self {
+        Self {
+            subscriber: mq::utils::register_input_subscriber(),
+            events: VecDeque::new(),
+            gamepads: Gamepads::new(),
+        }
+    }
 
-pub fn capture_input_events(dst: &mut VecDeque<InputEvent>) {
-    while let Some(chr) = mq::get_char_pressed() {
-        dst.push_back(InputEvent::InputCharacter(chr));
+    /// Push input events since the last frame to `dst`.
+    pub(crate) fn capture(&mut self, dst: &mut VecDeque<InputEvent>) {
+        mq::utils::repeat_all_miniquad_input(self, self.subscriber);
+        dst.append(&mut self.events);
+        capture_mouse_and_touch(dst);
+        self.gamepads.poll(dst);
     }
-    for code in mq::get_keys_pressed() {
-        if let Ok(button) = Str16::from_str(&format!("{code:?}").to_ascii_lowercase()) {
-            dst.push_back(InputEvent::Key { button: Button(button), direction: KeyDir::Down });
+}
+
+impl EventHandler for InputCapture {
+    fn update(&mut self) {}
+
+    fn draw(&mut self) {}
+
+    fn char_event(&mut self, chr: char, keymods: KeyMods, _repeat: bool) {
+        if !chr.is_control() && !keymods.ctrl {
+            self.events.push_back(InputEvent::Text(chr.into()));
         }
     }
-    for code in mq::get_keys_released() {
-        if let Ok(button) = Str16::from_str(&format!("{code:?}").to_ascii_lowercase()) {
-            dst.push_back(InputEvent::Key { button: Button(button), direction: KeyDir::Up });
+
+    fn key_down_event(&mut self, code: mq::KeyCode, keymods: KeyMods, _repeat: bool) {
+        if let Some(button) = key_name(code) {
+            self.events.push_back(InputEvent::Key { button, direction: KeyDir::Down });
+        }
+        // 📋 paste
+        if code == mq::KeyCode::V
+            && keymods.ctrl
+            && let Some(text) = miniquad::window::clipboard_get()
+        {
+            self.events.push_back(InputEvent::Text(text));
         }
     }
-    for (mq_button, button) in [(mq::MouseButton::Left, K_MOUSE1), (mq::MouseButton::Right, K_MOUSE2)] {
+
+    fn key_up_event(&mut self, code: mq::KeyCode, _keymods: KeyMods) {
+        if let Some(button) = key_name(code) {
+            self.events.push_back(InputEvent::Key { button, direction: KeyDir::Up });
+        }
+    }
+}
+
+impl Gamepads {
+    fn new() -> Self {
+        let gilrs = gilrs::Gilrs::new().map_err(|e| log::warn!("no gamepads: {e}")).ok();
+        Self {
+            gilrs,
+            buttons: Default::default(),
+            axes: Default::default(),
+        }
+    }
+
+    fn poll(&mut self, dst: &mut VecDeque<InputEvent>) {
+        let Some(gilrs) = &mut self.gilrs else { return };
+        while gilrs.next_event().is_some() {} // 👈 updates the gamepad states
+        let pads = gilrs.gamepads().map(|(_, pad)| pad).filter(|pad| pad.is_connected()).collect::<Vec<_>>();
+
+        for (i, button) in GAMEPAD_BUTTONS.into_iter().enumerate() {
+            let down = pads.iter().any(|pad| pad.is_pressed(GILRS_BUTTONS[i]));
+            if down != self.buttons[i] {
+                self.buttons[i] = down;
+                let direction = if down { KeyDir::Down } else { KeyDir::Up };
+                dst.push_back(InputEvent::Key { button, direction });
+            }
+        }
+
+        for (i, axis) in GamepadAxis::ALL.into_iter().enumerate() {
+            let values = pads.iter().map(|pad| pad.value(GILRS_AXES[i]));
+            let value = values.fold(0.0, |a: f32, b| if b.abs() > a.abs() { b } else { a });
+            let value = if matches!(axis, GamepadAxis::LeftY | GamepadAxis::RightY) { -value } else { value }; // 👈 gilrs: up is positive
+            if value != self.axes[i] {
+                self.axes[i] = value;
+                dst.push_back(InputEvent::GamepadAxis { axis, value });
+            }
+        }
+    }
+}
+
+fn capture_mouse_and_touch(dst: &mut VecDeque<InputEvent>) {
+    for (mq_button, button) in [(mq::MouseButton::Left, K_MOUSE1), (mq::MouseButton::Right, K_MOUSE2), (mq::MouseButton::Middle, K_MOUSE3)] {
         if mq::is_mouse_button_pressed(mq_button) {
             dst.push_back(InputEvent::Key { button, direction: KeyDir::Down });
         }
@@ -26,6 +140,23 @@ pub fn capture_input_events(dst: &mut VecDeque<InputEvent>) {
         }
     }
 
+    for touch in mq::touches() {
+        let phase = match touch.phase {
+            mq::TouchPhase::Started => TouchPhase::Start,
+            mq::TouchPhase::Moved => TouchPhase::Move,
+            mq::TouchPhase::Ended => TouchPhase::End,
+            mq::TouchPhase::Cancelled => TouchPhase::Cancel,
+            mq::TouchPhase::Stationary => continue,
+        };
+        let position = vec2(touch.position.x, touch.position.y).as_i32();
+        dst.push_back(InputEvent::Touch { id: touch.id, phase, position });
+    }
+
+    let (_, wheel) = mq::mouse_wheel();
+    if wheel != 0.0 {
+        dst.push_back(InputEvent::MouseWheel { delta: wheel });
+    }
+
     dst.push_back(InputEvent::MouseMove {
         position: vec2::from(mq::mouse_position()).as_i32(),
     });
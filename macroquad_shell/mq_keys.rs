@@ -0,0 +1,117 @@
+//! ⌨️ Macroquad key codes -> canonical key names (`shell_api::KEY_NAMES`).
+use crate::*;
+use mq::KeyCode as K;
+use proc_macros::*;
+
+/// Canonical name of a macroquad key, if it has one.
+pub(crate) fn key_name(code: K) -> Option<Button> {
+    Some(match code {
+        K::A => button!("a"),
+        K::B => button!("b"),
+        K::C => button!("c"),
+        K::D => button!("d"),
+        K::E => button!("e"),
+        K::F => button!("f"),
+        K::G => button!("g"),
+        K::H => button!("h"),
+        K::I => button!("i"),
+        K::J => button!("j"),
+        K::K => button!("k"),
+        K::L => button!("l"),
+        K::M => button!("m"),
+        K::N => button!("n"),
+        K::O => button!("o"),
+        K::P => button!("p"),
+        K::Q => button!("q"),
+        K::R => button!("r"),
+        K::S => button!("s"),
+        K::T => button!("t"),
+        K::U => button!("u"),
+        K::V => button!("v"),
+        K::W => button!("w"),
+        K::X => button!("x"),
+        K::Y => button!("y"),
+        K::Z => button!("z"),
+        K::Key0 => button!("0"),
+        K::Key1 => button!("1"),
+        K::Key2 => button!("2"),
+        K::Key3 => button!("3"),
+        K::Key4 => button!("4"),
+        K::Key5 => button!("5"),
+        K::Key6 => button!("6"),
+        K::Key7 => button!("7"),
+        K::Key8 => button!("8"),
+        K::Key9 => button!("9"),
+        K::F1 => button!("f1"),
+        K::F2 => button!("f2"),
+        K::F3 => button!("f3"),
+        K::F4 => button!("f4"),
+        K::F5 => button!("f5"),
+        K::F6 => button!("f6"),
+        K::F7 => button!("f7"),
+        K::F8 => button!("f8"),
+        K::F9 => button!("f9"),
+        K::F10 => button!("f10"),
+        K::F11 => button!("f11"),
+        K::F12 => button!("f12"),
+        K::GraveAccent => button!("backquote"),
+        K::Minus => button!("minus"),
+        K::Equal => button!("equal"),
+        K::LeftBracket => button!("bracketleft"),
+        K::RightBracket => button!("bracketright"),
+        K::Backslash => button!("backslash"),
+        K::Semicolon => button!("semicolon"),
+        K::Apostrophe => button!("quote"),
+        K::Comma => button!("comma"),
+        K::Period => button!("period"),
+        K::Slash => button!("slash"),
+        K::World1 | K::World2 => button!("intlbackslash"),
+        K::Space => K_SPACE,
+        K::Enter => K_ENTER,
+        K::Escape | K::Back => K_ESC, // 👈 Android back button
+        K::Backspace => K_BACKSPACE,
+        K::Tab => K_TAB,
+        K::CapsLock => button!("capslock"),
+        K::Insert => button!("insert"),
+        K::Delete => K_DELETE,
+        K::Home => K_HOME,
+        K::End => K_END,
+        K::PageUp => K_PAGE_UP,
+        K::PageDown => K_PAGE_DOWN,
+        K::Left => K_ARROW_LEFT,
+        K::Up => K_ARROW_UP,
+        K::Down => K_ARROW_DOWN,
+        K::Right => K_ARROW_RIGHT,
+        K::LeftShift => button!("shiftleft"),
+        K::RightShift => button!("shiftright"),
+        K::LeftControl => button!("controlleft"),
+        K::RightControl => button!("controlright"),
+        K::LeftAlt => button!("altleft"),
+        K::RightAlt => button!("altright"),
+        K::LeftSuper => button!("metaleft"),
+        K::RightSuper => button!("metaright"),
+        K::Menu => button!("contextmenu"),
+        K::PrintScreen => button!("printscreen"),
+        K::ScrollLock => button!("scrolllock"),
+        K::Pause => button!("pause"),
+        K::NumLock => button!("numlock"),
+        K::Kp0 => button!("numpad0"),
+        K::Kp1 => button!("numpad1"),
+        K::Kp2 => button!("numpad2"),
+        K::Kp3 => button!("numpad3"),
+        K::Kp4 => button!("numpad4"),
+        K::Kp5 => button!("numpad5"),
+        K::Kp6 => button!("numpad6"),
+        K::Kp7 => button!("numpad7"),
+        K::Kp8 => button!("numpad8"),
+        K::Kp9 => button!("numpad9"),
+        K::KpDecimal => button!("numpaddecimal"),
+        K::KpDivide => button!("numpaddivide"),
+        K::KpMultiply => button!("numpadmultiply"),
+        K::KpSubtract => button!("numpadsubtract"),
+        K::KpAdd => button!("numpadadd"),
+        K::KpEnter => button!("numpadenter"),
+        K::KpEqual => button!("numpadequal"),
+        _ => return None, // F13.., Unknown
+    })
+}
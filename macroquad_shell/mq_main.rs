@@ -1,9 +1,15 @@
 mod mq_draw;
 mod mq_inputs;
+mod mq_keys;
+mod mq_postprocessing;
+mod mq_requests;
 mod mq_resources;
 mod mq_storage;
 use mq_draw::*;
 use mq_inputs::*;
+use mq_keys::*;
+use mq_postprocessing::*;
+use mq_requests::*;
 use mq_resources::*;
 use mq_storage::*;
 
@@ -29,8 +35,10 @@ pub async fn mq_main<G: GameCore>() {
     let fallback = mq::Texture2D::from_image(&fallback_bitmap((0, 0, 255), vec2(24, 24) /*TODO*/));
     let mut res = Resources::new(fallback);
     let mut input_events = VecDeque::new();
+    let mut input_capture = InputCapture::new();
 
-    let mut g = match load_game() {
+    let mut storage = app_storage();
+    let mut g = match load_game(&mut storage, APP_KEY) {
         Some(state) => {
             log::info!("game loaded");
             state
@@ -43,6 +51,7 @@ pub async fn mq_main<G: GameCore>() {
 
     let mut out = Out::default();
     mq::prevent_quit();
+    mq::simulate_mouse_with_touch(false); // 👈 `Inputs` does that, with pinch and pan on top
 
     // ⚠️ 🕣 Time reference does not use UNIX Epoch and gets reset on program restart.
     // This mirrors Javascript and forces robust timekeeping logic to get excercised even inside macroquad.
@@ -51,7 +60,9 @@ pub async fn mq_main<G: GameCore>() {
     loop {
         if mq::is_quit_requested() {
             log::info!("quitting...");
-            // save_game(&g); <<< TODO
+            if let Err(e) = save_game(&mut storage, APP_KEY, &g) {
+                log::error!("save on quit: {e:#}");
+            }
             return; // 👈 exit
         }
 
@@ -59,10 +70,11 @@ pub async fn mq_main<G: GameCore>() {
         out.clear();
 
         out.viewport_size = vec2(mq::screen_width(), mq::screen_height()).as_u32();
-        capture_input_events(&mut input_events);
+        input_capture.capture(&mut input_events);
 
         g.tick(micros, input_events.drain(..), &mut out);
         mq_draw(&mut res, &out);
+        serve_requests(&mut storage, &mut g, &mut out.requests, &mut input_events);
 
         //if !g.paused {
         //    //println!("{ANSI_CLEAR}{}", &out.debug);
@@ -70,9 +82,6 @@ pub async fn mq_main<G: GameCore>() {
         //        println!("{}", &out.debug);
         //    }
         //}
-        if mq::is_key_pressed(mq::KeyCode::S) && mq::is_key_down(mq::KeyCode::LeftSuper) {
-            save_game(&g);
-        }
         //if mq::is_key_pressed(mq::KeyCode::Space) {
         //    g.paused = true;
         //    g.commands.push_back("tick".into());
@@ -0,0 +1,130 @@
+//! Post-processing (`Out::bloom`, `Out::vignette`), same look as the web shell's.
+//! When enabled, `mq_draw` renders the scenegraph to a render target,
+//! which is then copied to the screen through shaders.
+use crate::*;
+use macroquad::miniquad::{BlendFactor, BlendState, BlendValue, Equation, UniformType};
+
+/// Bloom is blurred at this fraction of the screen resolution (cheaper, and a wider glow).
+const BLOOM_DOWNSCALE: u32 = 4;
+
+pub(crate) struct PostProcessing {
+    /// Full-size frame, drawn by `mq_draw` when any effect is on.
+    frame: Option<mq::RenderTarget>,
+    /// Down-scaled frame, blurred for bloom.
+    small: Option<mq::RenderTarget>,
+    blur: mq::Material,
+    vignette: mq::Material,
+}
+
+impl PostProcessing {
+    pub fn new() -> Self {
+        // 👇 bloom: added to the frame.
+        let blur = load_material(BLUR, "Texel", BlendState::new(Equation::Add, BlendFactor::Value(BlendValue::SourceAlpha), BlendFactor::One));
+        // 👇 vignette: multiplied with the frame.
+        let vignette = load_material(VIGNETTE, "Viewport", BlendState::new(Equation::Add, BlendFactor::Zero, BlendFactor::Value(BlendValue::SourceColor)));
+        Self { frame: None, small: None, blur, vignette }
+    }
+
+    /// Render target to draw this frame to, if it needs any effects. Follows the viewport size.
+    pub fn target(&mut self, out: &Out) -> Option<mq::RenderTarget> {
+        if !(out.bloom || out.vignette) {
+            return None;
+        }
+
+        let size = out.viewport_size.map(|v| v.max(1));
+        if self.frame.as_ref().is_none_or(|rt| rt.texture.size() != mq::Vec2::new(size.x() as f32, size.y() as f32)) {
+            let frame = mq::render_target(size.x(), size.y());
+            frame.texture.set_filter(mq::FilterMode::Nearest);
+            let small = mq::render_target(u32::max(1, size.x() / BLOOM_DOWNSCALE), u32::max(1, size.y() / BLOOM_DOWNSCALE));
+            small.texture.set_filter(mq::FilterMode::Linear);
+            (self.frame, self.small) = (Some(frame), Some(small));
+        }
+        self.frame.clone()
+    }
+
+    /// Copy the frame (drawn to `target()`) to the screen, applying the effects.
+    pub fn apply(&self, out: &Out) {
+        let (Some(frame), Some(small)) = (&self.frame, &self.small) else { return };
+        if !(out.bloom || out.vignette) {
+            return;
+        }
+        let screen = mq::Vec2::new(out.viewport_size.x() as f32, out.viewport_size.y() as f32);
+
+        if out.bloom {
+            let small_size = small.texture.size();
+            mq::set_camera(&mq_screen_camera(small_size, Some(small.clone())));
+            mq::clear_background(mq::BLACK);
+            draw_fullscreen(&frame.texture, small_size);
+        }
+
+        mq::set_default_camera();
+        draw_fullscreen(&frame.texture, screen);
+
+        if out.bloom {
+            let texel = [1.0 / small.texture.width(), 1.0 / small.texture.height()];
+            mq::gl_use_material(&self.blur);
+            self.blur.set_uniform("Texel", texel);
+            draw_fullscreen(&small.texture, screen);
+        }
+
+        if out.vignette {
+            mq::gl_use_material(&self.vignette);
+            self.vignette.set_uniform("Viewport", [screen.x, screen.y]);
+            mq::draw_rectangle(0.0, 0.0, screen.x, screen.y, mq::WHITE);
+        }
+
+        mq::gl_use_default_material();
+    }
+}
+
+fn draw_fullscreen(texture: &mq::Texture2D, size: mq::Vec2) {
+    let params = mq::DrawTextureParams { dest_size: Some(size), ..Default::default() };
+    mq::draw_texture_ex(texture, 0.0, 0.0, mq::WHITE, params);
+}
+
+fn load_material(fragment: &str, uniform: &str, color_blend: BlendState) -> mq::Material {
+    let params = mq::MaterialParams {
+        pipeline_params: mq::PipelineParams {
+            color_blend: Some(color_blend),
+            ..Default::default()
+        },
+        uniforms: vec![mq::UniformDesc::new(uniform, UniformType::Float2)],
+        ..Default::default()
+    };
+    mq::load_material(mq::ShaderSource::Glsl { vertex: VERTEX, fragment }, params).expect("post-processing material")
+}
+
+/// 5x5 tent filter, `Texel` = 1 / texture size.
+const BLUR: &str = r#"#version 100
+precision mediump float;
+varying vec4 color;
+varying vec2 uv;
+
+uniform sampler2D Texture;
+uniform vec2 Texel;
+
+void main() {
+    vec4 sum = vec4(0.0);
+    for (int x = -2; x <= 2; x++) {
+        for (int y = -2; y <= 2; y++) {
+            float w = (3.0 - abs(float(x))) * (3.0 - abs(float(y)));
+            sum += w * texture2D(Texture, uv + vec2(float(x), float(y)) * Texel);
+        }
+    }
+    gl_FragColor = color * sum / 81.0;
+}"#;
+
+/// Same gradient as the web shell: no change up to 66% of the half-diagonal, slightly darker at 94%.
+const VIGNETTE: &str = r#"#version 100
+precision mediump float;
+varying vec4 color;
+varying vec2 uv;
+
+uniform vec2 Viewport;
+
+void main() {
+    vec2 p = (uv - 0.5) * Viewport;
+    float d = length(p) / length(0.5 * Viewport);
+    vec3 edge = vec3(244.0, 245.0, 247.0) / 255.0;
+    gl_FragColor = vec4(mix(vec3(1.0), edge, clamp((d - 0.66) / (0.94 - 0.66), 0.0, 1.0)), 1.0);
+}"#;
@@ -0,0 +1,64 @@
+//! Serve the game's `ShellRequest`s (file I/O, save slots, game data).
+use crate::*;
+
+/// Handle requests issued during the last tick.
+/// To be called after drawing (save thumbnails are taken from the screen).
+/// Results are queued as input events for the next tick.
+pub(crate) fn serve_requests<G: GameCore>(storage: &mut AppStorage, g: &mut G, requests: &mut Vec<ShellRequest>, input_events: &mut VecDeque<InputEvent>) {
+    for req in requests.drain(..) {
+        match req {
+            ShellRequest::ReadFile { path } | ShellRequest::PickFile { path } => {
+                // 👇 plain files, not compressed
+                let result = storage.0.load_bytes(&path).map_err(|e| format!("{e:#}"));
+                input_events.push_back(InputEvent::FileLoaded { path, result });
+            }
+            ShellRequest::WriteFile { path, data } => {
+                let msg = match storage.0.save_bytes(&path, &data) {
+                    Ok(()) => format!("wrote {STORAGE_DIR}/{path}"),
+                    Err(e) => format!("write {path}: {e:#}"),
+                };
+                input_events.push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::Save { slot, tick } => {
+                let meta = SaveMeta {
+                    name: slot.clone(),
+                    unix_secs: unix_secs(),
+                    tick,
+                    thumbnail: screen_thumbnail(),
+                };
+                let msg = match save_slot(storage, g, &meta) {
+                    Ok(()) => format!("saved {slot:?}"),
+                    Err(e) => format!("save {slot:?}: {e:#}"),
+                };
+                input_events.push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::Load { slot } => {
+                let msg = match load_slot(storage, &slot) {
+                    Ok(loaded) => {
+                        g.load(loaded);
+                        format!("loaded {slot:?}")
+                    }
+                    Err(e) => format!("{e:#}"),
+                };
+                input_events.push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::ListSaves => {
+                let msg = match list_saves(storage) {
+                    Ok(metas) => format_save_list(metas, unix_secs()),
+                    Err(e) => format!("list saves: {e:#}"),
+                };
+                input_events.push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::Store { key, data } => {
+                if let Err(e) = data_key(&key).and_then(|k| storage.save_bytes(&k, &data)) {
+                    input_events.push_back(InputEvent::ShellMessage(format!("store {key:?}: {e:#}")));
+                }
+            }
+            ShellRequest::Fetch { key } => {
+                let result = data_key(&key).and_then(|k| storage.load_bytes(&k)).map_err(|e| format!("{e:#}"));
+                input_events.push_back(InputEvent::Fetched { key, result });
+            }
+            ShellRequest::SetClipboard { text } => macroquad::miniquad::window::clipboard_set(&text),
+        }
+    }
+}
@@ -6,8 +6,14 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 use vector::*; // NOTE: macroquad `vec2` conflict
 
+/// Sprites, texture atlas, etc. are loaded from here.
+pub(crate) const ASSETS_DIR: &str = "assets";
+
 /// Resource loader + cache.
 pub struct Resources {
+    /// All sprites in one texture (see `atlas_builder`), if available.
+    atlas: Option<(mq::Texture2D, AtlasIndex)>,
+
     /// Loaded sprites, or red square for errored.
     cache: HashMap<Sprite, mq::Texture2D>,
 
@@ -16,23 +22,38 @@ pub struct Resources {
 
     /// Replacement sprite to show while loading (debug only).
     while_loading: mq::Texture2D,
+
+    /// Blend mode materials, see `Blend`.
+    pub(crate) blending: Blending,
+
+    /// Bloom, vignette.
+    pub(crate) post: PostProcessing,
 }
 
 impl Resources {
     pub fn new(while_loading: mq::Texture2D) -> Self {
         Self {
+            atlas: load_atlas(),
             cache: HashMap::default(),
             pending: HashMap::default(),
             while_loading,
+            blending: Blending::new(),
+            post: PostProcessing::new(),
         }
     }
 
-    /// Get bitmap for sprite.
+    /// Get bitmap for sprite, and the sprite's region in it (the atlas, or the whole bitmap).
     /// Still loading => returns `None`: can't draw yet, but will succeed soon
     /// Not found => replacement image
-    pub fn get(&mut self, sprite: &Sprite) -> Option<&mq::Texture2D> {
+    pub fn get(&mut self, sprite: &Sprite) -> Option<(&mq::Texture2D, AtlasRect)> {
+        if let Some((atlas, index)) = &self.atlas
+            && let Some(rect) = index.get(sprite)
+        {
+            return Some((atlas, rect));
+        }
+
         if let Some(bitmap) = self.cache.get(sprite) {
-            return Some(bitmap);
+            return Some((bitmap, AtlasRect::full(bitmap.size().as_uvec2().to_array().into())));
         }
 
         if self.pending.contains_key(sprite) {
@@ -43,7 +64,7 @@ impl Resources {
 
         #[cfg(debug_assertions)]
         {
-            Some(&self.while_loading)
+            Some((&self.while_loading, AtlasRect::full(self.while_loading.size().as_uvec2().to_array().into())))
         }
         #[cfg(not(debug_assertions))]
         {
@@ -51,6 +72,18 @@ impl Resources {
         }
     }
 
+    /// Bitmap and source rectangle to draw `cmd` from: the (part of the) sprite, or the current animation frame.
+    /// Animations are only available from the atlas (which holds the sprite sheets).
+    pub fn source(&mut self, cmd: &DrawSprite) -> Option<(&mq::Texture2D, AtlasRect)> {
+        match cmd.animation {
+            Some((animation, time)) => {
+                let (atlas, index) = self.atlas.as_ref()?;
+                index.frame(animation, time).map(|src| (atlas, src))
+            }
+            None => self.get(&cmd.sprite).map(|(bitmap, region)| (bitmap, region.source(cmd.src_pos, cmd.dst_size))),
+        }
+    }
+
     /// To be called on each tick. Newly loaded sprites become available.
     pub fn poll(&mut self) {
         // borrow checker song and dance.
@@ -77,7 +110,7 @@ impl Resources {
 async fn load_bitmap_or_fallback(sprite: Sprite) -> mq::Texture2D {
     const TILE_SIZE: vec2u16 = vec2(24, 24); // 🪲 TODO
 
-    let path = format!("a_strategy_game/assets/{}.png", sprite.file.as_str());
+    let path = format!("{ASSETS_DIR}/{}.png", sprite.file.as_str());
     log::trace!("load {path:?}");
     match mq::load_texture(&path).await {
         Ok(bitmap) => bitmap,
@@ -93,3 +126,19 @@ pub(crate) fn fallback_bitmap((r, g, b): (u8, u8, u8), size: vec2u16) -> mq::Ima
     let color = mq::Color::from_rgba(r, g, b, 255);
     mq::Image::gen_image_color(width, height, color)
 }
+
+/// Pack all sprites into a texture atlas (in memory, so it's always up to date).
+fn load_atlas() -> Option<(mq::Texture2D, AtlasIndex)> {
+    match atlas_builder::build_atlas(ASSETS_DIR) {
+        Ok((image, index)) => {
+            log::info!("texture atlas: {} sprites, {}x{}", index.sprites.len(), index.size, index.size);
+            let texture = mq::Texture2D::from_rgba8(image.width() as u16, image.height() as u16, image.as_raw());
+            texture.set_filter(mq::FilterMode::Nearest);
+            Some((texture, index))
+        }
+        Err(e) => {
+            log::warn!("no texture atlas, loading sprites one by one: {e:#}");
+            None
+        }
+    }
+}
@@ -1,51 +1,27 @@
 use crate::*;
-use anyhow::Context as _;
-use anyhow::Result;
-use core_util::*;
-use serde::{Serialize, de::DeserializeOwned};
-use std::fs;
 
-const APP_KEY: &'static str = "savegame";
+/// Key of the game saved on quit and loaded on startup.
+pub const APP_KEY: &str = "savegame";
 
-pub fn save_game<G: GameCore>(state: &G) {
-    log::info!("save game {APP_KEY}");
-    serialize(APP_KEY, state).expect("autosave");
-}
-
-pub fn load_game<G: GameCore>() -> Option<G> {
-    log::info!("loading... {APP_KEY}");
-    deserialize(APP_KEY).map_err(|e| log::info!("load_game {APP_KEY}: {e}")).ok()
-}
+/// Where saves, game data and files live.
+pub const STORAGE_DIR: &str = "app_storage";
 
-/// Serialize value to browser storage under given key.
-pub fn serialize<T>(key: &str, v: &T) -> Result<()>
-where
-    T: Serialize,
-{
-    let bytes = bincode::serde::encode_to_vec(v, bincode::config::standard()).with_context(|| format!("encode {key:?}"))?;
-    save_bytes(key, &bytes)
-}
+pub type AppStorage = Compressed<FsStorage>;
 
-/// Deserialize from browser storage under given key.
-pub fn deserialize<T>(key: &str) -> Result<T>
-where
-    T: DeserializeOwned,
-{
-    let bytes = load_bytes(key)?;
-    let (v, _) = bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).with_context(|| format!("decode {key:?}"))?;
-    Ok(v)
+pub fn app_storage() -> AppStorage {
+    Compressed(FsStorage::new(STORAGE_DIR))
 }
 
-/// Store data to browser storage under given key.
-pub fn save_bytes(key: &str, data: &[u8]) -> Result<()> {
-    let path = format!("app_storage/{key}");
-    log::info!("save {path:?}");
-    Ok(fs::write(path, data)?)
+/// Seconds since the UNIX epoch (wall clock, unlike the game's time).
+pub fn unix_secs() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
 }
 
-/// Load data from browser storage under given key.
-pub fn load_bytes(key: &str) -> Result<Vec<u8>> {
-    let path = format!("app_storage/{key}");
-    log::info!("load {path:?}");
-    Ok(fs::read(&path).with_context(|| format!("error reading {path:?}"))?)
+/// Thumbnail of what's currently on screen.
+pub fn screen_thumbnail() -> Thumbnail {
+    let image = mq::get_screen_data();
+    let size = vec2(image.width as u32, image.height as u32);
+    // 👇 OpenGL reads the bottom row first.
+    let rgba = image.bytes.chunks_exact(size.x() as usize * 4).rev().flatten().copied().collect::<Vec<_>>();
+    Thumbnail::downscale(size, &rgba)
 }
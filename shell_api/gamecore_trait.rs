@@ -1,8 +1,11 @@
 use crate::*;
-use serde::{Serialize, de::DeserializeOwned};
 
-pub trait GameCore: Default + DeserializeOwned + Serialize + 'static {
+/// The game, as seen by the shell. Saved with `encode_save` (hence `Migrate`).
+pub trait GameCore: Default + Migrate + 'static {
     fn tick(&mut self, unix_micros: u64, events: impl Iterator<Item = InputEvent>, out: &mut Out);
 
     fn reset(&mut self);
+
+    /// Replace the game by a loaded save slot, keeping what is not saved (e.g. console, camera).
+    fn load(&mut self, loaded: Self);
 }
@@ -1,17 +1,27 @@
 use crate::prelude::*;
 use std::ops::Deref;
+use std::str::FromStr;
 
 /// Key code or mouse button. Backed by short string (`Str16`), copyable.
+/// Keyboard keys use the same names on every shell, see `KEY_NAMES`.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Button(pub Str16);
 
 pub const K_MOUSE1: Button = button!("Mouse1");
 pub const K_MOUSE2: Button = button!("Mouse2");
+pub const K_MOUSE3: Button = button!("Mouse3");
 
 pub const K_CLI: Button = button!("cli");
+pub const K_INSPECT: Button = button!("inspect");
 pub const K_BACKSPACE: Button = button!("backspace");
 pub const K_ESC: Button = button!("escape");
 pub const K_ENTER: Button = button!("enter");
+pub const K_TAB: Button = button!("tab");
+pub const K_DELETE: Button = button!("delete");
+pub const K_HOME: Button = button!("home");
+pub const K_END: Button = button!("end");
+pub const K_PAGE_UP: Button = button!("pageup");
+pub const K_PAGE_DOWN: Button = button!("pagedown");
 
 pub const K_ARROW_LEFT: Button = button!("arrowleft");
 pub const K_ARROW_UP: Button = button!("arrowup");
@@ -23,8 +33,79 @@ pub const K_UP: Button = button!("up");
 pub const K_DOWN: Button = button!("down");
 pub const K_RIGHT: Button = button!("right");
 
+/// Gamepad buttons, in the order of the W3C "standard" gamepad mapping.
+pub const GAMEPAD_BUTTONS: [Button; 17] = [
+    K_PAD_A,
+    K_PAD_B,
+    K_PAD_X,
+    K_PAD_Y,
+    K_PAD_LB,
+    K_PAD_RB,
+    K_PAD_LT,
+    K_PAD_RT,
+    K_PAD_BACK,
+    K_PAD_START,
+    K_PAD_LS,
+    K_PAD_RS,
+    K_PAD_UP,
+    K_PAD_DOWN,
+    K_PAD_LEFT,
+    K_PAD_RIGHT,
+    K_PAD_HOME,
+];
+
+pub const K_PAD_A: Button = button!("pad_a");
+pub const K_PAD_B: Button = button!("pad_b");
+pub const K_PAD_X: Button = button!("pad_x");
+pub const K_PAD_Y: Button = button!("pad_y");
+pub const K_PAD_LB: Button = button!("pad_lb");
+pub const K_PAD_RB: Button = button!("pad_rb");
+pub const K_PAD_LT: Button = button!("pad_lt");
+pub const K_PAD_RT: Button = button!("pad_rt");
+pub const K_PAD_BACK: Button = button!("pad_back");
+pub const K_PAD_START: Button = button!("pad_start");
+pub const K_PAD_LS: Button = button!("pad_ls");
+pub const K_PAD_RS: Button = button!("pad_rs");
+pub const K_PAD_UP: Button = button!("pad_up");
+pub const K_PAD_DOWN: Button = button!("pad_down");
+pub const K_PAD_LEFT: Button = button!("pad_left");
+pub const K_PAD_RIGHT: Button = button!("pad_right");
+pub const K_PAD_HOME: Button = button!("pad_home");
+
 pub const K_SPACE: Button = button!("space");
 pub const K_TICK: Button = button!("tick");
+pub const K_QUICKSAVE: Button = button!("quicksave");
+pub const K_SELECT_ALL: Button = button!("selectall");
+pub const K_COPY: Button = button!("copy");
+pub const K_CUT: Button = button!("cut");
+
+/// ⌨️ Canonical keyboard key names, used by all shells (and in keymaps, e.g. `bind ctrl+s quicksave`).
+///
+/// Physical keys (not the characters they type, see `InputEvent::Text`),
+/// named after the W3C `KeyboardEvent.code`, lowercased,
+/// but without the `Key`/`Digit` prefixes: `KeyA` is "a", `Digit1` is "1", `ArrowLeft` is "arrowleft".
+#[rustfmt::skip]
+pub const KEY_NAMES: &[&str] = &[
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m",
+    "n", "o", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z",
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+    "f1", "f2", "f3", "f4", "f5", "f6", "f7", "f8", "f9", "f10", "f11", "f12",
+    "backquote", "minus", "equal", "bracketleft", "bracketright", "backslash",
+    "semicolon", "quote", "comma", "period", "slash", "intlbackslash",
+    "space", "enter", "escape", "backspace", "tab", "capslock",
+    "insert", "delete", "home", "end", "pageup", "pagedown",
+    "arrowleft", "arrowup", "arrowdown", "arrowright",
+    "shiftleft", "shiftright", "controlleft", "controlright", "altleft", "altright", "metaleft", "metaright",
+    "contextmenu", "printscreen", "scrolllock", "pause", "numlock",
+    "numpad0", "numpad1", "numpad2", "numpad3", "numpad4", "numpad5", "numpad6", "numpad7", "numpad8", "numpad9",
+    "numpaddecimal", "numpaddivide", "numpadmultiply", "numpadsubtract", "numpadadd", "numpadenter", "numpadequal",
+];
+
+/// The canonical key for a W3C `KeyboardEvent.code` (e.g. "KeyA" -> "a"), if it has one.
+pub fn key_from_code(code: &str) -> Option<Button> {
+    let name = code.strip_prefix("Key").or_else(|| code.strip_prefix("Digit")).unwrap_or(code).to_ascii_lowercase();
+    KEY_NAMES.contains(&name.as_str()).then(|| name.parse().ok()).flatten()
+}
 
 /// Constructor with compile-time size check (name fits `Str16`)
 /// E.g. `button!("Mouse1")`.
@@ -35,6 +116,14 @@ macro_rules! button {
     };
 }
 
+impl FromStr for Button {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(Self(s.parse()?))
+    }
+}
+
 impl Deref for Button {
     type Target = Str16;
 
@@ -42,3 +131,29 @@ impl Deref for Button {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn key_names() {
+        for name in KEY_NAMES {
+            expect_that!(name.parse::<Button>(), ok(anything()), "{name}");
+            expect_that!(KEY_NAMES.iter().filter(|n| *n == name).count(), eq(1), "{name}");
+        }
+        for key in [K_BACKSPACE, K_ESC, K_ENTER, K_TAB, K_DELETE, K_HOME, K_END, K_PAGE_UP, K_PAGE_DOWN, K_ARROW_LEFT, K_ARROW_UP, K_ARROW_DOWN, K_ARROW_RIGHT, K_SPACE] {
+            expect_that!(KEY_NAMES, contains(eq(&key.as_str())));
+        }
+    }
+
+    #[gtest]
+    fn from_code() {
+        expect_that!(key_from_code("KeyA"), some(eq(button!("a"))));
+        expect_that!(key_from_code("Digit7"), some(eq(button!("7"))));
+        expect_that!(key_from_code("ArrowLeft"), some(eq(K_ARROW_LEFT)));
+        expect_that!(key_from_code("Backquote"), some(eq(button!("backquote"))));
+        expect_that!(key_from_code("Lang1"), none());
+    }
+}
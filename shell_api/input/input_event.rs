@@ -2,10 +2,68 @@ use crate::prelude::*;
 
 #[derive(Debug)]
 pub enum InputEvent {
-    Key { button: Button, direction: KeyDir },
-    MouseMove { position: vec2i },
-    InputCharacter(char),
+    Key {
+        button: Button,
+        direction: KeyDir,
+    },
+    MouseMove {
+        position: vec2i,
+    },
+    /// Scrolled this many notches, positive is up (away from the user).
+    MouseWheel {
+        delta: f32,
+    },
+    /// A finger on a touch screen. `id` tells simultaneous fingers apart.
+    Touch {
+        id: u64,
+        phase: TouchPhase,
+        position: vec2i,
+    },
+    /// Gamepad stick position, -1..=1 (right/down is positive).
+    /// Gamepad buttons are `Key` events (`K_PAD_A`, ...).
+    GamepadAxis {
+        axis: GamepadAxis,
+        value: f32,
+    },
+    /// Text typed, pasted or committed by an input method (IME). No control characters.
+    /// Independent of `Key` events: use keys for actions (e.g. `K_BACKSPACE`), text for typing.
+    Text(String),
+    /// Text being composed by an input method, not yet committed (empty when done).
+    Composition(String),
     Command(String),
+    /// Answer to `ShellRequest::ReadFile`.
+    FileLoaded {
+        path: String,
+        result: Result<Vec<u8>, String>,
+    },
+    /// Answer to `ShellRequest::Fetch`.
+    Fetched {
+        key: String,
+        result: Result<Vec<u8>, String>,
+    },
+    /// Text for the user, e.g. the outcome of a `ShellRequest`.
+    ShellMessage(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Start,
+    Move,
+    End,
+    Cancel,
+}
+
+/// Gamepad axes, in the order of the W3C "standard" gamepad mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+}
+
+impl GamepadAxis {
+    pub const ALL: [Self; 4] = [Self::LeftX, Self::LeftY, Self::RightX, Self::RightY];
 }
 
 #[derive(Debug)]
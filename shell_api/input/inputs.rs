@@ -2,6 +2,9 @@ use std::collections::VecDeque;
 
 use crate::prelude::*;
 
+/// Stick positions closer to 0 than this count as 0 (sticks rarely center perfectly).
+const GAMEPAD_DEAD_ZONE: f32 = 0.15;
+
 /// Accumulates input events since the last tick,
 /// allowing for queries like "is this key currently held down?".
 ///
@@ -12,18 +15,30 @@ pub struct Inputs {
     buttons_pressed: HashSet<Button>,
     buttons_down: HashSet<Button>,
     buttons_released: HashSet<Button>,
+    /// OS key repeats of held buttons (e.g. holding backspace).
+    buttons_repeated: HashSet<Button>,
+    /// What each held physical key was mapped to when pressed,
+    /// so that releasing the modifier first (ctrl+s) still releases the right action.
+    pressed_as: HashMap<Button, Button>,
+    mods: Mods,
 
     mouse_position: vec2i,
     mouse_wheel: f32,
+    touches: Touches,
+    gamepad_axes: [f32; GamepadAxis::ALL.len()],
 
-    input_characters: String,
+    text: String,
+    composition: String,
     commands: VecDeque<String>,
+    loaded_files: Vec<(String, Result<Vec<u8>, String>)>,
+    fetched: Vec<(String, Result<Vec<u8>, String>)>,
+    shell_messages: Vec<String>,
 }
 
 impl Inputs {
     // To be called on each frame to advance time.
     // "just pressed" evolves to "is_down".
-    // "released" and input_characters gets forgotten.
+    // "released" and text gets forgotten.
     pub fn tick(&mut self, keymap: &Keymap, events: impl Iterator<Item = InputEvent>) {
         self.clear();
         for event in events {
@@ -35,12 +50,24 @@ impl Inputs {
         // Note: NOT clearing bottons_down.
         self.buttons_pressed.clear();
         self.buttons_released.clear();
-        self.input_characters.clear();
+        self.buttons_repeated.clear();
+        self.text.clear();
         self.commands.clear();
+        self.loaded_files.clear();
+        self.fetched.clear();
+        self.shell_messages.clear();
+        self.mouse_wheel = 0.0;
+        self.touches.clear();
+    }
+
+    /// Text typed (or pasted) since the last tick.
+    pub fn text(&self) -> &str {
+        &self.text
     }
 
-    pub fn input_characters(&self) -> &str {
-        &self.input_characters
+    /// Text being composed by an input method (IME), to be shown at the cursor. Not part of `text` (yet).
+    pub fn composition(&self) -> &str {
+        &self.composition
     }
 
     pub fn commands(&self) -> impl Iterator<Item = &str> {
@@ -51,6 +78,21 @@ impl Inputs {
         self.commands.drain(..)
     }
 
+    /// Files loaded since the last tick (see `ShellRequest::ReadFile`): path and contents or error.
+    pub fn drain_loaded_files(&mut self) -> impl Iterator<Item = (String, Result<Vec<u8>, String>)> {
+        self.loaded_files.drain(..)
+    }
+
+    /// Data fetched since the last tick (see `ShellRequest::Fetch`): key and data or error.
+    pub fn drain_fetched(&mut self) -> impl Iterator<Item = (String, Result<Vec<u8>, String>)> {
+        self.fetched.drain(..)
+    }
+
+    /// Messages from the shell since the last tick (e.g. "saved").
+    pub fn shell_messages(&self) -> impl Iterator<Item = &str> {
+        self.shell_messages.iter().map(String::as_str)
+    }
+
     /// Is a button currently held down?
     /// (This repeats on every tick for as long as the button is held)
     pub fn is_down(&self, but: Button) -> bool {
@@ -64,6 +106,11 @@ impl Inputs {
         self.buttons_pressed.contains(&but)
     }
 
+    /// Just pressed, or held long enough for the OS to repeat it (e.g. backspace, arrows in a text field).
+    pub fn pressed_or_repeated(&self, but: Button) -> bool {
+        self.just_pressed(but) || self.buttons_repeated.contains(&but)
+    }
+
     /// Was a button released right before the current tick?
     pub fn just_released(&self, but: Button) -> bool {
         self.buttons_released.contains(&but)
@@ -89,6 +136,50 @@ impl Inputs {
         self.buttons_down.remove(&but);
         self.buttons_pressed.remove(&but);
         self.buttons_released.remove(&but);
+        self.buttons_repeated.remove(&but);
+    }
+
+    /// Mouse wheel movement since the last tick, in notches.
+    /// Positive: scrolled up (away from the user).
+    pub fn mouse_wheel(&self) -> f32 {
+        self.mouse_wheel
+    }
+
+    /// Number of fingers on the touch screen.
+    /// A single finger also acts as the mouse (`K_MOUSE1`).
+    pub fn touch_count(&self) -> usize {
+        self.touches.count()
+    }
+
+    /// Two-finger pinch since the last tick, as a zoom factor (1.0: no pinch).
+    pub fn pinch(&self) -> f32 {
+        self.touches.pinch
+    }
+
+    /// Two-finger movement since the last tick, in pixels.
+    pub fn touch_pan(&self) -> vec2f {
+        self.touches.pan
+    }
+
+    /// Center of the fingers on the touch screen (e.g. to zoom around).
+    pub fn touch_center(&self) -> Option<vec2f> {
+        self.touches.center()
+    }
+
+    /// Gamepad stick position, -1..=1, with a dead zone around 0.
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        let v = self.gamepad_axes[axis as usize];
+        if v.abs() < GAMEPAD_DEAD_ZONE { 0.0 } else { v }
+    }
+
+    /// Left gamepad stick, -1..=1 (right/down is positive).
+    pub fn left_stick(&self) -> vec2f {
+        vec2(self.gamepad_axis(GamepadAxis::LeftX), self.gamepad_axis(GamepadAxis::LeftY))
+    }
+
+    /// Modifier keys currently held down.
+    pub fn mods(&self) -> Mods {
+        self.mods
     }
 
     /// Mouse position in logical pixels, relative to the top-left corner of the window.
@@ -104,16 +195,35 @@ impl Inputs {
             InputEvent::MouseMove { position } => {
                 self.record_mouse_position(position);
             }
-            InputEvent::InputCharacter(chr) => self.input_characters.push(chr),
+            InputEvent::MouseWheel { delta } => self.mouse_wheel += delta,
+            InputEvent::Touch { id, phase, position } => match self.touches.record(id, phase, position) {
+                Emulate::MouseDown(pos) => {
+                    self.record_mouse_position(pos);
+                    self.record_press(keymap, K_MOUSE1);
+                }
+                Emulate::MouseMove(pos) => self.record_mouse_position(pos),
+                Emulate::MouseUp => self.record_release(keymap, K_MOUSE1),
+                Emulate::Nothing => (),
+            },
+            InputEvent::GamepadAxis { axis, value } => self.gamepad_axes[axis as usize] = value,
+            InputEvent::Text(text) => self.text.extend(text.chars().filter(|c| !c.is_control())),
+            InputEvent::Composition(text) => self.composition = text,
             InputEvent::Command(cmd) => self.commands.push_back(cmd),
+            InputEvent::FileLoaded { path, result } => self.loaded_files.push((path, result)),
+            InputEvent::Fetched { key, result } => self.fetched.push((key, result)),
+            InputEvent::ShellMessage(msg) => self.shell_messages.push(msg),
         }
     }
 
     /// Record that this button was just pressed.
     fn record_press(&mut self, keymap: &Keymap, button: Button) {
-        let mapped = keymap.map(button);
+        let mapped = keymap.map(self.mods, button);
         log::trace!("press {mapped:?} (keymapped from {button:?})");
-        if !self.buttons_down.contains(&mapped) {
+        self.mods.set(Mods::of_key(button), true);
+        self.pressed_as.insert(button, mapped);
+        if self.buttons_down.contains(&mapped) {
+            self.buttons_repeated.insert(mapped);
+        } else {
             self.buttons_pressed.insert(mapped);
             self.buttons_down.insert(mapped);
         }
@@ -121,7 +231,8 @@ impl Inputs {
 
     /// Record that this button was just released.
     fn record_release(&mut self, keymap: &Keymap, button: Button) {
-        let button = keymap.map(button);
+        self.mods.set(Mods::of_key(button), false);
+        let button = self.pressed_as.remove(&button).unwrap_or_else(|| keymap.map(self.mods, button));
         self.buttons_released.insert(button);
         self.buttons_down.remove(&button);
     }
@@ -129,42 +240,4 @@ impl Inputs {
     fn record_mouse_position(&mut self, pos: vec2i) {
         self.mouse_position = pos
     }
-
-    // The relative mouse wheel movement since last tick.
-    //pub fn mouse_wheel_delta(&self) -> i32 {
-    //	let mut delta = 0;
-    //	if self.just_pressed(Button::MouseWheelDown) {
-    //		delta -= 1;
-    //	}
-    //	if self.just_pressed(Button::MouseWheelUp) {
-    //		delta += 1;
-    //	}
-    //	delta
-    //}
-
-    //fn record_mouse_wheel(&mut self, delta: &winit::event::MouseScrollDelta) {
-    //	/*
-    //		Mouse wheel delta's can vary wildly,
-    //		reduce them just a single Up / Down event
-    //		discarding the scroll amount.
-    //	*/
-    //	let dy = match delta {
-    //		winit::event::MouseScrollDelta::LineDelta(_, y) => *y,
-    //		winit::event::MouseScrollDelta::PixelDelta(PhysicalPosition { y, .. }) => *y as f32,
-    //	};
-    //	let button = match dy {
-    //		_ if dy > 0.0 => Some(Button::MouseWheelUp),
-    //		_ if dy < 0.0 => Some(Button::MouseWheelDown),
-    //		_ => None,
-    //	};
-    //	/*
-    //		Record both a press and release
-    //		to make the scroll event appear as a button press
-    //		(the scroll wheel cannot be "held down" continuously like a mouse button).
-    //	*/
-    //	if let Some(button) = button {
-    //		self.buttons_pressed.insert(button);
-    //		self.buttons_released.insert(button);
-    //	}
-    //}
 }
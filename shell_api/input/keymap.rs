@@ -1,19 +1,202 @@
 use crate::prelude::*;
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Debug, Default)]
-pub struct Keymap(pub HashMap<Button, Button>);
+/// Maps physical keys, with modifiers, to virtual buttons ("actions").
+/// E.g. `ctrl+s` -> `quicksave`, `arrowleft` -> `left`.
+/// Several chords may map to the same action. Unmapped keys pass through unchanged.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Keymap(pub BTreeMap<Chord, Button>);
+
+/// Modifier keys held down, e.g. `ctrl+shift`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Mods {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+/// Physical key plus modifiers, e.g. `ctrl+s`. Written (and serialized) as text.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(into = "String", try_from = "String")]
+pub struct Chord {
+    pub mods: Mods,
+    pub key: Button,
+}
 
 impl Keymap {
-    pub(crate) fn map(&self, button: Button) -> Button {
-        self.0.get(&button).copied().unwrap_or(button)
+    /// Bind a chord to an action, returning the action it was previously bound to.
+    pub fn bind(&mut self, chord: Chord, action: Button) -> Option<Button> {
+        self.0.insert(chord, action)
+    }
+
+    /// Remove a binding, returning the action it was bound to.
+    pub fn unbind(&mut self, chord: Chord) -> Option<Button> {
+        self.0.remove(&chord)
+    }
+
+    /// Chords bound to an action.
+    pub fn chords(&self, action: Button) -> impl Iterator<Item = Chord> + '_ {
+        self.0.iter().filter(move |(_, a)| **a == action).map(|(c, _)| *c)
+    }
+
+    /// All bindings, sorted by action.
+    pub fn iter(&self) -> impl Iterator<Item = (Chord, Button)> + '_ {
+        let mut bindings = self.0.iter().map(|(c, a)| (*c, *a)).collect::<Vec<_>>();
+        bindings.sort_by_key(|(c, a)| (*a, *c));
+        bindings.into_iter()
+    }
+
+    /// Action for a key pressed while holding `mods`.
+    /// Falls back to the unmodified binding (so that e.g. shift+arrowleft still moves left),
+    /// then to the key itself.
+    pub(crate) fn map(&self, mods: Mods, key: Button) -> Button {
+        self.0.get(&Chord { mods, key }).or_else(|| self.0.get(&Chord::from(key))).copied().unwrap_or(key)
     }
 }
 
-impl<T> From<T> for Keymap
+impl<T, C> From<T> for Keymap
 where
-    T: IntoIterator<Item = (Button, Button)>,
+    T: IntoIterator<Item = (C, Button)>,
+    C: Into<Chord>,
 {
     fn from(value: T) -> Self {
-        Self(value.into_iter().collect())
+        Self(value.into_iter().map(|(c, a)| (c.into(), a)).collect())
+    }
+}
+
+impl Mods {
+    pub const NONE: Self = Self { ctrl: false, shift: false, alt: false };
+    pub const CTRL: Self = Self { ctrl: true, ..Self::NONE };
+    pub const SHIFT: Self = Self { shift: true, ..Self::NONE };
+    pub const ALT: Self = Self { alt: true, ..Self::NONE };
+
+    /// Modifier set by a physical key, if any.
+    pub fn of_key(key: Button) -> Self {
+        match key.as_str() {
+            "controlleft" | "controlright" => Self::CTRL,
+            "shiftleft" | "shiftright" => Self::SHIFT,
+            "altleft" | "altright" => Self::ALT,
+            _ => Self::NONE,
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self == Self::NONE
+    }
+
+    /// Set (or clear) the modifiers in `other`.
+    pub(crate) fn set(&mut self, other: Self, down: bool) {
+        self.ctrl = if other.ctrl { down } else { self.ctrl };
+        self.shift = if other.shift { down } else { self.shift };
+        self.alt = if other.alt { down } else { self.alt };
+    }
+}
+
+impl From<Button> for Chord {
+    fn from(key: Button) -> Self {
+        Self { mods: Mods::NONE, key }
+    }
+}
+
+impl Chord {
+    pub fn new(mods: Mods, key: Button) -> Self {
+        Self { mods, key }
+    }
+}
+
+impl Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Mods { ctrl, shift, alt } = self.mods;
+        for (held, name) in [(ctrl, "ctrl+"), (shift, "shift+"), (alt, "alt+")] {
+            if held {
+                f.write_str(name)?;
+            }
+        }
+        f.write_str(self.key.as_str())
+    }
+}
+
+/// Parse e.g. `ctrl+shift+s`, `f2`, `shift++`. Case insensitive.
+impl FromStr for Chord {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_ascii_lowercase();
+        let (mods, key) = match s.strip_suffix('+').filter(|m| m.is_empty() || m.ends_with('+')) {
+            Some(mods) => (mods, "+"), // 👈 the "+" key itself
+            None => s.rsplit_once('+').unwrap_or(("", &s)),
+        };
+        if key.is_empty() {
+            bail!("missing key in {s:?}");
+        }
+        let mut chord = Chord::from(key.parse::<Button>()?);
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            match m {
+                "ctrl" | "control" => chord.mods.ctrl = true,
+                "shift" => chord.mods.shift = true,
+                "alt" => chord.mods.alt = true,
+                _ => bail!("unknown modifier {m:?} (have: ctrl, shift, alt)"),
+            }
+        }
+        Ok(chord)
+    }
+}
+
+impl From<Chord> for String {
+    fn from(chord: Chord) -> Self {
+        chord.to_string()
+    }
+}
+
+impl TryFrom<String> for Chord {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn chord_roundtrip() {
+        for s in ["s", "ctrl+s", "ctrl+shift+alt+f2", "shift++", "+"] {
+            let chord: Chord = s.parse().unwrap();
+            expect_that!(chord.to_string(), eq(s));
+        }
+        expect_that!("Control+S".parse::<Chord>().unwrap(), eq(Chord::new(Mods::CTRL, button!("s"))));
+        expect_that!("super+s".parse::<Chord>(), err(anything()));
+        expect_that!("ctrl+".parse::<Chord>(), err(anything()));
+    }
+
+    #[gtest]
+    fn map_with_mods() {
+        let keymap = Keymap::from([(Chord::new(Mods::CTRL, button!("s")), button!("quicksave")), (button!("s").into(), button!("down"))]);
+        expect_that!(keymap.map(Mods::CTRL, button!("s")), eq(button!("quicksave")));
+        expect_that!(keymap.map(Mods::NONE, button!("s")), eq(button!("down")));
+        expect_that!(keymap.map(Mods::SHIFT, button!("s")), eq(button!("down")));
+        expect_that!(keymap.map(Mods::NONE, button!("x")), eq(button!("x")));
+    }
+
+    #[gtest]
+    fn release_modifier_first() {
+        let keymap = Keymap::from([(Chord::new(Mods::CTRL, button!("s")), K_QUICKSAVE)]);
+        let key = |name: &str, direction| InputEvent::Key { button: name.parse().unwrap(), direction };
+        let mut inputs = Inputs::default();
+
+        inputs.tick(&keymap, [key("controlleft", KeyDir::Down), key("s", KeyDir::Down)].into_iter());
+        expect_that!(inputs.just_pressed(K_QUICKSAVE), eq(true));
+        expect_that!(inputs.mods(), eq(Mods::CTRL));
+
+        inputs.tick(&keymap, [key("controlleft", KeyDir::Up), key("s", KeyDir::Up)].into_iter());
+        expect_that!(inputs.just_released(K_QUICKSAVE), eq(true));
+        expect_that!(inputs.is_down(K_QUICKSAVE), eq(false));
+        expect_that!(inputs.mods(), eq(Mods::NONE));
     }
 }
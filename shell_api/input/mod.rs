@@ -2,8 +2,10 @@ mod button;
 mod input_event;
 mod inputs;
 mod keymap;
+mod touch;
 
 pub use button::*;
 pub use input_event::*;
 pub use inputs::*;
 pub use keymap::*;
+pub(crate) use touch::*;
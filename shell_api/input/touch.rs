@@ -0,0 +1,116 @@
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+/// Turns touch events into gestures:
+/// one finger acts like the left mouse button (tap = click, drag = drag),
+/// two fingers pinch (zoom) and pan.
+#[derive(Debug, Default)]
+pub(crate) struct Touches {
+    /// Current position of each finger on the screen.
+    fingers: BTreeMap<u64, vec2i>,
+
+    /// Is the first finger currently emulating `K_MOUSE1`?
+    mouse_down: bool,
+
+    /// Zoom factor since the last tick (1.0: none).
+    pub pinch: f32,
+
+    /// Two-finger movement since the last tick.
+    pub pan: vec2f,
+}
+
+/// What a touch event means for mouse emulation.
+pub(crate) enum Emulate {
+    MouseDown(vec2i),
+    MouseMove(vec2i),
+    MouseUp,
+    Nothing,
+}
+
+impl Touches {
+    pub fn clear(&mut self) {
+        self.pinch = 1.0;
+        self.pan = vec2f::ZERO;
+    }
+
+    pub fn count(&self) -> usize {
+        self.fingers.len()
+    }
+
+    /// Center of all fingers.
+    pub fn center(&self) -> Option<vec2f> {
+        let n = self.fingers.len();
+        (n != 0).then(|| self.fingers.values().map(|p| p.as_f32()).fold(vec2f::ZERO, |a, b| a + b) / n as f32)
+    }
+
+    pub fn record(&mut self, id: u64, phase: TouchPhase, position: vec2i) -> Emulate {
+        let before = self.two_finger_span();
+        match phase {
+            TouchPhase::Start => {
+                self.fingers.insert(id, position);
+            }
+            TouchPhase::Move => {
+                self.fingers.insert(id, position);
+            }
+            TouchPhase::End | TouchPhase::Cancel => {
+                self.fingers.remove(&id);
+            }
+        }
+        let after = self.two_finger_span();
+
+        // 🤏 pinch and pan while exactly two fingers stay down
+        if let (Some((c0, d0)), Some((c1, d1))) = (before, after)
+            && phase == TouchPhase::Move
+        {
+            if d0 > 0.0 {
+                self.pinch *= d1 / d0;
+            }
+            self.pan += c1 - c0;
+        }
+
+        match (self.fingers.len(), phase, self.mouse_down) {
+            (1, TouchPhase::Start, false) => {
+                self.mouse_down = true;
+                Emulate::MouseDown(position)
+            }
+            (1, TouchPhase::Move, true) => Emulate::MouseMove(position),
+            // 👇 lifting the only finger ends the click, adding a second one cancels it (it's a gesture)
+            (0 | 2, _, true) => {
+                self.mouse_down = false;
+                Emulate::MouseUp
+            }
+            _ => Emulate::Nothing,
+        }
+    }
+
+    /// Center and distance of the fingers, if exactly two.
+    fn two_finger_span(&self) -> Option<(vec2f, f32)> {
+        match self.fingers.values().collect::<Vec<_>>().as_slice() {
+            [a, b] => Some(((a.as_f32() + b.as_f32()) / 2.0, a.as_f32().distance_to(b.as_f32()))),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn pinch() {
+        let mut t = Touches::default();
+        t.clear();
+        expect_true!(matches!(t.record(1, TouchPhase::Start, vec2(100, 100)), Emulate::MouseDown(_)));
+        expect_true!(matches!(t.record(2, TouchPhase::Start, vec2(200, 100)), Emulate::MouseUp));
+        t.record(2, TouchPhase::Move, vec2(300, 100));
+        expect_that!(t.pinch, near(2.0, 1e-5));
+        expect_eq!(t.pan, vec2(50.0, 0.0));
+
+        t.clear();
+        expect_true!(matches!(t.record(2, TouchPhase::End, vec2(300, 100)), Emulate::Nothing));
+        expect_true!(matches!(t.record(1, TouchPhase::Move, vec2(110, 100)), Emulate::Nothing));
+        expect_eq!(t.pinch, 1.0);
+        expect_eq!(t.count(), 1);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::prelude::*;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+/// Sprite sheet metadata file extension: `crab.sheet.ron` describes `crab.png`.
+pub const SHEET_EXTENSION: &str = "sheet.ron";
+
+#[macro_export]
+macro_rules! animation {
+    ($arg:tt) => {
+        Animation { name: proc_macros::str16!($arg) }
+    };
+}
+
+/// Named animation in a `SpriteSheet`, e.g. `animation!("crab_walk")`.
+/// Names are unique across all sheets (`atlas_builder` rejects duplicates), so games don't need to know which sheet an animation is in.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Animation {
+    pub name: Str16,
+}
+
+/// 🎞️ A sprite cut into a grid of equally sized frames (numbered row by row, starting at 0),
+/// with named animations. Stored next to the sprite as `<sprite>.sheet.ron`,
+/// and collected into the `AtlasIndex` by `atlas_builder`. E.g.:
+///
+/// ```ron
+/// (
+///     frame_size: (32, 24),
+///     animations: {
+///         "crab_walk": (frames: [(cell: 0, duration: 0.1), (cell: 1, duration: 0.1)]),
+///         "crab_die": (frames: [(cell: 4, duration: 0.2), (cell: 5, duration: 1.0)], hold: true),
+///     },
+/// )
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct SpriteSheet {
+    pub frame_size: vec2u,
+    pub animations: BTreeMap<String, AnimationClip>,
+}
+
+/// Sequence of frames in a `SpriteSheet`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct AnimationClip {
+    pub frames: Vec<AnimationFrame>,
+    /// Stay on the last frame instead of looping.
+    #[serde(default)]
+    pub hold: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct AnimationFrame {
+    /// Grid cell, row by row.
+    pub cell: u32,
+    /// Seconds.
+    pub duration: f32,
+}
+
+/// ▶️ Plays one animation at a time (e.g. per critter), switching animations restarts them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnimationPlayer {
+    pub animation: Animation,
+    /// Time (seconds, e.g. `now_secs` of the game) at which `animation` started.
+    /// `f64`, so that animations don't stutter after the game has been running for hours.
+    pub start: f64,
+}
+
+impl SpriteSheet {
+    /// Region of a grid cell, given the sheet's region (in the atlas).
+    pub fn cell_rect(&self, sheet: AtlasRect, cell: u32) -> AtlasRect {
+        let size = self.frame_size.map(|v| v.max(1));
+        let columns = u32::max(1, sheet.size.x() / size.x());
+        AtlasRect {
+            pos: sheet.pos + vec2(cell % columns, cell / columns) * size,
+            size,
+        }
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self> {
+        Ok(ron::from_str(ron)?)
+    }
+}
+
+impl AnimationClip {
+    /// Total duration of one loop (seconds).
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|f| f.duration).sum()
+    }
+
+    /// Frame to show at given time (seconds) since the animation started. `None` if there are no frames.
+    pub fn frame_at(&self, time: f32) -> Option<&AnimationFrame> {
+        let total = self.duration();
+        let mut t = match self.hold {
+            true => time.min(total),
+            false if total > 0.0 => time.rem_euclid(total),
+            false => 0.0,
+        };
+        for frame in &self.frames {
+            if t < frame.duration {
+                return Some(frame);
+            }
+            t -= frame.duration;
+        }
+        self.frames.last()
+    }
+}
+
+impl AnimationPlayer {
+    pub fn new(animation: Animation, now: f64) -> Self {
+        Self { animation, start: now }
+    }
+
+    /// Switch to `animation`, unless already playing it.
+    pub fn play(&mut self, animation: Animation, now: f64) {
+        if animation != self.animation {
+            *self = Self::new(animation, now);
+        }
+    }
+
+    /// Time (seconds) into the current animation.
+    pub fn time(&self, now: f64) -> f32 {
+        (now - self.start) as f32
+    }
+
+    /// Current frame at position, for `Out::push_sprite`.
+    pub fn draw(&self, now: f64, pos: impl IntoPos) -> DrawSprite {
+        DrawSprite::animated(self.animation, self.time(now), pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    fn clip(hold: bool) -> AnimationClip {
+        let frames = [(0, 0.1), (1, 0.2), (2, 0.1)].map(|(cell, duration)| AnimationFrame { cell, duration }).to_vec();
+        AnimationClip { frames, hold }
+    }
+
+    #[gtest]
+    fn frame_at_time() {
+        let cell = |clip: &AnimationClip, t: f32| clip.frame_at(t).map(|f| f.cell);
+        let looping = clip(false);
+        expect_that!(cell(&looping, 0.0), some(eq(0)));
+        expect_that!(cell(&looping, 0.15), some(eq(1)));
+        expect_that!(cell(&looping, 0.35), some(eq(2)));
+        expect_that!(cell(&looping, 0.45), some(eq(0))); // 👈 looped
+        let hold = clip(true);
+        expect_that!(cell(&hold, 0.15), some(eq(1)));
+        expect_that!(cell(&hold, 10.0), some(eq(2)));
+        expect_that!(AnimationClip::default().frame_at(1.0), none());
+    }
+
+    #[gtest]
+    fn cell_rect_in_grid() {
+        let sheet = SpriteSheet { frame_size: vec2(100, 50), ..Default::default() };
+        let region = AtlasRect { pos: vec2(8, 16), size: vec2(300, 100) };
+        expect_that!(sheet.cell_rect(region, 0), eq(AtlasRect { pos: vec2(8, 16), size: vec2(100, 50) }));
+        expect_that!(sheet.cell_rect(region, 4), eq(AtlasRect { pos: vec2(108, 66), size: vec2(100, 50) }));
+    }
+
+    #[gtest]
+    fn player_restarts_on_switch() {
+        let mut player = AnimationPlayer::new(animation!("crab_walk"), 1.0);
+        player.play(animation!("crab_walk"), 2.0);
+        expect_that!(player.time(3.0), eq(2.0));
+        player.play(animation!("crab_die"), 2.5);
+        expect_that!(player.time(3.0), eq(0.5));
+        expect_that!(player.draw(3.0, (1, 2)).animation, some(eq((animation!("crab_die"), 0.5))));
+    }
+
+    #[gtest]
+    fn player_after_hours() {
+        let start = 5.0 * 3600.0 + 0.01;
+        let player = AnimationPlayer::new(animation!("crab_walk"), start);
+        expect_that!(player.time(start + 0.05), near(0.05, 1e-6)); // 👈 f32 seconds are ~1ms apart by now
+    }
+}
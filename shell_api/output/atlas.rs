@@ -0,0 +1,147 @@
+use crate::prelude::*;
+use anyhow::{Result, bail};
+use binpack2d::*;
+use std::collections::BTreeMap;
+
+/// Texture atlas image, in the assets directory next to the sprites it contains (see `atlas_builder`).
+pub const ATLAS_IMAGE: &str = "atlas.png";
+
+/// Texture atlas index (`AtlasIndex` as RON), next to `ATLAS_IMAGE`.
+pub const ATLAS_INDEX: &str = "atlas.ron";
+
+/// Largest atlas we try before giving up (texture size limit of most GPUs).
+pub const MAX_ATLAS_SIZE: u32 = 4096;
+
+/// Empty pixels between sprites, so that filtering doesn't bleed in neighbours.
+const ATLAS_MARGIN: u32 = 1;
+
+/// 🗺️ Where each sprite is in the texture atlas.
+/// Renderers draw a `Sprite` as its rectangle in the atlas image,
+/// falling back to loading `<sprite>.png` for sprites that aren't in the atlas.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct AtlasIndex {
+    /// Atlas image size (square).
+    pub size: u32,
+    /// Sprite file name (without `.png`) -> rectangle.
+    pub sprites: BTreeMap<String, AtlasRect>,
+    /// Sprite name -> its frames and animations, for sprites that are sprite sheets.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sheets: BTreeMap<String, SpriteSheet>,
+}
+
+/// Sub-rectangle of an image.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasRect {
+    /// Top-left corner.
+    pub pos: vec2u,
+    pub size: vec2u,
+}
+
+impl AtlasIndex {
+    /// Lay out sprites of given sizes, doubling the atlas size until they fit.
+    pub fn pack(sizes: impl IntoIterator<Item = (String, vec2u)>) -> Result<Self> {
+        let sizes = sizes.into_iter().collect::<Vec<_>>();
+        let mut size = 64;
+        loop {
+            let mut packer = MaxRectsPacker::new(vec2(size, size), PackOptions::default().with_margin(ATLAS_MARGIN));
+            match binpack2d(&mut packer, sizes.iter().map(|(name, size)| (*size, (name, *size)))) {
+                Ok(packed) => {
+                    let sprites = packed.into_iter().map(|((name, size), p)| (name.clone(), AtlasRect { pos: p.pos, size })).collect();
+                    return Ok(Self { size, sprites, sheets: BTreeMap::new() });
+                }
+                Err(e) if size >= MAX_ATLAS_SIZE => bail!("{} sprites don't fit in the atlas: {e}", sizes.len()),
+                Err(_) => size *= 2,
+            }
+        }
+    }
+
+    /// The sprite's rectangle in the atlas, if it's in there.
+    pub fn get(&self, sprite: &Sprite) -> Option<AtlasRect> {
+        self.sprites.get(sprite.file.as_str()).copied()
+    }
+
+    /// Region of an animation's frame at given time (seconds), if the animation is in one of the sheets.
+    pub fn frame(&self, animation: Animation, time: f32) -> Option<AtlasRect> {
+        let (name, sheet, clip) = self.sheets.iter().find_map(|(name, sheet)| sheet.animations.get(animation.name.as_str()).map(|clip| (name, sheet, clip)))?;
+        Some(sheet.cell_rect(*self.sprites.get(name)?, clip.frame_at(time)?.cell))
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self> {
+        Ok(ron::from_str(ron)?)
+    }
+
+    pub fn to_ron(&self) -> Result<String> {
+        Ok(ron::ser::to_string_pretty(self, Default::default())?)
+    }
+}
+
+impl AtlasRect {
+    /// Whole image of given size.
+    pub fn full(size: vec2u) -> Self {
+        Self { pos: vec2(0, 0), size }
+    }
+
+    /// Source rectangle for drawing a `DrawSprite` from this region:
+    /// `src_pos` (if any) is relative to the region, `dst_size` defaults to the region's size.
+    pub fn source(&self, src_pos: Option<vec2u16>, dst_size: Option<vec2<NonZeroU16>>) -> AtlasRect {
+        match src_pos {
+            None => *self,
+            Some(src_pos) => AtlasRect {
+                pos: self.pos + src_pos.as_u32(),
+                size: dst_size.map(|s| s.map(|v| v.get() as u32)).unwrap_or(self.size),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn pack_grows_until_fit() {
+        let sizes = (0..20).map(|i| (format!("s{i}"), vec2(30, 30)));
+        let atlas = AtlasIndex::pack(sizes).unwrap();
+        expect_that!(atlas.size, eq(256)); // 👈 20 x 31² doesn't fit in 128²
+        expect_that!(atlas.sprites.len(), eq(20));
+        expect_that!(atlas.get(&sprite!("s7")).map(|r| r.size), some(eq(vec2(30, 30))));
+        expect_that!(AtlasIndex::from_ron(&atlas.to_ron().unwrap()).unwrap(), eq(&atlas));
+    }
+
+    #[gtest]
+    fn source_rect() {
+        let region = AtlasRect { pos: vec2(64, 32), size: vec2(128, 128) };
+        expect_that!(region.source(None, None), eq(region));
+        let glyph = region.source(Some(vec2(8, 16)), Some(vec2(NonZeroU16::new(8).unwrap(), NonZeroU16::new(16).unwrap())));
+        expect_that!(glyph, eq(AtlasRect { pos: vec2(72, 48), size: vec2(8, 16) }));
+        let large = region.source(Some(vec2(300, 0)), Some(vec2(NonZeroU16::new(400).unwrap(), NonZeroU16::new(1).unwrap())));
+        expect_that!(large, eq(AtlasRect { pos: vec2(364, 32), size: vec2(400, 1) })); // 👈 beyond 255
+    }
+
+    #[gtest]
+    fn animation_frame() {
+        let mut atlas = AtlasIndex::pack([("crab".to_string(), vec2(64, 64))]).unwrap();
+        let walk = AnimationClip {
+            frames: vec![AnimationFrame { cell: 2, duration: 0.5 }, AnimationFrame { cell: 3, duration: 0.5 }],
+            hold: false,
+        };
+        atlas.sheets.insert(
+            "crab".into(),
+            SpriteSheet {
+                frame_size: vec2(32, 32),
+                animations: [("crab_walk".to_string(), walk)].into(),
+            },
+        );
+        let crab = atlas.sprites["crab"];
+        expect_that!(
+            atlas.frame(animation!("crab_walk"), 0.7),
+            some(eq(AtlasRect {
+                pos: crab.pos + vec2(32, 32),
+                size: vec2(32, 32)
+            }))
+        );
+        expect_that!(atlas.frame(animation!("crab_swim"), 0.7), none());
+        expect_that!(AtlasIndex::from_ron(&atlas.to_ron().unwrap()).unwrap(), eq(&atlas));
+    }
+}
@@ -0,0 +1,11 @@
+/// How a primitive is composited onto what is already drawn (after applying its opacity).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Blend {
+    /// Regular alpha blending.
+    #[default]
+    Normal,
+    /// Adds to the background: glows, sparks, light.
+    Add,
+    /// Darkens the background: shadows, tinting.
+    Multiply,
+}
@@ -0,0 +1,96 @@
+use crate::prelude::*;
+
+/// 🎥 Maps world coordinates to the screen:
+/// world position `pos` is shown at the center of the viewport,
+/// scaled by `zoom` and rotated by `rot` (radians, clockwise on screen).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub pos: vec2f,
+    pub zoom: f32,
+    pub rot: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self { pos: vec2f::ZERO, zoom: 1.0, rot: 0.0 }
+    }
+}
+
+impl Camera {
+    /// Camera showing world position `pos` at the viewport center, no zoom or rotation.
+    pub fn at(pos: vec2f) -> Self {
+        Self { pos, ..Self::default() }
+    }
+
+    /// World → screen as a 2D affine transform `[a, b, c, d, e, f]`:
+    /// `screen = (a*x + c*y + e, b*x + d*y + f)`.
+    /// (Same order as HTML canvas `setTransform`).
+    pub fn transform(&self, viewport: vec2u) -> [f32; 6] {
+        let (sin, cos) = self.rot.sin_cos();
+        let (a, b, c, d) = (self.zoom * cos, self.zoom * sin, -self.zoom * sin, self.zoom * cos);
+        let center = viewport.as_f32() / 2.0;
+        let (x, y) = self.pos.into();
+        [a, b, c, d, center.x() - (a * x + c * y), center.y() - (b * x + d * y)]
+    }
+
+    pub fn world_to_screen(&self, world: vec2f, viewport: vec2u) -> vec2f {
+        let [a, b, c, d, e, f] = self.transform(viewport);
+        let (x, y) = world.into();
+        vec2(a * x + c * y + e, b * x + d * y + f)
+    }
+
+    /// Inverse of `world_to_screen`, e.g. to find what's under the mouse.
+    pub fn screen_to_world(&self, screen: vec2f, viewport: vec2u) -> vec2f {
+        let (sin, cos) = self.rot.sin_cos();
+        let (x, y) = ((screen - viewport.as_f32() / 2.0) / self.zoom).into();
+        self.pos + vec2(cos * x + sin * y, -sin * x + cos * y)
+    }
+
+    /// Move by a distance in screen pixels (e.g. mouse drag).
+    pub fn pan_screen(&mut self, delta: vec2f) {
+        let (sin, cos) = self.rot.sin_cos();
+        let (x, y) = (delta / self.zoom).into();
+        self.pos -= vec2(cos * x + sin * y, -sin * x + cos * y);
+    }
+
+    /// Multiply zoom by `factor`, keeping the world position under screen position `anchor` in place.
+    pub fn zoom_at(&mut self, anchor: vec2f, viewport: vec2u, factor: f32) {
+        let before = self.screen_to_world(anchor, viewport);
+        self.zoom *= factor;
+        let after = self.screen_to_world(anchor, viewport);
+        self.pos += before - after;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn roundtrip() {
+        let viewport = vec2(640, 480);
+        let cam = Camera { pos: vec2(100.0, 50.0), zoom: 2.0, rot: 0.5 };
+        expect_that!(cam.world_to_screen(cam.pos, viewport).array(), elements_are![near(320.0, 1e-3), near(240.0, 1e-3)]);
+
+        for p in [vec2(0.0, 0.0), vec2(13.0, -7.0), vec2(100.0, 200.0)] {
+            let back = cam.screen_to_world(cam.world_to_screen(p, viewport), viewport);
+            expect_that!(back.array(), elements_are![near(p.x(), 1e-3), near(p.y(), 1e-3)]);
+        }
+    }
+
+    #[gtest]
+    fn zoom_at() {
+        let viewport = vec2(640, 480);
+        let mut cam = Camera::at(vec2(320.0, 240.0));
+        expect_eq!(cam.world_to_screen(vec2(10.0, 20.0), viewport), vec2(10.0, 20.0));
+
+        let anchor = vec2(100.0, 100.0);
+        let under_mouse = cam.screen_to_world(anchor, viewport);
+        cam.zoom_at(anchor, viewport, 3.0);
+        expect_that!(cam.world_to_screen(under_mouse, viewport).array(), elements_are![near(100.0, 1e-3), near(100.0, 1e-3)]);
+
+        cam.pan_screen(vec2(30.0, 0.0));
+        expect_that!(cam.world_to_screen(under_mouse, viewport).array(), elements_are![near(130.0, 1e-3), near(100.0, 1e-3)]);
+    }
+}
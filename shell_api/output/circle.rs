@@ -0,0 +1,49 @@
+use crate::prelude::*;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Circle {
+    pub center: vec2f,
+    pub radius: f32,
+    pub stroke: RGBA,
+    pub fill: RGBA,
+    pub width: u8,
+    pub blend: Blend,
+}
+
+impl Circle {
+    pub fn new(center: impl IntoPos, radius: f32, stroke: impl Into<RGBA>) -> Self {
+        Self {
+            center: center.into_pos(),
+            radius,
+            stroke: stroke.into(),
+            fill: RGBA::TRANSPARENT,
+            width: 1,
+            blend: Blend::Normal,
+        }
+    }
+
+    /// Filled, without outline.
+    pub fn filled(center: impl IntoPos, radius: f32, fill: impl Into<RGBA>) -> Self {
+        Self::new(center, radius, RGBA::TRANSPARENT).with_fill(fill)
+    }
+
+    pub fn with_fill(self, fill: impl Into<RGBA>) -> Self {
+        self.with(|v| v.fill = fill.into())
+    }
+
+    pub fn with_width(self, width: u8) -> Self {
+        self.with(|v| v.width = width)
+    }
+
+    /// Scale stroke and fill alpha.
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|v| {
+            v.stroke = v.stroke.with_opacity(opacity);
+            v.fill = v.fill.with_opacity(opacity);
+        })
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|v| v.blend = blend)
+    }
+}
@@ -84,6 +84,11 @@ impl RGBA {
         let RGBA([r, g, b, _]) = *self;
         RGBA([r, g, b, a])
     }
+
+    /// Alpha scaled by `opacity` (0.0 ..= 1.0).
+    pub fn with_opacity(&self, opacity: f32) -> RGBA {
+        self.with_alpha((self.a() as f32 * opacity.clamp(0.0, 1.0)).round() as u8)
+    }
 }
 
 impl Into<[u8; 4]> for RGBA {
@@ -0,0 +1,203 @@
+use crate::prelude::*;
+use anyhow::{Context, Result, anyhow, bail};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// The 8x16 ASCII font in `font.png`, always available.
+pub static EMBEDDED_FONT: LazyLock<Font> = LazyLock::new(Font::embedded);
+
+/// 🔤 Bitmap font: glyph metrics + a glyph atlas sprite (drawn from the texture atlas like any other sprite).
+/// Loaded from AngelCode BMFont text files (`.fnt`), e.g. baked from a TTF by `atlas_builder --font`.
+/// Layout runs game-side, so games embed the `.fnt` (`include_str!`) in a `LazyLock<Font>` static, like `EMBEDDED_FONT`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+    /// Glyph atlas.
+    pub sprite: Sprite,
+    /// Nominal size (pixels) the glyphs were rendered at. Drawing at other sizes scales them.
+    pub size: f32,
+    /// Distance between baselines (pixels, at `size`).
+    pub line_height: f32,
+    pub glyphs: HashMap<char, Glyph>,
+    /// Advance adjustment between pairs of characters.
+    pub kerning: HashMap<(char, char), f32>,
+}
+
+/// A character in the glyph atlas.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Glyph {
+    /// Rectangle in the glyph atlas (may be empty, e.g. for space).
+    pub src_pos: vec2u16,
+    pub size: vec2u16,
+    /// Where to draw the glyph, relative to the pen position (top of the line).
+    pub offset: vec2f,
+    /// How far to move the pen afterwards.
+    pub advance: f32,
+}
+
+/// Drawn instead of characters that are not in the font.
+const REPLACEMENT_CHAR: char = '?';
+
+impl Font {
+    /// Parse a BMFont text file (`info`, `common`, `page`, `char` and `kerning` lines; a single page).
+    pub fn from_bmfont(fnt: &str) -> Result<Self> {
+        let mut font = Font {
+            sprite: sprite!("font"),
+            size: 0.0,
+            line_height: 0.0,
+            glyphs: HashMap::default(),
+            kerning: HashMap::default(),
+        };
+        for (i, line) in fnt.lines().enumerate() {
+            let (tag, attrs) = bmfont_line(line);
+            let get = |key: &str| attrs.iter().find(|(k, _)| *k == key).map(|(_, v)| *v).ok_or_else(|| anyhow!("line {}: missing {key}", i + 1));
+            let num = |key: &str| -> Result<f32> { get(key)?.parse::<f32>().with_context(|| format!("line {}: {key}", i + 1)) };
+            let chr = |key: &str| -> Result<char> { char::from_u32(num(key)? as u32).ok_or_else(|| anyhow!("line {}: bad {key}", i + 1)) };
+            match tag {
+                "info" => font.size = num("size")?.abs(), // 👈 negative means "match char height" in BMFont
+                "common" => font.line_height = num("lineHeight")?,
+                "page" if num("id")? != 0.0 => bail!("line {}: only single-page fonts are supported", i + 1),
+                "page" => {
+                    let file = get("file")?;
+                    let name = file.strip_suffix(".png").unwrap_or(file);
+                    font.sprite = Sprite {
+                        file: Str16::from_str(name).map_err(|e| anyhow!("page {file}: {e}"))?,
+                    };
+                }
+                "char" => {
+                    let glyph = Glyph {
+                        src_pos: vec2(num("x")?, num("y")?).as_u16(),
+                        size: vec2(num("width")?, num("height")?).as_u16(),
+                        offset: vec2(num("xoffset")?, num("yoffset")?),
+                        advance: num("xadvance")?,
+                    };
+                    font.glyphs.insert(chr("id")?, glyph);
+                }
+                "kerning" => _ = font.kerning.insert((chr("first")?, chr("second")?), num("amount")?),
+                _ => (), // chars, kernings (counts), comments
+            }
+        }
+        if font.glyphs.is_empty() || font.size <= 0.0 || font.line_height <= 0.0 {
+            bail!("not a BMFont file (no size, lineHeight or chars)")
+        }
+        Ok(font)
+    }
+
+    /// Write as BMFont text file (see `from_bmfont`), with page `<sprite>.png`.
+    pub fn to_bmfont(&self) -> String {
+        let mut fnt = format!("info size={} unicode=1\ncommon lineHeight={} pages=1\npage id=0 file=\"{}.png\"\n", self.size, self.line_height, self.sprite.file);
+        let mut glyphs = self.glyphs.iter().collect::<Vec<_>>();
+        glyphs.sort_by_key(|(c, _)| **c);
+        fnt += &format!("chars count={}\n", glyphs.len());
+        for (c, g) in glyphs {
+            let (x, y, w, h) = (g.src_pos.x(), g.src_pos.y(), g.size.x(), g.size.y());
+            fnt += &format!("char id={} x={x} y={y} width={w} height={h} xoffset={} yoffset={} xadvance={} page=0\n", *c as u32, g.offset.x(), g.offset.y(), g.advance);
+        }
+        let mut kerning = self.kerning.iter().collect::<Vec<_>>();
+        kerning.sort_by_key(|((a, b), _)| (*a, *b));
+        fnt += &format!("kernings count={}\n", kerning.len());
+        for ((a, b), amount) in kerning {
+            fnt += &format!("kerning first={} second={} amount={amount}\n", *a as u32, *b as u32);
+        }
+        fnt
+    }
+
+    /// The 8x16 ASCII font in `font.png` (16 characters per row).
+    fn embedded() -> Self {
+        let size = EMBEDDED_CHAR_SIZE.as_u16();
+        let columns = EMBEDDED_FONTMAP_SIZE.x() as u16 / size.x();
+        let glyphs = (0..128u16)
+            .map(|c| {
+                let glyph = Glyph {
+                    src_pos: vec2(c % columns, c / columns) * size,
+                    size,
+                    offset: vec2(0.0, 0.0),
+                    advance: size.x() as f32,
+                };
+                (char::from(c as u8), glyph)
+            })
+            .collect();
+        Font {
+            sprite: sprite!("font"),
+            size: size.y() as f32,
+            line_height: size.y() as f32,
+            glyphs,
+            kerning: HashMap::default(),
+        }
+    }
+
+    /// Glyph for a character, or `REPLACEMENT_CHAR` if not in the font.
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&REPLACEMENT_CHAR))
+    }
+
+    /// Pen advance for `c` (at nominal size), including kerning after `prev`.
+    pub fn advance(&self, prev: Option<char>, c: char) -> f32 {
+        self.glyph(c).map(|g| g.advance).unwrap_or(0.0) + self.kerning(prev, c)
+    }
+
+    /// Advance adjustment for `c` after `prev` (at nominal size).
+    pub fn kerning(&self, prev: Option<char>, c: char) -> f32 {
+        prev.and_then(|prev| self.kerning.get(&(prev, c))).copied().unwrap_or(0.0)
+    }
+}
+
+/// Split a BMFont line into tag and `key=value` pairs (values may be quoted, with spaces).
+fn bmfont_line(line: &str) -> (&str, Vec<(&str, &str)>) {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let mut attrs = Vec::new();
+    while let Some((key, after)) = rest.trim_start().split_once('=') {
+        let (value, next) = match after.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => after.split_once(char::is_whitespace).unwrap_or((after, "")),
+        };
+        attrs.push((key.trim(), value));
+        rest = next;
+    }
+    (tag, attrs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    const FNT: &str = r#"info face="DejaVu Sans" size=16 bold=0 italic=0 unicode=1 padding=0,0,0,0 spacing=1,1
+common lineHeight=19 base=15 scaleW=64 scaleH=64 pages=1 packed=0
+page id=0 file="dejavu_16.png"
+chars count=3
+char id=32   x=0  y=0  width=0  height=0  xoffset=0 yoffset=15 xadvance=5 page=0 chnl=15
+char id=65   x=1  y=1  width=11 height=12 xoffset=0 yoffset=3  xadvance=11 page=0 chnl=15
+char id=233  x=13 y=1  width=8  height=13 xoffset=1 yoffset=2  xadvance=9 page=0 chnl=15
+kernings count=1
+kerning first=65 second=233 amount=-1
+"#;
+
+    #[gtest]
+    fn parse_bmfont() {
+        let font = Font::from_bmfont(FNT).unwrap();
+        expect_that!(font.sprite, eq(sprite!("dejavu_16")));
+        expect_that!((font.size, font.line_height), eq((16.0, 19.0)));
+        expect_that!(font.glyphs.len(), eq(3));
+        expect_that!(
+            font.glyphs[&'é'],
+            eq(Glyph {
+                src_pos: vec2(13, 1),
+                size: vec2(8, 13),
+                offset: vec2(1.0, 2.0),
+                advance: 9.0
+            })
+        );
+        expect_that!(font.advance(Some('A'), 'é'), eq(8.0));
+        expect_that!(font.advance(None, 'é'), eq(9.0));
+        expect_that!(Font::from_bmfont(&font.to_bmfont()).unwrap(), eq(&font));
+        expect_that!(Font::from_bmfont("hello"), err(anything()));
+    }
+
+    #[gtest]
+    fn embedded_font() {
+        let font = &*EMBEDDED_FONT;
+        expect_that!(font.glyphs[&'A'].src_pos, eq(vec2(8, 64))); // 👈 row 4, column 1
+        expect_that!(font.glyph('λ'), eq(font.glyphs.get(&'?')));
+    }
+}
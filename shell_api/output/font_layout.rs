@@ -4,7 +4,7 @@ use crate::prelude::*;
 pub const EMBEDDED_CHAR_SIZE: vec2u8 = vec2(8, 16);
 
 /// Overall size (in pixels) of the embedded font map.
-const EMBEDDED_FONTMAP_SIZE: vec2u = vec2u(128, 128);
+pub(crate) const EMBEDDED_FONTMAP_SIZE: vec2u = vec2u(128, 128);
 
 /// Special symbol in font.png.
 #[allow(unused)]
@@ -22,52 +22,231 @@ pub const FONT_CROSSHAIR: &str = "\x06\x07";
 #[allow(unused)]
 pub const FONT_SHIELD: &str = "\x08\x09";
 
+/// Horizontal text alignment: within the wrap width if any, else relative to the text position.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Font, size, color and alignment to draw text with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub font: &'static Font,
+    /// Font size in pixels (the font's glyphs are scaled from `Font::size`).
+    pub size: f32,
+    pub color: RGBA,
+    pub align: Align,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self::new(&EMBEDDED_FONT)
+    }
+}
+
+impl TextStyle {
+    /// Font at its nominal size, white, left-aligned.
+    pub fn new(font: &'static Font) -> Self {
+        Self {
+            font,
+            size: font.size,
+            color: RGBA::WHITE,
+            align: Align::Left,
+        }
+    }
+
+    pub fn with_size(self, size: f32) -> Self {
+        self.with(|s| s.size = size)
+    }
+
+    pub fn with_color(self, color: impl Into<RGBA>) -> Self {
+        self.with(|s| s.color = color.into())
+    }
+
+    pub fn with_align(self, align: Align) -> Self {
+        self.with(|s| s.align = align)
+    }
+
+    fn scale(&self) -> f32 {
+        self.size / self.font.size
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.font.line_height * self.scale()
+    }
+
+    /// Size (pixels) of text laid out by `layout`: width of the widest line x number of lines.
+    pub fn text_size(&self, text: &str, wrap_width: Option<f32>) -> vec2f {
+        let lines = self.wrap(text, wrap_width);
+        let width = lines.iter().map(|line| self.line_width(line)).fold(0.0, f32::max);
+        vec2(width, lines.len() as f32 * self.line_height())
+    }
+
+    /// One sprite per visible character, top-left at `pos`.
+    /// Lines break at `\n`, and between words to fit `wrap_width` (if any; words that don't fit on a line of their own are split).
+    pub fn layout(&self, pos: vec2f, text: &str, wrap_width: Option<f32>) -> Vec<DrawSprite> {
+        let scale = self.scale();
+        let mut glyphs = Vec::with_capacity(text.len());
+        for (i, line) in self.wrap(text, wrap_width).into_iter().enumerate() {
+            let slack = wrap_width.unwrap_or(0.0) - self.line_width(line);
+            let x = match self.align {
+                Align::Left => 0.0,
+                Align::Center => slack / 2.0,
+                Align::Right => slack,
+            };
+            let mut pen = pos + vec2(x, i as f32 * self.line_height());
+            let mut prev = None;
+            for c in line.chars() {
+                pen[0] += self.font.kerning(prev, c) * scale;
+                if let Some(glyph) = self.font.glyph(c) {
+                    if glyph.size.x() != 0 && glyph.size.y() != 0 {
+                        let sprite = DrawSprite::at_pos(self.font.sprite, pen + glyph.offset * scale).with_src_pos(glyph.src_pos).with_size(glyph.size);
+                        glyphs.push(sprite.with_pivot((0, 0)).with_scale(scale).with_tint(self.color));
+                    }
+                    pen[0] += glyph.advance * scale;
+                }
+                prev = Some(c);
+            }
+        }
+        glyphs
+    }
+
+    /// Width (pixels) of a single line.
+    fn line_width(&self, line: &str) -> f32 {
+        let mut prev = None;
+        line.chars().map(|c| self.font.advance(prev.replace(c), c)).sum::<f32>() * self.scale()
+    }
+
+    /// Split text into lines, see `layout`.
+    fn wrap<'t>(&self, text: &'t str, wrap_width: Option<f32>) -> Vec<&'t str> {
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let Some(wrap_width) = wrap_width else {
+                lines.push(paragraph);
+                continue;
+            };
+            let mut rest = paragraph;
+            loop {
+                let fit = self.fit(rest, wrap_width);
+                if fit == rest.len() {
+                    lines.push(rest);
+                    break;
+                }
+                // 👇 break after the last word that fits, else mid-word.
+                let space = if rest[fit..].starts_with(' ') { Some(fit) } else { rest[..fit].rfind(' ') };
+                let (line, next) = match space {
+                    Some(space) if !rest[..space].trim_end().is_empty() => (rest[..space].trim_end(), &rest[space..]),
+                    _ => (&rest[..fit], &rest[fit..]),
+                };
+                lines.push(line);
+                rest = next.trim_start_matches(' ');
+                if rest.is_empty() {
+                    break; // 👈 only trailing spaces left (an empty paragraph still gets its line above)
+                }
+            }
+        }
+        lines
+    }
+
+    /// Byte length of the longest prefix that fits in `width` (at least one character).
+    fn fit(&self, line: &str, width: f32) -> usize {
+        let scale = self.scale();
+        let mut pen = 0.0;
+        let mut prev = None;
+        for (i, c) in line.char_indices() {
+            pen += self.font.advance(prev.replace(c), c) * scale;
+            if pen > width && i != 0 {
+                return i;
+            }
+        }
+        line.len()
+    }
+}
+
 /// A mesh for rendering text at a given position on the screen (using the embedded bitmap font).
-/// Wraps long lines as shown below:
+/// Wraps long lines (between words) as shown below:
 ///
 ///   viewport size
 ///  +----------------+
 ///  |  `pos`+        |
-///  |        your tex|
-///  |        t here  |
+///  |        your    |
+///  |        text    |
 ///  |                |
 ///  +----------------+
 ///
 impl Out {
-    pub fn draw_text_screen(&mut self, layer: u8, pos: impl Into<vec2i>, text: impl AsRef<str>) {
-        draw_text(self, layer, pos.into(), text.as_ref());
-
-        fn draw_text(out: &mut Out, layer: u8, pos: vec2i, text: &str) {
-            let viewport_size = out.viewport_size.as_i32();
-            let char_stride = EMBEDDED_CHAR_SIZE.as_i32();
-
-            let mut char_pos = pos;
-            for &char in text.as_bytes() {
-                // newline
-                if char == b'\n' {
-                    char_pos[0] = pos.x();
-                    char_pos[1] += char_stride.y();
-                    continue;
-                }
-
-                // wrap long lines
-                if char_pos.x() > viewport_size.x() - char_stride.x() {
-                    char_pos[0] = pos.x();
-                    char_pos[1] += char_stride.y();
-                }
+    pub fn draw_text_screen(&mut self, layer: u8, pos: impl IntoPos, text: impl AsRef<str>) {
+        let pos = pos.into_pos();
+        let glyphs = TextStyle::default().layout(pos, text.as_ref(), Some(self.viewport_size.x() as f32 - pos.x()));
+        self.layer(layer).sprites.extend(glyphs);
+    }
 
-                let src_pos = chr_tex_pos_16x8(char, EMBEDDED_CHAR_SIZE);
-                out.draw_sprite_screen_with_source(layer, sprite!("font"), src_pos, EMBEDDED_CHAR_SIZE, char_pos);
+    /// Draw text in screen coordinates, with any font, size, color and alignment (no wrapping).
+    pub fn draw_text_styled(&mut self, layer: u8, pos: impl IntoPos, text: impl AsRef<str>, style: TextStyle) {
+        let glyphs = style.layout(pos.into_pos(), text.as_ref(), None);
+        self.layer(layer).sprites.extend(glyphs);
+    }
 
-                char_pos[0] += char_stride.x();
-            }
-        }
+    /// Draw text in screen coordinates, word-wrapped and aligned within the box's width.
+    /// Lines that don't fit the box's height are still drawn (see `TextStyle::text_size`).
+    pub fn draw_text_box(&mut self, layer: u8, bounds: impl IntoBounds, text: impl AsRef<str>, style: TextStyle) {
+        let bounds = bounds.into_bounds();
+        let glyphs = style.layout(bounds.min, text.as_ref(), Some(bounds.size().x()));
+        self.layer(layer).sprites.extend(glyphs);
     }
 }
 
-/// Pixel position (top-left corner) of an ascii character in the embedded font map.
-fn chr_tex_pos_16x8(char: u8, char_size: vec2u8) -> vec2u8 {
-    let x = char & 0xf;
-    let y = char >> 4;
-    vec2(x, y) * char_size
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    fn positions(glyphs: &[DrawSprite]) -> Vec<(f32, f32)> {
+        glyphs.iter().map(|g| (g.pos.x(), g.pos.y())).collect()
+    }
+
+    #[gtest]
+    fn word_wrap() {
+        let style = TextStyle::default();
+        expect_that!(style.wrap("hello world", Some(88.0)), eq(&["hello world"]));
+        expect_that!(style.wrap("hello world", Some(80.0)), eq(&["hello", "world"]));
+        expect_that!(style.wrap("hello ", Some(40.0)), eq(&["hello"])); // 👈 no empty line for trailing spaces
+        expect_that!(style.wrap("hello   ", Some(40.0)), eq(&["hello"]));
+        expect_that!(style.wrap("a\n\nb", None), eq(&["a", "", "b"]));
+        expect_that!(style.wrap("abcdefgh", Some(24.0)), eq(&["abc", "def", "gh"])); // 👈 split long words
+        expect_that!(style.wrap("ab cd", Some(1.0)), eq(&["a", "b", "c", "d"]));
+        expect_that!(style.wrap("héllo wörld", Some(80.0)), eq(&["héllo", "wörld"])); // 👈 UTF-8
+    }
+
+    #[gtest]
+    fn text_size() {
+        let style = TextStyle::default();
+        expect_that!(style.text_size("hello world", None), eq(vec2(88.0, 16.0)));
+        expect_that!(style.text_size("hello world", Some(80.0)), eq(vec2(40.0, 32.0)));
+        expect_that!(style.with_size(32.0).text_size("hi", None), eq(vec2(32.0, 32.0)));
+    }
+
+    #[gtest]
+    fn align_and_scale() {
+        let style = TextStyle::default().with_align(Align::Right);
+        expect_that!(positions(&style.layout(vec2(10.0, 20.0), "ab", Some(100.0))), eq(&[(94.0, 20.0), (102.0, 20.0)]));
+        let style = TextStyle::default().with_align(Align::Center).with_size(32.0);
+        let glyphs = style.layout(vec2(0.0, 0.0), "ab", None);
+        expect_that!(positions(&glyphs), eq(&[(-16.0, 0.0), (0.0, 0.0)]));
+        expect_that!(glyphs[0].scale, eq(2.0));
+        expect_that!(glyphs.iter().map(|g| g.corners(vec2(8.0, 16.0))[0]).collect::<Vec<_>>(), eq(&[vec2(-16.0, 0.0), vec2(0.0, 0.0)]));
+    }
+
+    #[gtest]
+    fn spaces_draw_nothing() {
+        static FONT: std::sync::LazyLock<Font> = std::sync::LazyLock::new(|| {
+            let mut font = EMBEDDED_FONT.clone();
+            font.glyphs.get_mut(&' ').unwrap().size = vec2(0, 0);
+            font
+        });
+        expect_that!(TextStyle::new(&FONT).layout(vec2(0.0, 0.0), "a b", None).len(), eq(2));
+    }
 }
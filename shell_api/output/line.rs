@@ -1,20 +1,28 @@
 use crate::prelude::*;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Line {
-    pub start: vec2i,
-    pub end: vec2i,
+    pub start: vec2f,
+    pub end: vec2f,
     pub color: RGBA,
     pub width: u8,
+    pub blend: Blend,
 }
 
 impl Line {
-    pub fn new(start: vec2i, end: vec2i) -> Self {
-        Self { start, end, color: RGBA::BLACK, width: 1 }
+    pub fn new(start: impl IntoPos, end: impl IntoPos) -> Self {
+        Self {
+            start: start.into_pos(),
+            end: end.into_pos(),
+            color: RGBA::BLACK,
+            width: 1,
+            blend: Blend::Normal,
+        }
     }
 
     #[must_use = "does not modify original"]
-    pub fn translated(self, delta: vec2i) -> Self {
+    pub fn translated(self, delta: impl IntoPos) -> Self {
+        let delta = delta.into_pos();
         self.with(|v| {
             v.start += delta;
             v.end += delta
@@ -28,4 +36,12 @@ impl Line {
     pub fn with_width(self, width: u8) -> Self {
         self.with(|v| v.width = width)
     }
+
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|v| v.color = v.color.with_opacity(opacity))
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|v| v.blend = blend)
+    }
 }
@@ -1,13 +1,35 @@
+mod animation;
+mod atlas;
+mod blend;
+mod camera;
+mod circle;
 mod color;
+mod font;
 mod font_layout;
 mod line;
 mod output;
+mod path;
+mod polygon;
+mod pos;
 mod rectangle;
+mod request;
 mod sprite;
+mod text;
 
+pub use animation::*;
+pub use atlas::*;
+pub use blend::*;
+pub use camera::*;
+pub use circle::*;
 pub use color::*;
+pub use font::*;
 pub use font_layout::*;
 pub use line::*;
 pub use output::*;
+pub use path::*;
+pub use polygon::*;
+pub use pos::*;
 pub use rectangle::*;
+pub use request::*;
 pub use sprite::*;
+pub use text::*;
@@ -34,40 +34,74 @@ pub const L_CLI: u8 = 8;
 
 /// Scenegraph, sounds, etc. to output after a tick.
 /// Sent to the browser who will render it.
+///
+/// Each layer has a world-space part (`world_layers`, drawn through the `camera`)
+/// and a screen-space part (`layers`, drawn on top of it).
 #[derive(Default, Debug, PartialEq)]
 pub struct Out {
-    pub camera_pos: vec2i,
+    pub camera: Camera,
     pub viewport_size: vec2u,
     pub layers: Vec<Layer>,
+    pub world_layers: Vec<Layer>,
     pub bloom: bool,
     pub vignette: bool,
     pub debug: String,
+    pub requests: Vec<ShellRequest>,
 }
 
 /// Command to draw a sprite.
 #[derive(Debug, PartialEq)]
 pub struct DrawSprite {
     pub sprite: Sprite,
-    pub pos: vec2i,
-    pub dst_size: Option<vec2<NonZeroU8>>,
-    pub src_pos: Option<vec2u8>,
+    pub pos: vec2f,
+    pub dst_size: Option<vec2<NonZeroU16>>,
+    pub src_pos: Option<vec2u16>,
+    /// Draw the current frame of this animation, at given time (seconds), instead of `sprite`.
+    /// The renderer looks up the frame in the animation's sprite sheet (see `SpriteSheet`).
+    pub animation: Option<(Animation, f32)>,
+    /// Radians, clockwise, around `pivot`.
     pub rot: f32,
+    /// Multiplied with the sprite's colors (`RGBA::WHITE`: unchanged).
+    pub tint: RGBA,
+    /// Mirror horizontally, vertically (within the sprite's rectangle, before scaling and rotation).
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Size multiplier, around `pivot`.
+    pub scale: f32,
+    /// Point that stays put when rotating and scaling, as a fraction of the size: (0, 0) is top-left, (0.5, 0.5) (default) the center.
+    pub pivot: vec2f,
+    /// 0.0 (invisible) ..= 1.0 (opaque).
+    pub opacity: f32,
+    pub blend: Blend,
 }
 
 impl DrawSprite {
     /// Draw sprite at position. Natural size.
-    pub fn at_pos(sprite: Sprite, pos: vec2i) -> Self {
+    pub fn at_pos(sprite: Sprite, pos: impl IntoPos) -> Self {
         Self {
             sprite,
-            pos,
+            pos: pos.into_pos(),
             dst_size: None,
             src_pos: None,
+            animation: None,
             rot: 0.0,
+            tint: RGBA::WHITE,
+            flip_x: false,
+            flip_y: false,
+            scale: 1.0,
+            pivot: vec2(0.5, 0.5),
+            opacity: 1.0,
+            blend: Blend::Normal,
         }
     }
 
-    pub fn with_size(self, dst_size: vec2u8) -> Self {
-        if let (Some(x), Some(y)) = (NonZeroU8::new(dst_size.x()), NonZeroU8::new(dst_size.y())) {
+    /// Draw animation frame at position (see `DrawSprite::animation`). Natural (frame) size.
+    pub fn animated(animation: Animation, time: f32, pos: impl IntoPos) -> Self {
+        Self::at_pos(Sprite { file: animation.name }, pos).with(|s| s.animation = Some((animation, time)))
+    }
+
+    pub fn with_size(self, dst_size: vec2u16) -> Self {
+        if let (Some(x), Some(y)) = (NonZeroU16::new(dst_size.x()), NonZeroU16::new(dst_size.y())) {
             self.with(|s| s.dst_size = Some(vec2(x, y)))
         } else {
             debug_assert!(dst_size.x() != 0 && dst_size.y() != 0, "zero dst_size");
@@ -75,13 +109,63 @@ impl DrawSprite {
         }
     }
 
-    pub fn with_src_pos(self, src_pos: vec2u8) -> Self {
+    pub fn with_src_pos(self, src_pos: vec2u16) -> Self {
         self.with(|s| s.src_pos = Some(src_pos))
     }
 
     pub fn with_rot(self, rot: f32) -> Self {
         self.with(|s| s.rot = rot)
     }
+
+    pub fn with_tint(self, tint: impl Into<RGBA>) -> Self {
+        self.with(|s| s.tint = tint.into())
+    }
+
+    pub fn with_flip(self, flip_x: bool, flip_y: bool) -> Self {
+        self.with(|s| (s.flip_x, s.flip_y) = (flip_x, flip_y))
+    }
+
+    pub fn with_scale(self, scale: f32) -> Self {
+        self.with(|s| s.scale = scale)
+    }
+
+    pub fn with_pivot(self, pivot: impl IntoPos) -> Self {
+        self.with(|s| s.pivot = pivot.into_pos())
+    }
+
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|s| s.opacity = opacity.clamp(0.0, 1.0))
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|s| s.blend = blend)
+    }
+
+    /// Where the source image's corners (top-left, top-right, bottom-right, bottom-left) end up,
+    /// given the unscaled size on screen (`dst_size`, or else the source size).
+    /// 👉 Both renderers place sprites with this, so that they agree on flip, scale, rotation and pivot.
+    pub fn corners(&self, size: vec2f) -> [vec2f; 4] {
+        let pivot = self.pivot * size;
+        let (sin, cos) = self.rot.sin_cos();
+        [vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(1.0, 1.0), vec2(0.0, 1.0)].map(|corner: vec2f| {
+            let x = if self.flip_x { 1.0 - corner.x() } else { corner.x() };
+            let y = if self.flip_y { 1.0 - corner.y() } else { corner.y() };
+            let p = (vec2(x, y) * size - pivot) * self.scale;
+            self.pos + pivot + vec2(p.x() * cos - p.y() * sin, p.x() * sin + p.y() * cos)
+        })
+    }
+
+    /// Affine transform `[a, b, c, d, e, f]` (as in canvas `setTransform`) that maps the unit square onto `corners`.
+    pub fn unit_square_transform(corners: [vec2f; 4]) -> [f32; 6] {
+        let [origin, right, _, down] = corners;
+        let (x, y) = (right - origin, down - origin);
+        [x.x(), x.y(), y.x(), y.y(), origin.x(), origin.y()]
+    }
+
+    /// Color to multiply the sprite with: `tint`, faded by `opacity`.
+    pub fn color(&self) -> RGBA {
+        self.tint.with_opacity(self.opacity)
+    }
 }
 
 #[derive(Default, Debug, PartialEq)]
@@ -89,6 +173,10 @@ pub struct Layer {
     pub sprites: Vec<DrawSprite>,
     pub lines: Vec<Line>,
     pub rectangles: Vec<Rectangle>,
+    pub circles: Vec<Circle>,
+    pub polygons: Vec<Polygon>,
+    pub paths: Vec<Path>,
+    pub texts: Vec<Text>,
 }
 
 impl Out {
@@ -98,40 +186,78 @@ impl Out {
 
     pub fn clear(&mut self) {
         self.layers.iter_mut().for_each(Layer::clear);
+        self.world_layers.iter_mut().for_each(Layer::clear);
         self.debug.clear();
+        self.requests.clear();
     }
 
     /// Draw sprite in world coordinates (i.e. taking into account camera).
-    pub fn draw_sprite(&mut self, layer: u8, sprite: Sprite, world_pos: vec2i) {
-        self.draw_sprite_screen(layer, sprite, world_pos - self.camera_pos);
+    pub fn draw_sprite(&mut self, layer: u8, sprite: Sprite, world_pos: impl IntoPos) {
+        self.world_layer(layer).sprites.push(DrawSprite::at_pos(sprite, world_pos));
     }
 
-    pub fn draw_sprite_rot(&mut self, layer: u8, sprite: Sprite, world_pos: vec2i, rot: f32) {
-        let pos = world_pos - self.camera_pos;
-        self.push_sprite(layer, DrawSprite { sprite, pos, dst_size: None, src_pos: None, rot });
+    /// Draw the frame of an animation at given time (seconds), in world coordinates.
+    pub fn draw_animation(&mut self, layer: u8, animation: Animation, time: f32, world_pos: impl IntoPos) {
+        self.world_layer(layer).sprites.push(DrawSprite::animated(animation, time, world_pos));
     }
 
-    pub fn draw_text(&mut self, layer: u8, text: impl AsRef<str>, world_pos: vec2i) {
-        let text = text.as_ref();
-        self.draw_text_screen(layer, world_pos - self.camera_pos, text);
+    pub fn draw_sprite_rot(&mut self, layer: u8, sprite: Sprite, world_pos: impl IntoPos, rot: f32) {
+        self.world_layer(layer).sprites.push(DrawSprite::at_pos(sprite, world_pos).with_rot(rot));
+    }
+
+    /// Draw text in world coordinates (no wrapping).
+    pub fn draw_text(&mut self, layer: u8, text: impl AsRef<str>, world_pos: impl IntoPos) {
+        let glyphs = TextStyle::default().layout(world_pos.into_pos(), text.as_ref(), None);
+        self.world_layer(layer).sprites.extend(glyphs);
     }
 
     /// Draw line in world coordinates (i.e. taking into account camera).
     pub fn draw_line(&mut self, layer: u8, line: Line) {
-        self.draw_line_screen(layer, line.translated(-self.camera_pos));
+        self.world_layer(layer).lines.push(line);
     }
 
     /// Draw rectangle in world coordinates (i.e. taking into account camera).
     pub fn draw_rect(&mut self, layer: u8, rect: Rectangle) {
-        self.draw_rect_screen(layer, rect.translated(-self.camera_pos));
+        self.world_layer(layer).rectangles.push(rect);
+    }
+
+    /// Draw circle in world coordinates (i.e. taking into account camera).
+    pub fn draw_circle(&mut self, layer: u8, circle: Circle) {
+        self.world_layer(layer).circles.push(circle);
+    }
+
+    /// Draw polygon in world coordinates (i.e. taking into account camera).
+    pub fn draw_polygon(&mut self, layer: u8, polygon: Polygon) {
+        self.world_layer(layer).polygons.push(polygon);
+    }
+
+    /// Draw polyline or curve in world coordinates (i.e. taking into account camera).
+    pub fn draw_path(&mut self, layer: u8, path: Path) {
+        self.world_layer(layer).paths.push(path);
+    }
+
+    /// Draw text in world coordinates, with the renderer's font (color, scale), see `Text`.
+    pub fn draw_text_ex(&mut self, layer: u8, text: Text) {
+        self.world_layer(layer).texts.push(text);
+    }
+
+    /// Mouse (or other screen) position in world coordinates.
+    pub fn screen_to_world(&self, screen: vec2i) -> vec2f {
+        self.camera.screen_to_world(screen.as_f32(), self.viewport_size)
+    }
+
+    /// Layers in drawing order, with `true` for world space (drawn through the camera).
+    pub fn iter_layers(&self) -> impl Iterator<Item = (&Layer, bool)> {
+        let n = usize::max(self.layers.len(), self.world_layers.len());
+        (0..n).flat_map(|i| [self.world_layers.get(i).map(|l| (l, true)), self.layers.get(i).map(|l| (l, false))]).flatten()
     }
 
     /// Draw sprite in screen coordinates (i.e. ignoring camera).
-    pub fn draw_sprite_screen(&mut self, layer: u8, sprite: Sprite, screen_pos: vec2i) {
+    pub fn draw_sprite_screen(&mut self, layer: u8, sprite: Sprite, screen_pos: impl IntoPos) {
         self.push_sprite(layer, DrawSprite::at_pos(sprite, screen_pos));
     }
 
-    pub fn draw_sprite_screen_with_size(&mut self, layer: u8, sprite: Sprite, pos: vec2i, dst_size: vec2u8) {
+    pub fn draw_sprite_screen_with_size(&mut self, layer: u8, sprite: Sprite, pos: impl IntoPos, dst_size: vec2u16) {
         self.push_sprite(layer, DrawSprite::at_pos(sprite, pos).with_size(dst_size));
     }
 
@@ -147,7 +273,7 @@ impl Out {
     /// |     +----+        |
     /// |                   |
     /// +-------------------+
-    pub fn draw_sprite_screen_with_source(&mut self, layer: u8, sprite: Sprite, src_pos: vec2u8, size: vec2u8, dst_pos: vec2i) {
+    pub fn draw_sprite_screen_with_source(&mut self, layer: u8, sprite: Sprite, src_pos: vec2u16, size: vec2u16, dst_pos: impl IntoPos) {
         self.layer(layer).sprites.push(DrawSprite::at_pos(sprite, dst_pos).with_src_pos(src_pos).with_size(size));
     }
 
@@ -160,13 +286,41 @@ impl Out {
         self.layer(layer).rectangles.push(rect.into());
     }
 
-    fn layer(&mut self, layer: u8) -> &mut Layer {
-        debug_assert!(layer <= 100, "too many layers");
-        while self.layers.len() <= layer.as_() {
-            self.layers.push(Layer::default());
-        }
-        &mut self.layers[layer as usize]
+    /// Draw circle in screen coordinates (i.e. ignoring camera).
+    pub fn draw_circle_screen(&mut self, layer: u8, circle: Circle) {
+        self.layer(layer).circles.push(circle);
+    }
+
+    /// Draw polygon in screen coordinates (i.e. ignoring camera).
+    pub fn draw_polygon_screen(&mut self, layer: u8, polygon: Polygon) {
+        self.layer(layer).polygons.push(polygon);
+    }
+
+    /// Draw polyline or curve in screen coordinates (i.e. ignoring camera).
+    pub fn draw_path_screen(&mut self, layer: u8, path: Path) {
+        self.layer(layer).paths.push(path);
+    }
+
+    /// Draw text in screen coordinates, with the renderer's font (color, scale), see `Text`.
+    pub fn draw_text_ex_screen(&mut self, layer: u8, text: Text) {
+        self.layer(layer).texts.push(text);
+    }
+
+    pub(crate) fn layer(&mut self, layer: u8) -> &mut Layer {
+        get_layer(&mut self.layers, layer)
+    }
+
+    fn world_layer(&mut self, layer: u8) -> &mut Layer {
+        get_layer(&mut self.world_layers, layer)
+    }
+}
+
+fn get_layer(layers: &mut Vec<Layer>, layer: u8) -> &mut Layer {
+    debug_assert!(layer <= 100, "too many layers");
+    while layers.len() <= layer.as_() {
+        layers.push(Layer::default());
     }
+    &mut layers[layer as usize]
 }
 
 impl Layer {
@@ -174,6 +328,57 @@ impl Layer {
         self.sprites.clear();
         self.lines.clear();
         self.rectangles.clear();
+        self.circles.clear();
+        self.polygons.clear();
+        self.paths.clear();
+        self.texts.clear();
         debug_assert!(self == &Self::default(), "Layer::clear is correct");
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    fn sprite() -> DrawSprite {
+        DrawSprite::at_pos(sprite!("crab"), (10, 20))
+    }
+
+    fn approx(corners: [vec2f; 4], want: [(f32, f32); 4]) {
+        for (got, want) in corners.into_iter().zip(want) {
+            assert!((got - want.into_pos()).len() < 1e-4, "got {corners:?}, want {want:?}");
+        }
+    }
+
+    #[gtest]
+    fn corners() {
+        let size = vec2(4.0, 2.0);
+        approx(sprite().corners(size), [(10.0, 20.0), (14.0, 20.0), (14.0, 22.0), (10.0, 22.0)]);
+        approx(sprite().with_flip(true, false).corners(size), [(14.0, 20.0), (10.0, 20.0), (10.0, 22.0), (14.0, 22.0)]);
+        approx(sprite().with_flip(false, true).corners(size), [(10.0, 22.0), (14.0, 22.0), (14.0, 20.0), (10.0, 20.0)]);
+        approx(sprite().with_scale(2.0).corners(size), [(8.0, 19.0), (16.0, 19.0), (16.0, 23.0), (8.0, 23.0)]);
+        approx(sprite().with_scale(2.0).with_pivot((0, 0)).corners(size), [(10.0, 20.0), (18.0, 20.0), (18.0, 24.0), (10.0, 24.0)]);
+        // 👇 quarter turn clockwise around the center (12, 21)
+        approx(sprite().with_rot(std::f32::consts::FRAC_PI_2).corners(size), [(13.0, 19.0), (13.0, 23.0), (11.0, 23.0), (11.0, 19.0)]);
+        approx(sprite().with_rot(std::f32::consts::PI).with_pivot((0, 0)).corners(size), [(10.0, 20.0), (6.0, 20.0), (6.0, 18.0), (10.0, 18.0)]);
+        approx(sprite().with_flip(true, false).with_scale(0.5).with_pivot((1, 1)).corners(size), [(14.0, 21.0), (12.0, 21.0), (12.0, 22.0), (14.0, 22.0)]);
+    }
+
+    /// The web shell draws through `unit_square_transform`, macroquad through `corners`: they must agree.
+    /// 👉 How each shell feeds them to its renderer is tested there (`sprite_quad`, `sprite_on_unit_square`).
+    #[gtest]
+    fn renderer_parity() {
+        let cmd = sprite().with_flip(true, true).with_scale(1.5).with_pivot((0.25, 1.0)).with_rot(0.7);
+        let corners = cmd.corners(vec2(24.0, 16.0));
+        let [a, b, c, d, e, f] = DrawSprite::unit_square_transform(corners);
+        let transformed = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| (a * x + c * y + e, b * x + d * y + f));
+        approx(corners, transformed);
+    }
+
+    #[gtest]
+    fn tint_and_opacity() {
+        expect_that!(sprite().color(), eq(RGBA::WHITE));
+        expect_that!(sprite().with_tint(RGBA::RED).with_opacity(0.5).color(), eq(RGBA([255, 0, 0, 128])));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::prelude::*;
+
+/// Stroked polyline or curve (cubic Bézier segments), drawn with round joins and caps.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Path {
+    pub start: vec2f,
+    pub segments: Vec<PathSegment>,
+    pub closed: bool,
+    pub color: RGBA,
+    pub width: u8,
+    pub blend: Blend,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PathSegment {
+    Line(vec2f),
+    Cubic { c1: vec2f, c2: vec2f, end: vec2f },
+}
+
+/// Number of line segments a Bézier segment is flattened into (by renderers without native curves).
+pub const CURVE_SUBDIVISIONS: usize = 12;
+
+impl Path {
+    pub fn new(start: impl Into<vec2f>) -> Self {
+        Self {
+            start: start.into(),
+            segments: vec![],
+            closed: false,
+            color: RGBA::BLACK,
+            width: 1,
+            blend: Blend::Normal,
+        }
+    }
+
+    /// Straight lines through all points.
+    pub fn polyline(points: impl IntoIterator<Item = vec2f>) -> Self {
+        let mut points = points.into_iter();
+        let start = points.next().unwrap_or_default();
+        Self::new(start).with(|p| p.segments.extend(points.map(PathSegment::Line)))
+    }
+
+    /// Smooth curve through all points (Catmull-Rom spline, as cubic Bézier segments).
+    pub fn smooth(points: &[vec2f]) -> Self {
+        let Some(&start) = points.first() else { return Self::new(vec2f::ZERO) };
+        let at = |i: isize| points[i.clamp(0, points.len() as isize - 1) as usize];
+        let mut path = Self::new(start);
+        for i in 0..points.len() as isize - 1 {
+            let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+            path = path.cubic_to(p1 + (p2 - p0) / 6.0, p2 - (p3 - p1) / 6.0, p2);
+        }
+        path
+    }
+
+    pub fn line_to(self, end: impl Into<vec2f>) -> Self {
+        self.with(|p| p.segments.push(PathSegment::Line(end.into())))
+    }
+
+    pub fn cubic_to(self, c1: impl Into<vec2f>, c2: impl Into<vec2f>, end: impl Into<vec2f>) -> Self {
+        let (c1, c2, end) = (c1.into(), c2.into(), end.into());
+        self.with(|p| p.segments.push(PathSegment::Cubic { c1, c2, end }))
+    }
+
+    /// Connect the end back to the start.
+    pub fn closed(self) -> Self {
+        self.with(|p| p.closed = true)
+    }
+
+    pub fn with_color(self, color: RGBA) -> Self {
+        self.with(|v| v.color = color)
+    }
+
+    pub fn with_width(self, width: u8) -> Self {
+        self.with(|v| v.width = width)
+    }
+
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|v| v.color = v.color.with_opacity(opacity))
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|v| v.blend = blend)
+    }
+
+    /// Points along the path, curves approximated by `CURVE_SUBDIVISIONS` straight lines each.
+    /// Closed paths end with the start point.
+    pub fn flatten(&self) -> Vec<vec2f> {
+        let mut points = vec![self.start];
+        let mut pos = self.start;
+        for &segment in &self.segments {
+            match segment {
+                PathSegment::Line(end) => points.push(end),
+                PathSegment::Cubic { c1, c2, end } => {
+                    points.extend((1..=CURVE_SUBDIVISIONS).map(|i| cubic(pos, c1, c2, end, i as f32 / CURVE_SUBDIVISIONS as f32)));
+                }
+            }
+            pos = *points.last().unwrap();
+        }
+        if self.closed {
+            points.push(self.start);
+        }
+        points
+    }
+}
+
+/// Point at `t` (0..=1) on a cubic Bézier curve.
+fn cubic(p0: vec2f, c1: vec2f, c2: vec2f, p1: vec2f, t: f32) -> vec2f {
+    let s = 1.0 - t;
+    (s * s * s) * p0 + (3.0 * s * s * t) * c1 + (3.0 * s * t * t) * c2 + (t * t * t) * p1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn smooth_passes_through_points() {
+        let points = [vec2(0.0, 0.0), vec2(10.0, 5.0), vec2(20.0, 0.0), vec2(30.0, 10.0)];
+        let flat = Path::smooth(&points).flatten();
+        expect_that!(flat.len(), eq(1 + 3 * CURVE_SUBDIVISIONS));
+        for (i, p) in points.iter().enumerate() {
+            expect_that!(flat[i * CURVE_SUBDIVISIONS].distance_to(*p), near(0.0, 1e-4));
+        }
+    }
+
+    #[gtest]
+    fn flatten_closed_polyline() {
+        let path = Path::polyline([vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(1.0, 1.0)]).closed();
+        expect_that!(path.flatten(), elements_are![eq(&vec2(0.0, 0.0)), eq(&vec2(1.0, 0.0)), eq(&vec2(1.0, 1.0)), eq(&vec2(0.0, 0.0))]);
+    }
+}
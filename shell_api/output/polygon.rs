@@ -0,0 +1,43 @@
+use crate::prelude::*;
+
+/// Closed polygon. Renderers may only fill convex polygons correctly (macroquad: triangle fan).
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polygon {
+    pub points: Vec<vec2f>,
+    pub stroke: RGBA,
+    pub fill: RGBA,
+    pub width: u8,
+    pub blend: Blend,
+}
+
+impl Polygon {
+    pub fn new<P: IntoPos>(points: impl IntoIterator<Item = P>, stroke: impl Into<RGBA>) -> Self {
+        Self {
+            points: points.into_iter().map(P::into_pos).collect(),
+            stroke: stroke.into(),
+            fill: RGBA::TRANSPARENT,
+            width: 1,
+            blend: Blend::Normal,
+        }
+    }
+
+    pub fn with_fill(self, fill: impl Into<RGBA>) -> Self {
+        self.with(|v| v.fill = fill.into())
+    }
+
+    pub fn with_width(self, width: u8) -> Self {
+        self.with(|v| v.width = width)
+    }
+
+    /// Scale stroke and fill alpha.
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|v| {
+            v.stroke = v.stroke.with_opacity(opacity);
+            v.fill = v.fill.with_opacity(opacity);
+        })
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|v| v.blend = blend)
+    }
+}
@@ -0,0 +1,52 @@
+use crate::prelude::*;
+use num_traits::AsPrimitive;
+
+/// Drawing position. Primitives store float coordinates, so that slowly moving things (physics: `vec2f`)
+/// don't snap between pixels. Integer positions (UI: `vec2i`, `(i32, i32)`) convert implicitly.
+pub trait IntoPos {
+    fn into_pos(self) -> vec2f;
+}
+
+impl<T: AsPrimitive<f32>> IntoPos for vec2<T> {
+    fn into_pos(self) -> vec2f {
+        self.as_f32()
+    }
+}
+
+impl<T: AsPrimitive<f32>> IntoPos for (T, T) {
+    fn into_pos(self) -> vec2f {
+        vec2(self.0.as_(), self.1.as_())
+    }
+}
+
+/// Like `IntoPos`, for rectangles: `Bounds2Df`, `Bounds2Di`, or a `(min, max)` tuple.
+pub trait IntoBounds {
+    fn into_bounds(self) -> Bounds2Df;
+}
+
+impl<T: AsPrimitive<f32>> IntoBounds for Bounds2D<T> {
+    fn into_bounds(self) -> Bounds2Df {
+        Bounds2D { min: self.min.as_f32(), max: self.max.as_f32() }
+    }
+}
+
+impl<P: IntoPos> IntoBounds for (P, P) {
+    fn into_bounds(self) -> Bounds2Df {
+        Bounds2D::new(self.0.into_pos(), self.1.into_pos())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn int_and_float_positions() {
+        expect_that!(vec2(1, 2).into_pos(), eq(vec2(1.0, 2.0)));
+        expect_that!((1.5, 2.25).into_pos(), eq(vec2(1.5, 2.25)));
+        expect_that!(Line::new((0, 0), vec2(0.5, 0.5)).translated((1, 1)).end, eq(vec2(1.5, 1.5)));
+        expect_that!(Rectangle::new(((0, 0), (4, 2)), RGBA::WHITE).bounds, eq(Bounds2D::new(vec2(0.0, 0.0), vec2(4.0, 2.0))));
+        expect_that!(RGBA::WHITE.with_opacity(0.5).a(), eq(128));
+    }
+}
@@ -1,42 +1,56 @@
 use crate::prelude::*;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Rectangle {
-    pub bounds: Bounds,
+    pub bounds: Bounds2Df,
     pub stroke: RGBA,
     pub fill: RGBA,
+    pub blend: Blend,
 }
 
 impl Rectangle {
-    pub fn new(bounds: impl Into<Bounds>, stroke: impl Into<RGBA>) -> Self {
+    pub fn new(bounds: impl IntoBounds, stroke: impl Into<RGBA>) -> Self {
         Self {
-            bounds: bounds.into(),
+            bounds: bounds.into_bounds(),
             stroke: stroke.into(),
             fill: RGBA::TRANSPARENT,
+            blend: Blend::Normal,
         }
     }
 
     /// Like `new`, but pass the top-left position and size.
-    pub fn with_size(pos: impl Into<vec2i>, size: impl Into<vec2i>, stroke: impl Into<RGBA>) -> Self {
-        Self::new(Bounds::with_size(pos.into(), size.into()), stroke)
+    pub fn with_size(pos: impl IntoPos, size: impl IntoPos, stroke: impl Into<RGBA>) -> Self {
+        Self::new(Bounds2D::with_size(pos.into_pos(), size.into_pos()), stroke)
     }
 
     /// Like `new`, but pass the center position and radius (half size).
-    pub fn with_radius(pos: impl Into<vec2i>, size: impl Into<vec2i>, stroke: impl Into<RGBA>) -> Self {
-        Self::new(Bounds::with_radius(pos.into(), size.into()), stroke)
+    pub fn with_radius(pos: impl IntoPos, size: impl IntoPos, stroke: impl Into<RGBA>) -> Self {
+        Self::new(Bounds2D::with_radius(pos.into_pos(), size.into_pos()), stroke)
     }
 
     pub fn with_fill(self, fill: impl Into<RGBA>) -> Self {
         self.with(|v| v.fill = fill.into())
     }
 
-    pub fn translated(self, delta: Vector<i32, 2>) -> Self {
-        self.with(|v| v.bounds = v.bounds.translated(delta))
+    /// Scale stroke and fill alpha.
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|v| {
+            v.stroke = v.stroke.with_opacity(opacity);
+            v.fill = v.fill.with_opacity(opacity);
+        })
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|v| v.blend = blend)
+    }
+
+    pub fn translated(self, delta: impl IntoPos) -> Self {
+        self.with(|v| v.bounds = v.bounds.translated(delta.into_pos()))
     }
 }
 
-impl<B: Into<Bounds>, S: Into<RGBA>> From<(B, S)> for Rectangle {
+impl<B: IntoBounds, S: Into<RGBA>> From<(B, S)> for Rectangle {
     fn from((bounds, stroke): (B, S)) -> Self {
-        Self::new(bounds.into(), stroke.into())
+        Self::new(bounds, stroke.into())
     }
 }
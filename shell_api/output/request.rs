@@ -0,0 +1,36 @@
+/// Something the game asks the shell to do, because the game core cannot do I/O itself.
+/// Pushed to `Out::requests`, the result arrives as an `InputEvent` on a later tick.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ShellRequest {
+    /// Read a file. Macroquad: from `app_storage/`, web: from the `assets/` URL.
+    /// Answered by `InputEvent::FileLoaded`.
+    ReadFile { path: String },
+
+    /// Let the user choose a file to open (web: upload dialog, macroquad: same as `ReadFile`).
+    /// Answered by `InputEvent::FileLoaded` with the requested `path` (not the chosen file's name).
+    PickFile { path: String },
+
+    /// Write a file. Macroquad: to `app_storage/`, web: download.
+    /// Answered by `InputEvent::ShellMessage`.
+    WriteFile { path: String, data: Vec<u8> },
+
+    /// Save the game (with a thumbnail of the current frame) to a named slot (see `slot_key`).
+    /// `tick` is recorded in the slot's `SaveMeta`. Answered by `InputEvent::ShellMessage`.
+    Save { slot: String, tick: u64 },
+
+    /// Replace the game by the one saved in a slot. Answered by `InputEvent::ShellMessage`.
+    Load { slot: String },
+
+    /// List save slots. Answered by `InputEvent::ShellMessage`.
+    ListSaves,
+
+    /// Persist arbitrary data under a key (see `data_key`).
+    /// Only answered (by `InputEvent::ShellMessage`) on error.
+    Store { key: String, data: Vec<u8> },
+
+    /// Retrieve data persisted with `Store`. Answered by `InputEvent::Fetched`.
+    Fetch { key: String },
+
+    /// Put text on the system clipboard (e.g. copied from the console). Not answered.
+    SetClipboard { text: String },
+}
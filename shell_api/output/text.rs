@@ -0,0 +1,46 @@
+use crate::prelude::*;
+
+/// Text drawn with the renderer's own (monospace) font, unlike `draw_text` which uses the embedded bitmap font.
+/// `scale` 1.0 is about the height of `EMBEDDED_CHAR_SIZE`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Text {
+    /// Top-left corner.
+    pub pos: vec2f,
+    pub text: String,
+    pub color: RGBA,
+    pub scale: f32,
+    pub blend: Blend,
+}
+
+impl Text {
+    pub fn new(pos: impl IntoPos, text: impl Into<String>) -> Self {
+        Self {
+            pos: pos.into_pos(),
+            text: text.into(),
+            color: RGBA::WHITE,
+            scale: 1.0,
+            blend: Blend::Normal,
+        }
+    }
+
+    pub fn with_color(self, color: RGBA) -> Self {
+        self.with(|v| v.color = color)
+    }
+
+    pub fn with_scale(self, scale: f32) -> Self {
+        self.with(|v| v.scale = scale)
+    }
+
+    pub fn with_opacity(self, opacity: f32) -> Self {
+        self.with(|v| v.color = v.color.with_opacity(opacity))
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Self {
+        self.with(|v| v.blend = blend)
+    }
+
+    /// Font size in pixels.
+    pub fn font_size(&self) -> f32 {
+        self.scale * EMBEDDED_CHAR_SIZE.y() as f32
+    }
+}
@@ -9,8 +9,7 @@ pub use proc_macros::*;
 pub use serde::{Deserialize, Serialize};
 pub use vector::*;
 
-pub use std::num::NonZeroU8;
+pub use std::num::NonZeroU16;
 
 pub type HashSet<T> = fnv::FnvHashSet<T>;
 pub type HashMap<K, V> = fnv::FnvHashMap<K, V>;
-pub type Bounds = Bounds2D<i32>;
@@ -0,0 +1,177 @@
+//! Versioned save-game format.
+//!
+//! A save is a small header followed by the bincode-encoded game state:
+//!
+//! ```text
+//! magic        4 bytes   "SAVE"
+//! format       u16       version of this envelope format
+//! type id      u8 + str  identifies the game (`Migrate::TYPE_ID`)
+//! version      u32       schema version of the game state (`Migrate::VERSION`)
+//! checksum     u64       FNV-1a of the payload
+//! payload      ..        bincode
+//! ```
+//!
+//! Older schema versions are upgraded with `Migrate::migrate`.
+//! Saves from before this format existed (bare bincode) are treated as version 0.
+use crate::prelude::*;
+use anyhow::{Context as _, Result, bail, ensure};
+use serde::de::DeserializeOwned;
+use std::hash::Hasher as _;
+
+const MAGIC: &[u8; 4] = b"SAVE";
+const FORMAT: u16 = 1;
+
+/// Save-game schema version and upgrade hook.
+///
+/// When changing a serialized struct: keep a copy of the old struct (e.g. `GameStateV1`),
+/// bump `VERSION` and convert the old struct in `migrate`. E.g.:
+///
+/// ```text
+/// fn migrate(version: u32, payload: &[u8]) -> Result<Self> {
+///     match version {
+///         1 => Ok(decode_payload::<GameStateV1>(payload)?.into()),
+///         _ => bail!("cannot migrate from version {version}"),
+///     }
+/// }
+/// ```
+pub trait Migrate: Serialize + DeserializeOwned {
+    /// Identifies the game, so that saves of other games are rejected.
+    const TYPE_ID: &'static str;
+
+    /// Current schema version.
+    const VERSION: u32;
+
+    /// Decode a payload saved with an older schema `version` (< `VERSION`).
+    fn migrate(version: u32, _payload: &[u8]) -> Result<Self> {
+        bail!("{}: cannot migrate save from version {version} to {}", Self::TYPE_ID, Self::VERSION)
+    }
+}
+
+/// Encode value with header (see module docs).
+pub fn encode_save<T: Migrate>(v: &T) -> Result<Vec<u8>> {
+    let payload = bincode::serde::encode_to_vec(v, bincode::config::standard()).context("encode save")?;
+    let type_id = T::TYPE_ID.as_bytes();
+    ensure!(type_id.len() <= u8::MAX as usize, "type id too long");
+
+    let mut bytes = Vec::with_capacity(payload.len() + 32);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&FORMAT.to_le_bytes());
+    bytes.push(type_id.len() as u8);
+    bytes.extend_from_slice(type_id);
+    bytes.extend_from_slice(&T::VERSION.to_le_bytes());
+    bytes.extend_from_slice(&checksum(&payload).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+/// Decode a save written by `encode_save`, migrating older versions.
+pub fn decode_save<T: Migrate>(bytes: &[u8]) -> Result<T> {
+    let Some(mut r) = bytes.strip_prefix(MAGIC) else {
+        log::warn!("save has no header, trying to migrate from version 0");
+        return T::migrate(0, bytes);
+    };
+
+    let format = u16::from_le_bytes(take(&mut r)?);
+    ensure!(format == FORMAT, "unsupported save format {format}");
+    let [type_id_len] = take(&mut r)?;
+    let type_id = take_slice(&mut r, type_id_len as usize)?;
+    ensure!(type_id == T::TYPE_ID.as_bytes(), "save is for {:?}, not {:?}", String::from_utf8_lossy(type_id), T::TYPE_ID);
+    let version = u32::from_le_bytes(take(&mut r)?);
+    let sum = u64::from_le_bytes(take(&mut r)?);
+    let payload = r;
+    ensure!(checksum(payload) == sum, "save is corrupted (checksum mismatch)");
+
+    match version {
+        v if v == T::VERSION => decode_payload(payload),
+        v if v < T::VERSION => {
+            log::info!("migrating save from version {version} to {}", T::VERSION);
+            T::migrate(version, payload)
+        }
+        _ => bail!("save has version {version}, newer than supported {}", T::VERSION),
+    }
+}
+
+/// Decode a bare bincode payload. For use in `Migrate::migrate`.
+pub fn decode_payload<T: DeserializeOwned>(payload: &[u8]) -> Result<T> {
+    let (v, _) = bincode::serde::decode_from_slice(payload, bincode::config::standard()).context("decode save")?;
+    Ok(v)
+}
+
+fn take<const N: usize>(r: &mut &[u8]) -> Result<[u8; N]> {
+    Ok(take_slice(r, N)?.try_into().expect("length checked"))
+}
+
+fn take_slice<'a>(r: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    ensure!(r.len() >= n, "save is truncated");
+    let (head, tail) = r.split_at(n);
+    *r = tail;
+    Ok(head)
+}
+
+fn checksum(data: &[u8]) -> u64 {
+    let mut hasher = fnv::FnvHasher::default();
+    hasher.write(data);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct V1 {
+        a: u32,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct V2 {
+        a: u32,
+        b: String,
+    }
+
+    impl Migrate for V1 {
+        const TYPE_ID: &'static str = "test";
+        const VERSION: u32 = 1;
+    }
+
+    impl Migrate for V2 {
+        const TYPE_ID: &'static str = "test";
+        const VERSION: u32 = 2;
+
+        fn migrate(version: u32, payload: &[u8]) -> anyhow::Result<Self> {
+            match version {
+                1 => decode_payload::<V1>(payload).map(|v1| V2 { a: v1.a, b: "new".into() }),
+                _ => bail!("cannot migrate from {version}"),
+            }
+        }
+    }
+
+    #[gtest]
+    fn roundtrip() {
+        let v = V2 { a: 1, b: "b".into() };
+        expect_eq!(decode_save::<V2>(&encode_save(&v).unwrap()).unwrap(), v);
+    }
+
+    #[gtest]
+    fn migrate() {
+        let bytes = encode_save(&V1 { a: 7 }).unwrap();
+        expect_eq!(decode_save::<V2>(&bytes).unwrap(), V2 { a: 7, b: "new".into() });
+    }
+
+    #[gtest]
+    fn errors() {
+        // newer than supported
+        let bytes = encode_save(&V2 { a: 1, b: "b".into() }).unwrap();
+        expect_true!(decode_save::<V1>(&bytes).is_err());
+
+        // corrupted
+        let mut bytes = encode_save(&V1 { a: 7 }).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        expect_true!(decode_save::<V1>(&bytes).is_err());
+
+        // truncated, no header
+        expect_true!(decode_save::<V1>(&bytes[..6]).is_err());
+        expect_true!(decode_save::<V2>(b"garbage").is_err());
+    }
+}
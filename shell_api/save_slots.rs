@@ -0,0 +1,90 @@
+//! Named save slots (see `ShellRequest::Save`, `Load`, `ListSaves`).
+//!
+//! Each slot is stored under two keys: `save.<name>` (the game, see `encode_save`)
+//! and `save.<name>.meta` (`SaveMeta`, for listing without decoding the game).
+use crate::prelude::*;
+use anyhow::{Result, ensure};
+
+/// Size (in pixels) of save slot thumbnails.
+pub const THUMBNAIL_SIZE: vec2u = vec2u(96, 64);
+
+/// Information about a save slot, stored next to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SaveMeta {
+    pub name: String,
+    /// Wall-clock time of saving, seconds since the UNIX epoch.
+    pub unix_secs: u64,
+    /// Game tick at the time of saving.
+    pub tick: u64,
+    pub thumbnail: Thumbnail,
+}
+
+/// Downscaled screenshot, RGBA, row-major, top row first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Thumbnail {
+    pub size: vec2u,
+    pub rgba: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// Nearest-neighbor downscale of an RGBA image (top row first) to `THUMBNAIL_SIZE`.
+    pub fn downscale(size: vec2u, rgba: &[u8]) -> Self {
+        if size.x() == 0 || size.y() == 0 || rgba.len() < (size.x() * size.y() * 4) as usize {
+            return Self::default();
+        }
+        let mut thumb = Vec::with_capacity((THUMBNAIL_SIZE.x() * THUMBNAIL_SIZE.y() * 4) as usize);
+        for y in 0..THUMBNAIL_SIZE.y() {
+            for x in 0..THUMBNAIL_SIZE.x() {
+                let src = vec2(x * size.x() / THUMBNAIL_SIZE.x(), y * size.y() / THUMBNAIL_SIZE.y());
+                let i = ((src.y() * size.x() + src.x()) * 4) as usize;
+                thumb.extend_from_slice(&rgba[i..i + 4]);
+            }
+        }
+        Self { size: THUMBNAIL_SIZE, rgba: thumb }
+    }
+}
+
+/// Storage key of the game in save slot `name`.
+/// Names may contain letters, digits, `_`, `-` and `.`.
+pub fn slot_key(name: &str) -> Result<String> {
+    ensure_valid_name(name)?;
+    Ok(format!("save.{name}"))
+}
+
+pub(crate) fn ensure_valid_name(name: &str) -> Result<()> {
+    ensure!(!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c)), "invalid name: {name:?}");
+    Ok(())
+}
+
+/// Storage key of the `SaveMeta` of save slot `name`.
+pub fn meta_key(name: &str) -> Result<String> {
+    Ok(slot_key(name)? + ".meta")
+}
+
+/// Inverse of `meta_key`: slot name if `key` is a meta key.
+pub fn slot_name_from_meta_key(key: &str) -> Option<&str> {
+    key.strip_prefix("save.")?.strip_suffix(".meta")
+}
+
+/// Human-readable listing of save slots, most recent first.
+pub fn format_save_list(mut metas: Vec<SaveMeta>, now_unix_secs: u64) -> String {
+    if metas.is_empty() {
+        return "no saves".into();
+    }
+    metas.sort_by_key(|m| std::cmp::Reverse(m.unix_secs));
+    let width = metas.iter().map(|m| m.name.len()).max().unwrap_or_default();
+    metas
+        .iter()
+        .map(|m| format!("{:width$}  {:>10} ago  tick {}", m.name, format_duration(now_unix_secs.saturating_sub(m.unix_secs)), m.tick))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
@@ -3,7 +3,13 @@ pub(crate) mod prelude;
 mod gamecore_trait;
 mod input;
 mod output;
+mod save_envelope;
+mod save_slots;
+mod storage;
 
 pub use gamecore_trait::*;
 pub use input::*;
 pub use output::*;
+pub use save_envelope::*;
+pub use save_slots::*;
+pub use storage::*;
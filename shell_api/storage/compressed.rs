@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use anyhow::{Result, anyhow};
+
+/// Compresses values (DEFLATE) before passing them to the wrapped `Storage`.
+/// Values stored without compression (no header) are still loaded as-is.
+#[derive(Debug, Clone, Default)]
+pub struct Compressed<S>(pub S);
+
+/// Prefix of compressed values.
+const MAGIC: &[u8; 4] = b"DFL1";
+
+/// miniz compression level (0-10).
+const LEVEL: u8 = 6;
+
+impl<S: Storage> Storage for Compressed<S> {
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&miniz_oxide::deflate::compress_to_vec(data, LEVEL));
+        self.0.save_bytes(key, &bytes)
+    }
+
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        let bytes = self.0.load_bytes(key)?;
+        match bytes.strip_prefix(MAGIC) {
+            Some(compressed) => miniz_oxide::inflate::decompress_to_vec(compressed).map_err(|e| anyhow!("{key:?}: decompress: {e}")),
+            None => Ok(bytes),
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        self.0.remove(key)
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        self.0.keys()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn roundtrip() {
+        let mut s = Compressed(MemStorage::default());
+        let data = vec![42u8; 1000];
+        s.save_bytes("a", &data).unwrap();
+        expect_eq!(s.load_bytes("a").unwrap(), data);
+        expect_lt!(s.0.load_bytes("a").unwrap().len(), 100);
+
+        s.save("b", &(1, "two".to_string())).unwrap();
+        expect_eq!(s.load::<(i32, String)>("b").unwrap(), (1, "two".to_string()));
+
+        // uncompressed values from before
+        s.0.save_bytes("c", b"raw").unwrap();
+        expect_eq!(s.load_bytes("c").unwrap(), b"raw");
+
+        expect_true!(s.load_bytes("nope").is_err());
+        expect_eq!(s.keys().unwrap(), vec!["a", "b", "c"]);
+    }
+}
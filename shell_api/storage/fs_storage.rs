@@ -0,0 +1,53 @@
+use crate::prelude::*;
+use anyhow::{Context as _, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// `Storage` backed by files in a directory (one file per key).
+/// Not available in the browser.
+#[derive(Debug, Clone)]
+pub struct FsStorage {
+    dir: PathBuf,
+}
+
+impl FsStorage {
+    /// Store files in `dir` (created on first save).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+}
+
+impl Storage for FsStorage {
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        log::info!("save {path:?}");
+        fs::create_dir_all(&self.dir).with_context(|| format!("create {:?}", self.dir))?;
+        fs::write(&path, data).with_context(|| format!("write {path:?}"))
+    }
+
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.path(key);
+        log::info!("load {path:?}");
+        fs::read(&path).with_context(|| format!("read {path:?}"))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e).with_context(|| format!("remove {key:?}")),
+            _ => Ok(()),
+        }
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        match fs::read_dir(&self.dir) {
+            Ok(entries) => entries.map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned())).collect(),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e).with_context(|| format!("read {:?}", self.dir)),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Saving and loading games to/from a `Storage`, shared by all shells.
+use crate::prelude::*;
+use anyhow::{Context as _, Result};
+
+/// Save the whole game state under `key` (see `encode_save`).
+pub fn save_game<G: GameCore>(storage: &mut dyn Storage, key: &str, state: &G) -> Result<()> {
+    log::info!("save game {key}");
+    storage.save_bytes(key, &encode_save(state)?)
+}
+
+/// Load (and migrate) the game saved under `key`.
+/// A save that exists but cannot be loaded is backed up (as `<key>.unreadable`), so that it does not get overwritten.
+pub fn load_game<G: GameCore>(storage: &mut dyn Storage, key: &str) -> Option<G> {
+    log::info!("loading... {key}");
+    let bytes = storage.load_bytes(key).map_err(|e| log::info!("load_game {key}: {e:#}")).ok()?;
+    match decode_save(&bytes) {
+        Ok(g) => Some(g),
+        Err(e) => {
+            let backup = format!("{key}.unreadable");
+            log::error!("load_game {key}: {e:#}, backing up to {backup}");
+            storage.save_bytes(&backup, &bytes).map_err(|e| log::error!("backup {backup}: {e:#}")).ok();
+            None
+        }
+    }
+}
+
+/// Save game + metadata to save slot `meta.name`.
+pub fn save_slot<G: GameCore>(storage: &mut dyn Storage, state: &G, meta: &SaveMeta) -> Result<()> {
+    let slot = &meta.name;
+    storage.save_bytes(&slot_key(slot)?, &encode_save(state)?)?;
+    storage.save_bytes(&meta_key(slot)?, &bincode::serde::encode_to_vec(meta, bincode::config::standard())?)
+}
+
+/// Load (and migrate) the game from a named slot.
+pub fn load_slot<G: GameCore>(storage: &dyn Storage, slot: &str) -> Result<G> {
+    decode_save(&storage.load_bytes(&slot_key(slot)?)?).with_context(|| format!("load {slot:?}"))
+}
+
+/// Metadata of all save slots. Unreadable metadata is skipped (with a warning).
+pub fn list_saves(storage: &dyn Storage) -> Result<Vec<SaveMeta>> {
+    let mut metas = Vec::new();
+    for key in storage.keys()? {
+        if slot_name_from_meta_key(&key).is_some() {
+            match storage.load_bytes(&key).and_then(|bytes| decode_payload(&bytes)) {
+                Ok(meta) => metas.push(meta),
+                Err(e) => log::warn!("{key}: {e:#}"),
+            }
+        }
+    }
+    Ok(metas)
+}
+
+/// Storage key for the game's own data (`ShellRequest::Store`, `Fetch`),
+/// kept apart from saves. Same naming rules as save slots.
+pub fn data_key(name: &str) -> Result<String> {
+    ensure_valid_name(name)?;
+    Ok(format!("data.{name}"))
+}
@@ -0,0 +1,27 @@
+use crate::prelude::*;
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+
+/// In-memory `Storage`. Forgets everything when dropped.
+#[derive(Default, Debug, Clone)]
+pub struct MemStorage(pub BTreeMap<String, Vec<u8>>);
+
+impl Storage for MemStorage {
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        self.0.insert(key.to_owned(), data.to_owned());
+        Ok(())
+    }
+
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        self.0.get(key).cloned().ok_or_else(|| anyhow!("{key:?}: not found"))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        self.0.remove(key);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        Ok(self.0.keys().cloned().collect())
+    }
+}
@@ -0,0 +1,11 @@
+mod compressed;
+mod fs_storage;
+mod game_saves;
+mod mem_storage;
+mod storage_trait;
+
+pub use compressed::*;
+pub use fs_storage::*;
+pub use game_saves::*;
+pub use mem_storage::*;
+pub use storage_trait::*;
@@ -0,0 +1,58 @@
+use anyhow::{Context as _, Result};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// 🗄️ Persistent key-value store for byte blobs.
+///
+/// Implemented by each shell's backend (filesystem, browser localStorage / IndexedDB)
+/// and `MemStorage` (tests, fallback). Wrap in `Compressed` to compress values.
+/// Keys are short ASCII names like `savegame` or `save.tank1.meta`.
+pub trait Storage {
+    /// Store data under given key, replacing what was there.
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// Load data stored under given key. Error if not found.
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>>;
+
+    /// Remove a key. Not an error if it did not exist.
+    fn remove(&mut self, key: &str) -> Result<()>;
+
+    /// All keys currently stored, in no particular order.
+    fn keys(&self) -> Result<Vec<String>>;
+
+    /// Serialize a value (bincode) and store it under given key.
+    fn save<T: Serialize>(&mut self, key: &str, v: &T) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let bytes = bincode::serde::encode_to_vec(v, bincode::config::standard()).with_context(|| format!("encode {key:?}"))?;
+        self.save_bytes(key, &bytes)
+    }
+
+    /// Load and deserialize a value stored with `save`.
+    fn load<T: DeserializeOwned>(&self, key: &str) -> Result<T>
+    where
+        Self: Sized,
+    {
+        let bytes = self.load_bytes(key)?;
+        let (v, _) = bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).with_context(|| format!("decode {key:?}"))?;
+        Ok(v)
+    }
+}
+
+impl<S: Storage + ?Sized> Storage for Box<S> {
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        self.as_mut().save_bytes(key, data)
+    }
+
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        self.as_ref().load_bytes(key)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        self.as_mut().remove(key)
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        self.as_ref().keys()
+    }
+}
@@ -3,22 +3,31 @@
 use crate::*;
 
 /// Listen for keyup/keydown events, push them to `VecDeque` for later later consumption.
+/// Keys are physical (`KeyboardEvent.code`), translated to canonical names (`shell_api::KEY_NAMES`).
+/// Typed text arrives separately, see `listen_text`.
 pub(crate) fn listen_keys(events: Shared<VecDeque<InputEvent>>) {
     let events_clone = events.clone();
     let keydown_closure = Closure::<dyn FnMut(_)>::new(move |event: KeyboardEvent| {
-        // mapping to lowercase for consistency with macroquad.
-        if let Ok(key) = Str16::from_str(&event.key().to_ascii_lowercase()) {
-            events_clone.borrow_mut().push_back(InputEvent::Key { button: Button(key), direction: KeyDir::Down });
+        // Tab is used for console completion, don't move focus away from the canvas.
+        if event.key() == "Tab" {
+            event.prevent_default();
         }
-        if let Some(chr) = event_to_chr(event) {
-            events_clone.borrow_mut().push_back(InputEvent::InputCharacter(chr));
+        // Ctrl+S is bound to quicksave, don't open the browser's "save page" dialog.
+        if event.ctrl_key() && event.code() == "KeyS" {
+            event.prevent_default();
+        }
+        if event.is_composing() {
+            return; // 👈 keys belong to the input method
+        }
+        if let Some(button) = key_from_code(&event.code()) {
+            events_clone.borrow_mut().push_back(InputEvent::Key { button, direction: KeyDir::Down });
         }
     });
 
     let events_clone = events.clone();
     let keyup_closure = Closure::<dyn FnMut(_)>::new(move |event: KeyboardEvent| {
-        if let Ok(key) = Str16::from_str(&event.key().to_ascii_lowercase()) {
-            events_clone.borrow_mut().push_back(InputEvent::Key { button: Button(key), direction: KeyDir::Up });
+        if let Some(button) = key_from_code(&event.code()) {
+            events_clone.borrow_mut().push_back(InputEvent::Key { button, direction: KeyDir::Up });
         }
     });
 
@@ -32,17 +41,64 @@ pub(crate) fn listen_keys(events: Shared<VecDeque<InputEvent>>) {
     keyup_closure.forget();
 }
 
-fn event_to_chr(event: KeyboardEvent) -> Option<char> {
-    // ⚠️ This is an approximate hack but otherwise extremely tricky in JS.
-    // Would require shenanigans like a hidden input element that steals focus from canvas.
-    //log::info!("key {}", event.key());
-    match event.key().as_str() {
-        chr if chr.len() == 1 => chr.chars().next(),
-        "Backspace" => Some('\u{0008}'),
-        "Enter" => Some('\u{000D}'),
-        "Escape" => Some('\u{001B}'),
-        _ => None,
+/// Listen for text input: typing, input method (IME) composition and paste.
+///
+/// Only editable elements receive those, so text goes to an invisible textarea,
+/// focused when the canvas is clicked, and emptied after each input.
+pub(crate) fn listen_text(canvas: &HtmlCanvasElement, events: Shared<VecDeque<InputEvent>>) {
+    let document = window().document().unwrap();
+    let textarea = document.create_element("textarea").unwrap().dyn_into::<HtmlTextAreaElement>().unwrap();
+    for (name, value) in [("autocapitalize", "off"), ("autocomplete", "off"), ("spellcheck", "false")] {
+        textarea.set_attribute(name, value).unwrap();
+    }
+    let style = textarea.style();
+    for (name, value) in [("position", "fixed"), ("left", "0"), ("top", "0"), ("width", "1px"), ("height", "1px"), ("opacity", "0"), ("pointer-events", "none")] {
+        style.set_property(name, value).unwrap();
     }
+    document.body().unwrap().append_child(&textarea).unwrap();
+    let _ = textarea.focus();
+
+    // 👇 after mousedown, which would move focus to the canvas.
+    let textarea_clone = textarea.clone();
+    let click = Closure::<dyn FnMut()>::new(move || {
+        let _ = textarea_clone.focus();
+    });
+    canvas.add_event_listener_with_callback("click", click.as_ref().unchecked_ref()).unwrap();
+
+    let take_text = move |textarea: &HtmlTextAreaElement, events: &Shared<VecDeque<InputEvent>>| {
+        let text = textarea.value();
+        if !text.is_empty() {
+            textarea.set_value("");
+            events.borrow_mut().push_back(InputEvent::Text(text));
+        }
+    };
+
+    let (textarea_clone, events_clone) = (textarea.clone(), events.clone());
+    let input = Closure::<dyn FnMut(_)>::new(move |event: web_sys::InputEvent| {
+        if !event.is_composing() {
+            take_text(&textarea_clone, &events_clone); // 👈 typed or pasted
+        }
+    });
+    textarea.add_event_listener_with_callback("input", input.as_ref().unchecked_ref()).unwrap();
+
+    let events_clone = events.clone();
+    let composition_update = Closure::<dyn FnMut(_)>::new(move |event: CompositionEvent| {
+        events_clone.borrow_mut().push_back(InputEvent::Composition(event.data().unwrap_or_default()));
+    });
+    textarea.add_event_listener_with_callback("compositionupdate", composition_update.as_ref().unchecked_ref()).unwrap();
+
+    let (textarea_clone, events_clone) = (textarea.clone(), events.clone());
+    let composition_end = Closure::<dyn FnMut(_)>::new(move |_: CompositionEvent| {
+        events_clone.borrow_mut().push_back(InputEvent::Composition(String::new()));
+        take_text(&textarea_clone, &events_clone);
+    });
+    textarea.add_event_listener_with_callback("compositionend", composition_end.as_ref().unchecked_ref()).unwrap();
+
+    // Keep the closures alive
+    click.forget();
+    input.forget();
+    composition_update.forget();
+    composition_end.forget();
 }
 
 /// Listen for mouse events on a canvas, push them to `VecDeque` for later later consumption.
@@ -51,6 +107,7 @@ pub(crate) fn listen_mouse(canvas: &HtmlCanvasElement, events: Shared<VecDeque<I
     let mousedown = Closure::wrap(Box::new(move |event: MouseEvent| {
         match event.button() {
             0 => events_clone.borrow_mut().push_back(InputEvent::Key { button: K_MOUSE1, direction: KeyDir::Down }),
+            1 => events_clone.borrow_mut().push_back(InputEvent::Key { button: K_MOUSE3, direction: KeyDir::Down }),
             2 => events_clone.borrow_mut().push_back(InputEvent::Key { button: K_MOUSE2, direction: KeyDir::Down }),
             _ => (),
         };
@@ -60,6 +117,7 @@ pub(crate) fn listen_mouse(canvas: &HtmlCanvasElement, events: Shared<VecDeque<I
     let mouseup = Closure::wrap(Box::new(move |event: MouseEvent| {
         match event.button() {
             0 => events_clone.borrow_mut().push_back(InputEvent::Key { button: K_MOUSE1, direction: KeyDir::Up }),
+            1 => events_clone.borrow_mut().push_back(InputEvent::Key { button: K_MOUSE3, direction: KeyDir::Up }),
             2 => events_clone.borrow_mut().push_back(InputEvent::Key { button: K_MOUSE2, direction: KeyDir::Up }),
             _ => (),
         };
@@ -72,6 +130,21 @@ pub(crate) fn listen_mouse(canvas: &HtmlCanvasElement, events: Shared<VecDeque<I
         });
     }) as Box<dyn FnMut(_)>);
 
+    let events_clone = Rc::clone(&events);
+    let wheel = Closure::wrap(Box::new(move |event: WheelEvent| {
+        event.prevent_default(); // 👈 don't scroll the page
+        // 👇 roughly in notches, like macroquad
+        let delta = match event.delta_mode() {
+            WheelEvent::DOM_DELTA_PIXEL => event.delta_y() / 100.0,
+            WheelEvent::DOM_DELTA_LINE => event.delta_y() / 3.0,
+            _ => event.delta_y(),
+        };
+        events_clone.borrow_mut().push_back(InputEvent::MouseWheel { delta: -delta as f32 });
+    }) as Box<dyn FnMut(_)>);
+
+    canvas.add_event_listener_with_callback("wheel", wheel.as_ref().unchecked_ref()).unwrap();
+    wheel.forget();
+
     canvas.add_event_listener_with_callback("mousedown", mousedown.as_ref().unchecked_ref()).unwrap();
     mousedown.forget();
 
@@ -81,3 +154,58 @@ pub(crate) fn listen_mouse(canvas: &HtmlCanvasElement, events: Shared<VecDeque<I
     canvas.add_event_listener_with_callback("mousemove", mousemove.as_ref().unchecked_ref()).unwrap();
     mousemove.forget();
 }
+
+/// Listen for touch events on a canvas, push them to `VecDeque` for later consumption.
+pub(crate) fn listen_touch(canvas: &HtmlCanvasElement, events: Shared<VecDeque<InputEvent>>) {
+    for (name, phase) in [("touchstart", TouchPhase::Start), ("touchmove", TouchPhase::Move), ("touchend", TouchPhase::End), ("touchcancel", TouchPhase::Cancel)] {
+        let events = Rc::clone(&events);
+        let canvas_clone = canvas.clone();
+        let closure = Closure::wrap(Box::new(move |event: TouchEvent| {
+            event.prevent_default(); // 👈 no emulated mouse events, no scrolling
+            let rect = canvas_clone.get_bounding_client_rect();
+            let touches = event.changed_touches();
+            for touch in (0..touches.length()).filter_map(|i| touches.get(i)) {
+                let position = vec2(touch.client_x() as f64 - rect.left(), touch.client_y() as f64 - rect.top()).as_i32();
+                events.borrow_mut().push_back(InputEvent::Touch { id: touch.identifier() as u64, phase, position });
+            }
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(name, closure.as_ref().unchecked_ref()).unwrap();
+        closure.forget();
+    }
+}
+
+/// 🎮 Gamepad state as of the last poll, to turn it into events.
+/// All connected gamepads act as one.
+#[derive(Default)]
+pub(crate) struct Gamepads {
+    buttons: [bool; GAMEPAD_BUTTONS.len()],
+    axes: [f32; GamepadAxis::ALL.len()],
+}
+
+impl Gamepads {
+    /// The browser has no gamepad events for buttons and sticks, poll on each frame.
+    pub(crate) fn poll(&mut self, events: &Shared<VecDeque<InputEvent>>) {
+        let Ok(pads) = window().navigator().get_gamepads() else { return };
+        let pads = pads.iter().filter_map(|pad| pad.dyn_into::<Gamepad>().ok()).filter(Gamepad::connected).collect_vec();
+
+        let mut events = events.borrow_mut();
+        for (i, button) in GAMEPAD_BUTTONS.into_iter().enumerate() {
+            let down = pads.iter().any(|pad| pad.buttons().get(i as u32).dyn_into::<GamepadButton>().is_ok_and(|b| b.pressed()));
+            if down != self.buttons[i] {
+                self.buttons[i] = down;
+                let direction = if down { KeyDir::Down } else { KeyDir::Up };
+                events.push_back(InputEvent::Key { button, direction });
+            }
+        }
+
+        for (i, axis) in GamepadAxis::ALL.into_iter().enumerate() {
+            // 👇 the stick moved furthest, whichever pad (slot) it's on.
+            let values = pads.iter().map(|pad| pad.axes().get(i as u32).as_f64().unwrap_or_default() as f32);
+            let value = values.fold(0.0, |a: f32, b| if b.abs() > a.abs() { b } else { a });
+            if value != self.axes[i] {
+                self.axes[i] = value;
+                events.push_back(InputEvent::GamepadAxis { axis, value });
+            }
+        }
+    }
+}
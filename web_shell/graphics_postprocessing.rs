@@ -31,21 +31,72 @@ pub fn inverse_bloom(canvas: &HtmlCanvasElement, context: &CanvasRenderingContex
     context.restore();
 }
 
-pub fn bloom(canvas: &HtmlCanvasElement, context: &CanvasRenderingContext2d) {
-    if is_safari() {
-        // Safari does not allow dst == src canvas.
-        // Can't be bothered to work around this.
-        return;
+/// Blur radius of the bloom glow (CSS pixels).
+const BLOOM_BLUR: &str = "blur(6px)";
+
+/// Bloom without `ctx.filter`: shrink the frame by this factor, then scale it back up smoothly.
+const BLOOM_DOWNSCALE: u32 = 8;
+
+/// Post-processing effects (`Out::bloom`, `Out::vignette`), applied after drawing the scenegraph.
+pub(crate) struct PostProcessing {
+    /// Offscreen copy of the frame, for effects that read back the canvas.
+    scratch: HtmlCanvasElement,
+    scratch_ctx: CanvasRenderingContext2d,
+    /// Safari can't draw a canvas onto itself, and older versions ignore `ctx.filter`.
+    safari: bool,
+}
+
+impl PostProcessing {
+    pub fn new() -> Self {
+        let scratch = window().document().expect("document").create_element("canvas").expect("canvas").dyn_into::<HtmlCanvasElement>().unwrap();
+        let scratch_ctx = scratch.get_context("2d").expect("context2d").unwrap().dyn_into::<CanvasRenderingContext2d>().unwrap();
+        Self { scratch, scratch_ctx, safari: is_safari() }
     }
 
+    /// Apply the effects selected for this frame.
+    pub fn apply(&mut self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d, out: &Out) {
+        if out.bloom {
+            match self.safari {
+                false => bloom(canvas, ctx),
+                true => self.bloom_downscaled(canvas, ctx),
+            }
+        }
+        if out.vignette {
+            vignette(canvas, ctx);
+        }
+    }
+
+    /// Bloom via the scratch canvas: a smoothly up-scaled thumbnail approximates the blur.
+    fn bloom_downscaled(&mut self, canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) {
+        let (w, h) = (canvas.width(), canvas.height());
+        let (sw, sh) = (u32::max(1, w / BLOOM_DOWNSCALE), u32::max(1, h / BLOOM_DOWNSCALE));
+        if (self.scratch.width(), self.scratch.height()) != (sw, sh) {
+            self.scratch.set_width(sw);
+            self.scratch.set_height(sh);
+        }
+
+        self.scratch_ctx.set_image_smoothing_enabled(true);
+        self.scratch_ctx.clear_rect(0.0, 0.0, sw.as_(), sh.as_());
+        self.scratch_ctx.draw_image_with_html_canvas_element_and_dw_and_dh(canvas, 0.0, 0.0, sw.as_(), sh.as_()).expect("downscale");
+
+        ctx.save();
+        ctx.set_image_smoothing_enabled(true);
+        ctx.set_global_composite_operation("lighter").unwrap();
+        ctx.draw_image_with_html_canvas_element_and_dw_and_dh(&self.scratch, 0.0, 0.0, w.as_(), h.as_()).expect("upscale");
+        ctx.restore();
+    }
+}
+
+/// Add a blurred copy of the canvas onto itself (not on Safari, see `PostProcessing`).
+fn bloom(canvas: &HtmlCanvasElement, context: &CanvasRenderingContext2d) {
     context.save();
     context.set_global_composite_operation("lighter").unwrap();
-    context.set_filter("blur(6px)");
-    context.draw_image_with_html_canvas_element(&canvas, 0.0, 0.0).unwrap();
+    context.set_filter(BLOOM_BLUR);
+    context.draw_image_with_html_canvas_element(canvas, 0.0, 0.0).unwrap();
     context.restore();
 }
 
-pub fn vignette(canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) {
+fn vignette(canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d) {
     let width = canvas.width() as f64;
     let height = canvas.height() as f64;
     let center_x = width / 2.0;
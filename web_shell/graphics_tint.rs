@@ -0,0 +1,43 @@
+//! Sprite tinting (`DrawSprite::tint`). Canvas 2D can't multiply images with a color,
+//! so tinted sprites are first copied to a scratch canvas and tinted there.
+use crate::*;
+
+pub(crate) struct Tinting {
+    scratch: HtmlCanvasElement,
+    scratch_ctx: CanvasRenderingContext2d,
+}
+
+impl Tinting {
+    pub fn new() -> Self {
+        let scratch = window().document().expect("document").create_element("canvas").expect("canvas").dyn_into::<HtmlCanvasElement>().unwrap();
+        let scratch_ctx = scratch.get_context("2d").expect("context2d").unwrap().dyn_into::<CanvasRenderingContext2d>().unwrap();
+        Self { scratch, scratch_ctx }
+    }
+
+    /// The `src` rectangle of `bitmap`, multiplied by `tint` (colors only, alpha is up to `set_global_alpha`),
+    /// in the top-left corner of the returned canvas.
+    pub fn apply(&self, bitmap: &ImageBitmap, src: AtlasRect, tint: RGBA) -> &HtmlCanvasElement {
+        let (w, h) = (src.size.x(), src.size.y());
+        if self.scratch.width() < w || self.scratch.height() < h {
+            self.scratch.set_width(u32::max(w, self.scratch.width()));
+            self.scratch.set_height(u32::max(h, self.scratch.height()));
+        }
+        let ctx = &self.scratch_ctx;
+        let (w, h) = (w as f64, h as f64);
+        let draw = || {
+            ctx.draw_image_with_image_bitmap_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(bitmap, src.pos.x().as_(), src.pos.y().as_(), w, h, 0.0, 0.0, w, h)
+                .expect("draw");
+        };
+
+        ctx.set_image_smoothing_enabled(false);
+        ctx.set_global_composite_operation("copy").unwrap();
+        draw();
+        // 👇 multiply colors, then cut out the sprite's shape again (multiply made the transparent parts opaque).
+        ctx.set_global_composite_operation("multiply").unwrap();
+        ctx.set_fill_style_str(&RGB::new(tint.r(), tint.g(), tint.b()).hex());
+        ctx.fill_rect(0.0, 0.0, w, h);
+        ctx.set_global_composite_operation("destination-in").unwrap();
+        draw();
+        &self.scratch
+    }
+}
@@ -18,27 +18,29 @@ pub fn cmd(cmd: String) {
 }
 
 /// Execute commands consumed from COMMAND_BUFFER,
-/// forward to game state if not JS-specific.
-pub(crate) fn exec_pending_commands<G: GameCore>(state: &mut G) {
+/// forward to game state (as `InputEvent::Command`) if not JS-specific.
+pub(crate) fn exec_pending_commands<G: GameCore>(storage: &mut AppStorage, state: &mut G, input_events: &Shared<VecDeque<InputEvent>>) {
     for cmd in COMMAND_BUFFER.lock().unwrap().drain(..) {
-        match exec_command(state, &cmd) {
-            Ok(()) => log::info!("js command {cmd:?}: OK"),
+        match exec_command(storage, state, &cmd) {
+            Ok(true) => log::info!("js command {cmd:?}: OK"),
+            Ok(false) => input_events.borrow_mut().push_back(InputEvent::Command(cmd)),
             Err(e) => log::info!("js command {cmd:?}: {e:?}"),
         }
     }
 }
 
 // Execute a single command.
-// If unknown, forward to the game state.
-fn exec_command<G: GameCore>(state: &mut G, cmd: &str) -> JsResult<()> {
+// Returns `false` if unknown (to be forwarded to the game state).
+fn exec_command<G: GameCore>(storage: &mut AppStorage, state: &mut G, cmd: &str) -> JsResult<bool> {
     match cmd.trim().split_ascii_whitespace().collect::<Vec<_>>().as_slice() {
-        &["save"] => Ok(save_game(state)),
-        &["reset"] => Ok(reset(state)),
-        &["save_reload"] => Ok(save_reload(state)),
-        &["toggle_large"] => Ok(toggle_large()),
-        &["screenshot"] => download_screenshot("screenshot.png"),
-        _ => Err("unknown command".into()),
+        &["save"] => save_game(storage, APP_KEY, state).map_err(|e| format!("{e:#}"))?,
+        &["reset"] => reset(state),
+        &["save_reload"] => save_reload(storage, state)?,
+        &["toggle_large"] => toggle_large(),
+        &["screenshot"] => download_screenshot("screenshot.png")?,
+        _ => return Ok(false),
     }
+    Ok(true)
 }
 
 // toggle between large & small canvas size.
@@ -63,9 +65,17 @@ fn toggle_large() {
 }
 
 // save + reload command
-fn save_reload<G: GameCore>(state: &G) {
-    save_game(state);
-    window().location().reload().expect("reload");
+fn save_reload<G: GameCore>(storage: &mut AppStorage, state: &G) -> JsResult<()> {
+    save_game(storage, APP_KEY, state).map_err(|e| format!("{e:#}"))?;
+    // 👇 don't reload before the save has hit IndexedDB
+    let flushed = storage.0.flush();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = flushed.await {
+            log::error!("flush: {e:?}");
+        }
+        window().location().reload().expect("reload");
+    });
+    Ok(())
 }
 
 // reset gamestate command
@@ -0,0 +1,186 @@
+//! `Storage` in IndexedDB, which (unlike localStorage) holds hundreds of MB.
+use crate::*;
+use anyhow::Result;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+const DB_NAME: &str = "app_storage";
+const DB_VERSION: u32 = 1;
+const STORE: &str = "kv";
+
+/// IndexedDB is asynchronous, `Storage` is not. So:
+/// everything is read into memory when opening,
+/// writes go to memory immediately and to IndexedDB in the background.
+///
+/// If IndexedDB is not available (e.g. some private browsing modes),
+/// falls back to `LocalStorage`.
+pub struct IdbStorage {
+    cache: MemStorage,
+    backend: Backend,
+}
+
+enum Backend {
+    Idb(IdbDatabase),
+    Local,
+}
+
+impl IdbStorage {
+    /// Open the database and load all contents.
+    /// On first use, copies over what was in localStorage.
+    pub async fn open() -> Self {
+        match Self::open_idb().await {
+            Ok(s) => s,
+            Err(e) => {
+                log::warn!("IndexedDB unavailable, falling back to localStorage: {e:?}");
+                Self {
+                    cache: MemStorage::default(),
+                    backend: Backend::Local,
+                }
+            }
+        }
+    }
+
+    async fn open_idb() -> JsResult<Self> {
+        let factory = window().indexed_db()?.ok_or("no indexedDB")?;
+        let req = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+        let on_upgrade = Closure::<dyn FnMut()>::new({
+            let req = req.clone();
+            move || match req.result().and_then(|db| db.dyn_into::<IdbDatabase>()?.create_object_store(STORE)) {
+                Ok(_) => log::info!("IndexedDB: created {DB_NAME}/{STORE}"),
+                Err(e) => log::error!("IndexedDB upgrade: {e:?}"),
+            }
+        });
+        req.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+        let db = request_done(&req).await?.dyn_into::<IdbDatabase>()?;
+        req.set_onupgradeneeded(None);
+        drop(on_upgrade);
+
+        let mut s = Self {
+            cache: MemStorage::default(),
+            backend: Backend::Idb(db),
+        };
+        s.load_all().await?;
+
+        if s.cache.0.is_empty() {
+            s.migrate_from_local_storage();
+        }
+        Ok(s)
+    }
+
+    /// Read all entries into the cache.
+    async fn load_all(&mut self) -> JsResult<()> {
+        let store = self.store(IdbTransactionMode::Readonly)?;
+        let keys = store.get_all_keys()?;
+        let values = store.get_all()?;
+        // 👇 both sorted by key
+        let keys = js_sys::Array::from(&request_done(&keys).await?);
+        let values = js_sys::Array::from(&request_done(&values).await?);
+        for (key, value) in keys.iter().zip(values.iter()) {
+            let Some(key) = key.as_string() else { continue };
+            self.cache.0.insert(key, Uint8Array::new(&value).to_vec());
+        }
+        log::info!("IndexedDB: loaded {} entries", self.cache.0.len());
+        Ok(())
+    }
+
+    fn migrate_from_local_storage(&mut self) {
+        let Ok(keys) = LocalStorage.keys() else { return };
+        for key in keys {
+            // 👇 skips other values that happen to be in localStorage
+            if let Ok(data) = LocalStorage.load_bytes(&key) {
+                log::info!("IndexedDB: migrating {key:?} from localStorage");
+                self.save_bytes(&key, &data).map_err(|e| log::error!("{e:#}")).ok();
+            }
+        }
+    }
+
+    /// Resolves once all writes issued so far have been committed.
+    /// Await before leaving the page.
+    pub fn flush(&self) -> impl Future<Output = JsResult<()>> + 'static {
+        // 👇 transactions on the same store run in order
+        let req = match &self.backend {
+            Backend::Idb(_) => Some(self.store(IdbTransactionMode::Readonly).and_then(|store| store.count())),
+            Backend::Local => None,
+        };
+        async move {
+            if let Some(req) = req {
+                request_done(&req?).await?;
+            }
+            Ok(())
+        }
+    }
+
+    fn store(&self, mode: IdbTransactionMode) -> JsResult<IdbObjectStore> {
+        match &self.backend {
+            Backend::Idb(db) => db.transaction_with_str_and_mode(STORE, mode)?.object_store(STORE),
+            Backend::Local => Err("not using IndexedDB".into()),
+        }
+    }
+
+    /// Issue a write, log if it fails.
+    fn write(&self, what: String, f: impl FnOnce(&IdbObjectStore) -> JsResult<IdbRequest>) -> Result<()> {
+        let req = self.store(IdbTransactionMode::Readwrite).and_then(|store| f(&store)).map_err(js_err)?;
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = request_done(&req).await {
+                log::error!("IndexedDB {what}: {e:?}");
+            }
+        });
+        Ok(())
+    }
+}
+
+impl Storage for IdbStorage {
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        match &self.backend {
+            Backend::Idb(_) => self.write(format!("put {key:?}"), |store| store.put_with_key(&Uint8Array::from(data), &JsValue::from_str(key)))?,
+            Backend::Local => LocalStorage.save_bytes(key, data)?,
+        }
+        self.cache.save_bytes(key, data)
+    }
+
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        match &self.backend {
+            Backend::Idb(_) => self.cache.load_bytes(key),
+            Backend::Local => LocalStorage.load_bytes(key),
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Idb(_) => self.write(format!("delete {key:?}"), |store| store.delete(&JsValue::from_str(key)))?,
+            Backend::Local => LocalStorage.remove(key)?,
+        }
+        self.cache.remove(key)
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        match &self.backend {
+            Backend::Idb(_) => self.cache.keys(),
+            Backend::Local => LocalStorage.keys(),
+        }
+    }
+}
+
+/// Wait for an IndexedDB request to succeed or fail.
+async fn request_done(req: &IdbRequest) -> JsResult<JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let on_success = Closure::<dyn FnMut()>::once({
+            let req = req.clone();
+            move || {
+                resolve.call1(&JsValue::NULL, &req.result().unwrap_or_default()).ok();
+            }
+        });
+        let on_error = Closure::<dyn FnMut()>::once({
+            let req = req.clone();
+            move || {
+                reject.call1(&JsValue::NULL, &req.error().ok().flatten().map(JsValue::from).unwrap_or_default()).ok();
+            }
+        });
+        req.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+        req.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        // 👇 each called at most once, freed with the request
+        on_success.into_js_value();
+        on_error.into_js_value();
+    });
+    JsFuture::from(promise).await
+}
@@ -10,9 +10,28 @@ pub(crate) fn draw(canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d, r
     ctx.clear_rect(0.0, 0.0, canvas.width().as_(), canvas.height().as_());
 
     // Draw layers starting from 0 for correct Z-ordering.
-    for Layer { sprites, lines, rectangles } in &out.layers {
+    for (
+        Layer {
+            sprites,
+            lines,
+            rectangles,
+            circles,
+            polygons,
+            paths,
+            texts,
+        },
+        world,
+    ) in out.iter_layers()
+    {
+        let [a, b, c, d, e, f] = match world {
+            true => out.camera.transform(out.viewport_size).map(|v| v as f64),
+            false => [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        };
+        ctx.set_transform(a, b, c, d, e, f).expect("set_transform");
+
         // ▭ rectangles
         for rect in rectangles {
+            set_blend(ctx, rect.blend);
             if rect.fill != RGBA::TRANSPARENT {
                 ctx.set_fill_style_str(&rect.fill.hex());
                 ctx.fill_rect(
@@ -39,42 +58,54 @@ pub(crate) fn draw(canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d, r
             }
         }
 
+        // ⬠ polygons
+        for poly in polygons {
+            set_blend(ctx, poly.blend);
+            ctx.begin_path();
+            for (i, p) in poly.points.iter().enumerate() {
+                match i {
+                    0 => ctx.move_to(p.x().as_(), p.y().as_()),
+                    _ => ctx.line_to(p.x().as_(), p.y().as_()),
+                }
+            }
+            ctx.close_path();
+            fill_and_stroke(ctx, poly.fill, poly.stroke, poly.width);
+        }
+
+        // ● circles
+        for circle in circles {
+            set_blend(ctx, circle.blend);
+            ctx.begin_path();
+            ctx.arc(circle.center.x().as_(), circle.center.y().as_(), circle.radius.as_(), 0.0, std::f64::consts::TAU).expect("arc");
+            fill_and_stroke(ctx, circle.fill, circle.stroke, circle.width);
+        }
+
         // 🦀 sprites
         for cmd in sprites {
-            if let Some(bitmap) = res.get(&cmd.sprite) {
-                let dst_size = match cmd.dst_size {
-                    None => vec2(bitmap.width(), bitmap.height()),
-                    Some(dst_size) => dst_size.map(|v| v.get().as_()),
-                };
-
-                let src_size = match cmd.src_pos {
-                    None => vec2(bitmap.width(), bitmap.height()),
-                    Some(_) => dst_size,
-                };
-
-                let source = match cmd.src_pos {
-                    None => vec2d(0.0, 0.0),
-                    Some(src) => src.as_f64(),
-                };
-
-                ctx.draw_image_with_image_bitmap_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
-                    bitmap,
-                    source.x(),         // source x
-                    source.y(),         // source y
-                    src_size.x().as_(), // source width
-                    src_size.y().as_(), // source height
-                    cmd.pos.x().as_(),  // dest x
-                    cmd.pos.y().as_(),  // dest y
-                    dst_size.x().as_(), // dest width
-                    dst_size.y().as_(), // dest height
-                )
+            if let Some((bitmap, src)) = res.source(cmd).map(|(bitmap, src)| (bitmap.clone(), src)) {
+                let [a, b, c, d, e, f] = sprite_transform(cmd, src);
+
+                ctx.save();
+                set_blend(ctx, cmd.blend);
+                ctx.set_global_alpha(cmd.color().a() as f64 / 255.0);
+                ctx.transform(a, b, c, d, e, f).expect("transform"); // 👈 on top of the camera
+                let (sw, sh) = (src.size.x().as_(), src.size.y().as_());
+                match cmd.tint {
+                    RGBA([255, 255, 255, _]) => ctx.draw_image_with_image_bitmap_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(&bitmap, src.pos.x().as_(), src.pos.y().as_(), sw, sh, 0.0, 0.0, 1.0, 1.0),
+                    tint => {
+                        let tinted = res.tinting.apply(&bitmap, src, tint);
+                        ctx.draw_image_with_html_canvas_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(tinted, 0.0, 0.0, sw, sh, 0.0, 0.0, 1.0, 1.0)
+                    }
+                }
                 .expect("draw");
+                ctx.restore();
             }
         }
 
         // ╱ lines
         for line in lines {
             // TODO: need 0.5 pix offset
+            set_blend(ctx, line.blend);
             ctx.begin_path();
             ctx.set_stroke_style_str(&line.color.hex());
             ctx.set_line_width(line.width.as_());
@@ -82,11 +113,105 @@ pub(crate) fn draw(canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d, r
             ctx.line_to(line.end.x().as_(), line.end.y().as_());
             ctx.stroke();
         }
+
+        // 〰 paths
+        ctx.set_line_join("round");
+        ctx.set_line_cap("round");
+        for path in paths {
+            set_blend(ctx, path.blend);
+            ctx.begin_path();
+            ctx.move_to(path.start.x().as_(), path.start.y().as_());
+            for segment in &path.segments {
+                match *segment {
+                    PathSegment::Line(p) => ctx.line_to(p.x().as_(), p.y().as_()),
+                    PathSegment::Cubic { c1, c2, end } => ctx.bezier_curve_to(c1.x().as_(), c1.y().as_(), c2.x().as_(), c2.y().as_(), end.x().as_(), end.y().as_()),
+                }
+            }
+            if path.closed {
+                ctx.close_path();
+            }
+            ctx.set_stroke_style_str(&path.color.hex());
+            ctx.set_line_width(path.width.as_());
+            ctx.stroke();
+        }
+        ctx.set_line_join("miter");
+        ctx.set_line_cap("butt");
+
+        // 🔤 text
+        ctx.set_text_baseline("top");
+        for text in texts {
+            set_blend(ctx, text.blend);
+            ctx.set_font(&format!("{}px monospace", text.font_size()));
+            ctx.set_fill_style_str(&text.color.hex());
+            ctx.fill_text(&text.text, text.pos.x().as_(), text.pos.y().as_()).expect("fill_text");
+        }
+        set_blend(ctx, Blend::Normal);
     }
 
-    if out.bloom {
-        //graphics_postprocessing::bloom(canvas, ctx);
+    ctx.reset_transform().expect("reset_transform");
+
+    res.post.apply(canvas, ctx, out);
+}
+
+/// Transform (`[a, b, c, d, e, f]` as in canvas `transform`) that maps the unit square, where sprites are drawn,
+/// onto `DrawSprite::corners`.
+fn sprite_transform(cmd: &DrawSprite, src: AtlasRect) -> [f64; 6] {
+    let size = match cmd.dst_size {
+        None => src.size.as_f32(),
+        Some(dst_size) => dst_size.map(|v| v.get().as_()),
+    };
+    DrawSprite::unit_square_transform(cmd.corners(size)).map(|v| v as f64)
+}
+
+/// Fill and stroke the current path, skipping transparent colors.
+fn fill_and_stroke(ctx: &CanvasRenderingContext2d, fill: RGBA, stroke: RGBA, width: u8) {
+    if fill != RGBA::TRANSPARENT {
+        ctx.set_fill_style_str(&fill.hex());
+        ctx.fill();
+    }
+    if stroke != RGBA::TRANSPARENT {
+        ctx.set_stroke_style_str(&stroke.hex());
+        ctx.set_line_width(width.as_());
+        ctx.stroke();
+    }
+}
+
+/// Canvas composite operation for a blend mode.
+fn set_blend(ctx: &CanvasRenderingContext2d, blend: Blend) {
+    let op = match blend {
+        Blend::Normal => "source-over",
+        Blend::Add => "lighter",
+        Blend::Multiply => "multiply",
+    };
+    ctx.set_global_composite_operation(op).expect("set_global_composite_operation");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use googletest::prelude::*;
+
+    /// A 4x2 sprite at (10, 20). 👉 Geometry cases are in `DrawSprite`'s `corners` test.
+    fn sprite() -> DrawSprite {
+        DrawSprite::at_pos(sprite!("crab"), (10, 20))
     }
 
-    //graphics_postprocessing::vignette(canvas, ctx);
+    #[gtest]
+    fn sprite_on_unit_square() {
+        let src = AtlasRect { pos: vec2(8, 4), size: vec2(4, 2) };
+        let cmd = sprite().with_flip(true, false).with_scale(1.5).with_pivot((0.25, 1.0)).with_rot(0.7);
+        let [a, b, c, d, e, f] = sprite_transform(&cmd, src);
+        let got = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| (a * x + c * y + e, b * x + d * y + f)); // 👈 image drawn at (0, 0, 1, 1)
+        for (got, want) in got.into_iter().zip(cmd.corners(vec2(4.0, 2.0))) {
+            expect_that!(got.0, near(want.x() as f64, 1e-4));
+            expect_that!(got.1, near(want.y() as f64, 1e-4));
+        }
+    }
+
+    #[gtest]
+    fn dst_size() {
+        let src = AtlasRect { pos: vec2(8, 4), size: vec2(4, 2) };
+        let cmd = sprite().with_size(vec2(8, 8));
+        expect_that!(sprite_transform(&cmd, src), eq([8.0, 0.0, 0.0, 8.0, 10.0, 20.0]));
+    }
 }
@@ -0,0 +1,127 @@
+//! Serve the game's `ShellRequest`s (file I/O, save slots, game data).
+use crate::*;
+
+/// Handle requests issued during the last tick.
+/// To be called after drawing (save thumbnails are taken from the canvas).
+/// Results are queued as input events when ready (HTTP requests are async).
+pub(crate) fn serve_requests<G: GameCore>(storage: &mut AppStorage, g: &mut G, requests: &mut Vec<ShellRequest>, input_events: &Shared<VecDeque<InputEvent>>) {
+    for req in requests.drain(..) {
+        match req {
+            ShellRequest::ReadFile { path } => {
+                let input_events = Rc::clone(input_events);
+                wasm_bindgen_futures::spawn_local(async move {
+                    let url = format!("assets/{path}");
+                    let result = http_get_with_trunk_hack(&url).await.map_err(|e| format!("{url}: {e:?}"));
+                    input_events.borrow_mut().push_back(InputEvent::FileLoaded { path, result });
+                });
+            }
+            ShellRequest::PickFile { path } => {
+                if let Err(e) = upload_file(path, Rc::clone(input_events)) {
+                    log::error!("upload: {e:?}");
+                }
+            }
+            ShellRequest::WriteFile { path, data } => {
+                let msg = match download_file(&path, &data) {
+                    Ok(()) => format!("downloaded {path}"),
+                    Err(e) => format!("download {path}: {e:?}"),
+                };
+                input_events.borrow_mut().push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::Save { slot, tick } => {
+                let meta = SaveMeta {
+                    name: slot.clone(),
+                    unix_secs: unix_secs(),
+                    tick,
+                    thumbnail: canvas_thumbnail().map_err(|e| log::warn!("thumbnail: {e:?}")).unwrap_or_default(),
+                };
+                let msg = match save_slot(storage, g, &meta) {
+                    Ok(()) => format!("saved {slot:?}"),
+                    Err(e) => format!("save {slot:?}: {e:#}"),
+                };
+                input_events.borrow_mut().push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::Load { slot } => {
+                let msg = match load_slot(storage, &slot) {
+                    Ok(loaded) => {
+                        g.load(loaded);
+                        format!("loaded {slot:?}")
+                    }
+                    Err(e) => format!("{e:#}"),
+                };
+                input_events.borrow_mut().push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::ListSaves => {
+                let msg = match list_saves(storage) {
+                    Ok(metas) => format_save_list(metas, unix_secs()),
+                    Err(e) => format!("list saves: {e:#}"),
+                };
+                input_events.borrow_mut().push_back(InputEvent::ShellMessage(msg));
+            }
+            ShellRequest::Store { key, data } => {
+                if let Err(e) = data_key(&key).and_then(|k| storage.save_bytes(&k, &data)) {
+                    input_events.borrow_mut().push_back(InputEvent::ShellMessage(format!("store {key:?}: {e:#}")));
+                }
+            }
+            ShellRequest::Fetch { key } => {
+                let result = data_key(&key).and_then(|k| storage.load_bytes(&k)).map_err(|e| format!("{e:#}"));
+                input_events.borrow_mut().push_back(InputEvent::Fetched { key, result });
+            }
+            ShellRequest::SetClipboard { text } => {
+                let _ = window().navigator().clipboard().write_text(&text); // 👈 a promise, nothing to wait for
+            }
+        }
+    }
+}
+
+/// Offer data as a file download.
+fn download_file(filename: &str, data: &[u8]) -> JsResult<()> {
+    let parts = js_sys::Array::of1(&Uint8Array::from(data));
+    let blob = Blob::new_with_u8_array_sequence(&parts)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let a = window().document().ok_or("no document")?.create_element("a")?.dyn_into::<HtmlAnchorElement>()?;
+    a.set_href(&url);
+    a.set_download(filename);
+    a.click();
+
+    // 👇 not right away: Firefox may cancel the download that `click` just started.
+    let revoke = Closure::<dyn FnMut()>::once(move || _ = Url::revoke_object_url(&url));
+    window().set_timeout_with_callback_and_timeout_and_arguments_0(revoke.as_ref().unchecked_ref(), REVOKE_DELAY_MS)?;
+    revoke.forget();
+    Ok(())
+}
+
+/// How long (milliseconds) download URLs stay valid.
+const REVOKE_DELAY_MS: i32 = 10_000;
+
+/// Open a file dialog, reply with `InputEvent::FileLoaded` (under the requested `path`) when the user has chosen.
+fn upload_file(path: String, input_events: Shared<VecDeque<InputEvent>>) -> JsResult<()> {
+    let input = window().document().ok_or("no document")?.create_element("input")?.dyn_into::<HtmlInputElement>()?;
+    input.set_type("file");
+
+    // 👇 the user closed the dialog without choosing a file.
+    let (path_clone, input_events_clone) = (path.clone(), Rc::clone(&input_events));
+    let on_cancel = Closure::<dyn FnMut()>::once(move || {
+        let result = Err(format!("{path_clone}: cancelled"));
+        input_events_clone.borrow_mut().push_back(InputEvent::FileLoaded { path: path_clone, result });
+    });
+    input.add_event_listener_with_callback("cancel", on_cancel.as_ref().unchecked_ref())?;
+    on_cancel.forget();
+
+    let input_clone = input.clone();
+    let on_change = Closure::<dyn FnMut()>::once(move || {
+        let Some(file) = input_clone.files().and_then(|files| files.get(0)) else { return };
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = match JsFuture::from(file.array_buffer()).await {
+                Ok(buffer) => Ok(Uint8Array::new(&buffer).to_vec()),
+                Err(e) => Err(format!("{}: {e:?}", file.name())),
+            };
+            input_events.borrow_mut().push_back(InputEvent::FileLoaded { path, result });
+        });
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    on_change.forget();
+
+    input.click();
+    Ok(())
+}
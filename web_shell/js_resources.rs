@@ -6,6 +6,9 @@ use std::task::{Context, Poll};
 
 /// Resource loader + cache.
 pub struct Resources {
+    /// All sprites in one bitmap (see `atlas_builder`), if available.
+    atlas: Option<(ImageBitmap, AtlasIndex)>,
+
     /// Loaded sprites, or red square for errored.
     cache: HashMap<Sprite, ImageBitmap>,
 
@@ -14,23 +17,38 @@ pub struct Resources {
 
     /// Replacement sprite to show while loading (debug only).
     while_loading: ImageBitmap,
+
+    /// Scratch canvas for `DrawSprite::tint`.
+    pub(crate) tinting: Tinting,
+
+    /// Bloom, vignette.
+    pub(crate) post: PostProcessing,
 }
 
 impl Resources {
-    pub fn new(while_loading: ImageBitmap) -> Self {
+    pub fn new(while_loading: ImageBitmap, atlas: Option<(ImageBitmap, AtlasIndex)>) -> Self {
         Self {
+            atlas,
             cache: HashMap::default(),
             pending: HashMap::default(),
             while_loading,
+            tinting: Tinting::new(),
+            post: PostProcessing::new(),
         }
     }
 
-    /// Get bitmap for sprite.
+    /// Get bitmap for sprite, and the sprite's region in it (the atlas, or the whole bitmap).
     /// Still loading => returns `None`: can't draw yet, but will succeed soon
     /// Not found => replacement image
-    pub fn get(&mut self, sprite: &Sprite) -> Option<&ImageBitmap> {
+    pub fn get(&mut self, sprite: &Sprite) -> Option<(&ImageBitmap, AtlasRect)> {
+        if let Some((atlas, index)) = &self.atlas
+            && let Some(rect) = index.get(sprite)
+        {
+            return Some((atlas, rect));
+        }
+
         if let Some(bitmap) = self.cache.get(sprite) {
-            return Some(bitmap);
+            return Some((bitmap, AtlasRect::full(vec2(bitmap.width(), bitmap.height()))));
         }
 
         if self.pending.contains_key(sprite) {
@@ -41,7 +59,7 @@ impl Resources {
 
         #[cfg(debug_assertions)]
         {
-            Some(&self.while_loading)
+            Some((&self.while_loading, AtlasRect::full(vec2(self.while_loading.width(), self.while_loading.height()))))
         }
         #[cfg(not(debug_assertions))]
         {
@@ -49,6 +67,18 @@ impl Resources {
         }
     }
 
+    /// Bitmap and source rectangle to draw `cmd` from: the (part of the) sprite, or the current animation frame.
+    /// Animations are only available from the atlas (which holds the sprite sheets).
+    pub fn source(&mut self, cmd: &DrawSprite) -> Option<(&ImageBitmap, AtlasRect)> {
+        match cmd.animation {
+            Some((animation, time)) => {
+                let (atlas, index) = self.atlas.as_ref()?;
+                index.frame(animation, time).map(|src| (atlas, src))
+            }
+            None => self.get(&cmd.sprite).map(|(bitmap, region)| (bitmap, region.source(cmd.src_pos, cmd.dst_size))),
+        }
+    }
+
     /// To be called on each tick. Newly loaded sprites become available.
     pub fn poll(&mut self) {
         // borrow checker song and dance.
@@ -71,6 +101,21 @@ impl Resources {
     }
 }
 
+/// Load the texture atlas (`ATLAS_INDEX`, `ATLAS_IMAGE`) over HTTP.
+/// Without it, sprites are loaded one by one.
+pub async fn load_atlas() -> Option<(ImageBitmap, AtlasIndex)> {
+    let index = match http_get_with_trunk_hack(&format!("assets/{ATLAS_INDEX}")).await {
+        Ok(ron) => AtlasIndex::from_ron(&String::from_utf8_lossy(&ron)).inspect_err(|e| log::error!("{ATLAS_INDEX}: {e:#}")).ok()?,
+        Err(e) => {
+            log::warn!("no texture atlas, loading sprites one by one: {e:?}");
+            return None;
+        }
+    };
+    let bitmap = load_bitmap(&format!("assets/{ATLAS_IMAGE}")).await.inspect_err(|e| log::error!("{ATLAS_IMAGE}: {e:?}")).ok()?;
+    log::info!("texture atlas: {} sprites, {}x{}", index.sprites.len(), index.size, index.size);
+    Some((bitmap, index))
+}
+
 /// load sprite over HTTP, return fallback (red square) on error.
 async fn load_bitmap_or_fallback(sprite: Sprite) -> ImageBitmap {
     let path = format!("assets/{}.png", sprite.file.as_str());
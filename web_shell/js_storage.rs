@@ -1,46 +1,70 @@
 //! Persist data in browser storage.
 use crate::*;
+use anyhow::{Context as _, Result, anyhow};
 use base64::Engine as _;
 
-pub fn save_game<G: GameCore>(state: &G) {
-    log::info!("save... {APP_KEY}");
-    serialize(APP_KEY, state).expect("autosave");
+/// Key of the game saved by the "save" command and loaded on startup.
+pub const APP_KEY: &str = "a_strategy_game_data_v01";
+
+pub type AppStorage = Compressed<IdbStorage>;
+
+/// Open IndexedDB (falls back to localStorage if unavailable).
+pub async fn app_storage() -> AppStorage {
+    Compressed(IdbStorage::open().await)
 }
 
-pub fn load_game<G: GameCore>() -> Option<G> {
-    log::info!("loading... {APP_KEY}");
-    deserialize(APP_KEY).map_err(|e| log::error!("load_game {APP_KEY}: {e:?}")).ok()
+/// `Storage` in the browser's localStorage.
+/// Values are base64-encoded strings, limited to a few MB in total.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStorage;
+
+impl LocalStorage {
+    fn storage() -> Result<web_sys::Storage> {
+        window().local_storage().map_err(js_err)?.ok_or_else(|| anyhow!("no local storage"))
+    }
 }
 
-/// Serialize value to browser storage under given key.
-pub fn serialize<T>(key: &str, v: &T) -> JsResult<()>
-where
-    T: Serialize,
-{
-    let bytes = bincode::serde::encode_to_vec(v, bincode::config::standard()).map_err(|e| format!("encode {key:?}: {e:?}"))?;
-    save_bytes(key, &bytes)
+impl Storage for LocalStorage {
+    fn save_bytes(&mut self, key: &str, data: &[u8]) -> Result<()> {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+        Self::storage()?.set_item(key, &encoded).map_err(js_err).with_context(|| format!("save {key:?}"))
+    }
+
+    fn load_bytes(&self, key: &str) -> Result<Vec<u8>> {
+        let s = Self::storage()?.get_item(key).map_err(js_err)?.ok_or_else(|| anyhow!("{key:?}: not found"))?;
+        base64::engine::general_purpose::STANDARD.decode(&s).with_context(|| format!("decode bytes for {key:?}"))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<()> {
+        Self::storage()?.remove_item(key).map_err(js_err)
+    }
+
+    fn keys(&self) -> Result<Vec<String>> {
+        let storage = Self::storage()?;
+        let n = storage.length().map_err(js_err)?;
+        Ok((0..n).filter_map(|i| storage.key(i).ok().flatten()).collect())
+    }
 }
 
-/// Deserialize from browser storage under given key.
-pub fn deserialize<T>(key: &str) -> JsResult<T>
-where
-    T: DeserializeOwned,
-{
-    let bytes = load_bytes(key)?;
-    let (v, _) = bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).map_err(|e| format!("decode {key:?}: {e:?}"))?;
-    Ok(v)
+/// Seconds since the UNIX epoch (wall clock, unlike the game's time).
+pub fn unix_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
 }
 
-/// Store data to browser storage under given key.
-pub fn save_bytes(key: &str, data: &[u8]) -> JsResult<()> {
-    let storage = window().local_storage().unwrap().unwrap();
-    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
-    storage.set_item(key, &encoded)
+/// Thumbnail of what's currently on the canvas.
+pub fn canvas_thumbnail() -> JsResult<Thumbnail> {
+    let canvas = get_element_by_id::<HtmlCanvasElement>("canvas");
+    let thumb = window().document().ok_or("no document")?.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+    thumb.set_width(THUMBNAIL_SIZE.x());
+    thumb.set_height(THUMBNAIL_SIZE.y());
+    let ctx = thumb.get_context("2d")?.ok_or("no context2d")?.dyn_into::<CanvasRenderingContext2d>()?;
+    let (w, h) = (THUMBNAIL_SIZE.x() as f64, THUMBNAIL_SIZE.y() as f64);
+    ctx.draw_image_with_html_canvas_element_and_dw_and_dh(&canvas, 0.0, 0.0, w, h)?;
+    let rgba = ctx.get_image_data(0.0, 0.0, w, h)?.data().0;
+    Ok(Thumbnail { size: THUMBNAIL_SIZE, rgba })
 }
 
-/// Load data from browser storage under given key.
-pub fn load_bytes(key: &str) -> JsResult<Vec<u8>> {
-    let storage = window().local_storage().unwrap().unwrap();
-    let s = storage.get_item(key)?.unwrap_or_else(|| "load_bytes {key:?}: not found".into());
-    base64::engine::general_purpose::STANDARD.decode(&s).map_err(|e| format!("decode bytes for {key:?}: {e}").into())
+/// Convert a JavaScript error.
+pub fn js_err(e: JsValue) -> anyhow::Error {
+    anyhow!("{e:?}")
 }
@@ -1,43 +1,47 @@
 mod event_listeners;
 mod graphics_postprocessing;
+mod graphics_tint;
 mod http_get;
 mod js_commands;
+mod js_idb;
 mod js_renderer;
+mod js_requests;
 mod js_resources;
 mod js_storage;
 mod load_bitmap;
 mod time;
 
 use event_listeners::*;
+use graphics_postprocessing::*;
+use graphics_tint::*;
 use http_get::*;
 use js_commands::*;
+use js_idb::*;
 use js_renderer::*;
+use js_requests::*;
 use js_resources::*;
 use js_storage::*;
 use load_bitmap::*;
 use shell_api::*;
 use time::*;
 
-use fixed_str::*;
 use vector::*;
 
 pub use itertools::Itertools as _;
 use js_sys::Uint8Array;
 use js_sys::Uint8ClampedArray;
 use num_traits::AsPrimitive as _;
-use serde::{Serialize, de::DeserializeOwned};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::HtmlAnchorElement;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement, HtmlImageElement, ImageBitmap, ImageData, KeyboardEvent, MouseEvent, Request, RequestInit, Response, Window};
+use web_sys::{Blob, CompositionEvent, Gamepad, GamepadButton, HtmlAnchorElement, HtmlInputElement, HtmlTextAreaElement, TouchEvent, Url};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement, HtmlImageElement, ImageBitmap, ImageData, KeyboardEvent, MouseEvent, Request, RequestInit, Response, WheelEvent, Window};
 
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
-use std::str::FromStr as _;
 use std::sync::Mutex;
 
 type JsResult<T> = Result<T, JsValue>;
@@ -65,8 +69,9 @@ pub fn lib_main<G: GameCore>() {
 async fn start<G: GameCore>() -> JsResult<()> {
     log::info!("async fn start spawned. Hello from async Rust.");
 
-    let mut res = Resources::new(fallback_bitmap((0, 0, 255), TILE_SIZE).await.unwrap());
-    let mut g = match load_game() {
+    let mut res = Resources::new(fallback_bitmap((0, 0, 255), TILE_SIZE).await.unwrap(), load_atlas().await);
+    let mut storage = app_storage().await;
+    let mut g = match load_game(&mut storage, APP_KEY) {
         Some(state) => {
             log::info!("game loaded");
             state
@@ -84,6 +89,9 @@ async fn start<G: GameCore>() -> JsResult<()> {
     let input_events = Shared::<VecDeque<InputEvent>>::default();
     listen_keys(Rc::clone(&input_events));
     listen_mouse(&canvas, Rc::clone(&input_events));
+    listen_touch(&canvas, Rc::clone(&input_events));
+    listen_text(&canvas, Rc::clone(&input_events));
+    let mut gamepads = Gamepads::default();
 
     let mut out = Out::default();
 
@@ -92,20 +100,20 @@ async fn start<G: GameCore>() -> JsResult<()> {
         out.clear();
         out.viewport_size = vec2(canvas.width(), canvas.height());
 
+        gamepads.poll(&input_events);
         g.tick(now_micros(), input_events.borrow_mut().drain(..), &mut out);
 
         draw(&canvas, &ctx, &mut res, &out);
+        serve_requests(&mut storage, &mut g, &mut out.requests, &input_events);
 
         get_element_by_id::<HtmlElement>("debug").set_inner_text(&out.debug);
 
-        exec_pending_commands(&mut g);
+        exec_pending_commands(&mut storage, &mut g, &input_events);
     });
 
     Ok(())
 }
 
-const APP_KEY: &str = "a_strategy_game_data_v01";
-
 pub async fn fallback_bitmap((r, g, b): (u8, u8, u8), size: u32) -> Result<ImageBitmap, JsValue> {
     let width = size as usize;
     let height = size as usize;